@@ -0,0 +1,41 @@
+//! Compares how much is written to the terminal for each frame of a ticking clock with
+//! `ClearStrategy::Diff`, which only redraws the characters that changed, and
+//! `ClearStrategy::PerLine`, which redraws every line.
+//!
+//! Run with `cargo bench --bench clock_diff`.
+
+use sketch::{bench::Frames, *};
+use std::{hint::black_box, time::Instant};
+
+const RUNS: u32 = 2_000;
+
+const TIME_STYLE: Style = Style::new().bold();
+
+/// The view of a clock `seconds` after midnight, under a few lines which never change.
+fn clock(seconds: u32) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600 % 24, seconds / 60 % 60, seconds % 60);
+    let time = TIME_STYLE.render(format!("{hours:02}:{minutes:02}:{seconds:02}"));
+    format!("World clock\n\nLondon  {time}\n\nPress q to quit")
+}
+
+fn bench(name: &str, strategy: ClearStrategy) {
+    let mut frames = Frames::new(strategy);
+    // The first frame is always drawn in full.
+    frames.draw(&clock(0));
+
+    let mut bytes = 0;
+    let start = Instant::now();
+    for n in 1..=RUNS {
+        bytes += black_box(frames.draw(&clock(n))).len();
+    }
+    let elapsed = start.elapsed() / RUNS;
+    println!(
+        "{name:<12} {} bytes, {elapsed:?} per frame",
+        bytes / RUNS as usize
+    );
+}
+
+fn main() {
+    bench("diff", ClearStrategy::Diff);
+    bench("per_line", ClearStrategy::PerLine);
+}
@@ -0,0 +1,41 @@
+//! Compares building a view of many styled runs with `Style::render`, which allocates a `String`
+//! for every run, and `Style::render_into`, which appends every run to one buffer.
+//!
+//! Run with `cargo bench --bench styled_runs`.
+
+use sketch::*;
+use std::{hint::black_box, time::Instant};
+
+const RUNS_PER_VIEW: usize = 500;
+const RUNS: u32 = 2_000;
+
+const STYLES: [Style; 3] = [
+    Style::new().bold(),
+    Style::new().fg(Color::Green),
+    Style::new().dim().italic(),
+];
+
+fn bench(name: &str, frame: impl Fn() -> String) {
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        black_box(frame());
+    }
+    println!("{name:<12} {:?} per frame", start.elapsed() / RUNS);
+}
+
+fn main() {
+    bench("render", || {
+        let mut view = String::new();
+        for n in 0..RUNS_PER_VIEW {
+            view += &STYLES[n % STYLES.len()].render("run ");
+        }
+        view
+    });
+    bench("render_into", || {
+        let mut view = String::with_capacity(RUNS_PER_VIEW * 16);
+        for n in 0..RUNS_PER_VIEW {
+            STYLES[n % STYLES.len()].render_into(&mut view, "run ");
+        }
+        view
+    });
+}
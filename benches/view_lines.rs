@@ -0,0 +1,56 @@
+//! Compares building a whole 10,000 line view with only building the lines which fit on a 40 row
+//! terminal using `Model::view_lines`.
+//!
+//! Run with `cargo bench --bench view_lines`.
+
+use sketch::*;
+use std::{hint::black_box, time::Instant};
+
+const LINES: usize = 10_000;
+const ROWS: usize = 40;
+const RUNS: u32 = 200;
+
+const NUMBER_STYLE: Style = Style::new().dim();
+
+struct Log;
+
+impl Log {
+    fn line(&self, n: usize) -> String {
+        format!("{} entry {n}", NUMBER_STYLE.render(format!("{n:>5}")))
+    }
+}
+
+impl Model for Log {
+    fn update(self, _: &Msg) -> (Self, Option<Msg>) {
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        (0..LINES)
+            .map(|n| self.line(n))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn view_lines(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new((0..LINES).map(|n| self.line(n)))
+    }
+}
+
+fn bench(name: &str, frame: impl Fn() -> String) {
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        black_box(frame());
+    }
+    println!("{name:<12} {:?} per frame", start.elapsed() / RUNS);
+}
+
+fn main() {
+    bench("view", || {
+        let view = Log.view();
+        view.split('\n').take(ROWS).collect::<Vec<_>>().join("\n")
+    });
+    bench("view_lines", || {
+        Log.view_lines().take(ROWS).collect::<Vec<_>>().join("\n")
+    });
+}
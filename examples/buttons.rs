@@ -0,0 +1,58 @@
+use sketch::*;
+
+const BUTTON_STYLE: Style = Style::new().reverse();
+const COUNT_STYLE: Style = Style::new().yellow().bold();
+
+fn main() -> std::io::Result<()> {
+    let model = Buttons::default();
+    App::new(model).with_mouse_capture(true).run()?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct Buttons {
+    count: isize,
+    zones: HitMap,
+}
+
+impl Model for Buttons {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if let Some(mouse) = msg.cast::<Mouse>().filter(|m| m.is_left() && m.is_press()) {
+            match self.zones.hit_test(mouse).as_deref() {
+                Some("decrement") => self.count -= 1,
+                Some("increment") => self.count += 1,
+                Some("quit") => return (self, Some(Msg::new(Quit))),
+                _ => {}
+            }
+        }
+        if let Some(key) = msg.cast::<Key>() {
+            if key.code == KeyCode::Char('q') {
+                return (self, Some(Msg::new(Quit)));
+            }
+        }
+
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        // Lay the buttons out in a row below the count, registering where each one is drawn.
+        let mut row = String::new();
+        let mut column = 0;
+        for (name, label) in [
+            ("decrement", " - "),
+            ("increment", " + "),
+            ("quit", " Quit "),
+        ] {
+            let width = label.len() as u16;
+            self.zones.register(name, Rect::new(column, 2, width, 1));
+            row.push_str(&BUTTON_STYLE.render(label));
+            row.push(' ');
+            column += width + 1;
+        }
+
+        format!(
+            "Count: {}\n\n{row}",
+            COUNT_STYLE.render(self.count.to_string())
+        )
+    }
+}
@@ -0,0 +1,48 @@
+use sketch::{widgets::Select, *};
+
+fn main() -> std::io::Result<()> {
+    let model = Palette {
+        select: Select::new([
+            "Open File",
+            "Save File",
+            "Save All",
+            "Close Editor",
+            "Toggle Sidebar",
+            "Show All Commands",
+        ]),
+    };
+    let palette = App::new(model).run()?;
+
+    if let Some(command) = palette.select.chosen() {
+        println!("You chose {command}");
+    }
+    Ok(())
+}
+
+struct Palette {
+    select: Select,
+}
+
+impl Model for Palette {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if let Some(key) = msg.cast::<Key>() {
+            match key.code {
+                KeyCode::Esc => return (self, Some(Msg::new(Quit))),
+                KeyCode::Char('c') if key.with_control() => return (self, Some(Msg::new(Quit))),
+                _ => {}
+            }
+        }
+
+        if msg.is::<widgets::Selected>() {
+            return (self, Some(Msg::new(Quit)));
+        }
+
+        let (select, msg) = self.select.update(msg);
+        self.select = select;
+        (self, msg)
+    }
+
+    fn view(&self) -> String {
+        self.select.view()
+    }
+}
@@ -4,7 +4,8 @@ const COUNTER_STYLE: Style = Style::new().yellow().bold();
 
 fn main() -> std::io::Result<()> {
     let model = Counter::default();
-    App::new(model).run()
+    App::new(model).run()?;
+    Ok(())
 }
 
 #[derive(Default)]
@@ -0,0 +1,75 @@
+use sketch::{
+    widgets::{Help, KeyBinding, KeyMap},
+    *,
+};
+
+const ITEMS: [&str; 3] = ["Apples", "Bananas", "Cherries"];
+
+fn main() -> std::io::Result<()> {
+    let keys = Keys {
+        up: KeyBinding::new([KeyCode::Up, KeyCode::Char('k')]).with_help("↑/k", "up"),
+        down: KeyBinding::new([KeyCode::Down, KeyCode::Char('j')]).with_help("↓/j", "down"),
+        help: KeyBinding::new([KeyCode::Char('?')]).with_help("?", "toggle help"),
+        quit: KeyBinding::new([KeyCode::Char('q'), KeyCode::Esc]).with_help("q", "quit"),
+    };
+    let model = Menu {
+        selected: 0,
+        help: Help::new(keys.key_map()),
+        keys,
+    };
+    App::new(model).run()?;
+    Ok(())
+}
+
+struct Keys {
+    up: KeyBinding,
+    down: KeyBinding,
+    help: KeyBinding,
+    quit: KeyBinding,
+}
+
+impl Keys {
+    fn key_map(&self) -> KeyMap {
+        KeyMap::new()
+            .with_group("Navigation", [self.up.clone(), self.down.clone()])
+            .with_group("Actions", [self.help.clone(), self.quit.clone()])
+    }
+}
+
+struct Menu {
+    selected: usize,
+    keys: Keys,
+    help: Help,
+}
+
+impl Model for Menu {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if let Some(key) = msg.cast::<Key>() {
+            if self.keys.up.matches(key) {
+                self.selected = self.selected.saturating_sub(1);
+            } else if self.keys.down.matches(key) {
+                self.selected = (self.selected + 1).min(ITEMS.len() - 1);
+            } else if self.keys.help.matches(key) {
+                self.help.toggle();
+            } else if self.keys.quit.matches(key) {
+                return (self, Some(Msg::new(Quit)));
+            }
+        }
+
+        let (help, msg) = self.help.update(msg);
+        self.help = help;
+        (self, msg)
+    }
+
+    fn view(&self) -> String {
+        let items: Vec<String> = ITEMS
+            .iter()
+            .enumerate()
+            .map(|(n, item)| match n == self.selected {
+                true => format!("> {item}"),
+                false => format!("  {item}"),
+            })
+            .collect();
+        format!("{}\n\n{}", items.join("\n"), self.help.view())
+    }
+}
@@ -0,0 +1,51 @@
+use sketch::*;
+
+const HEADER_STYLE: Style = Style::new().bold();
+const CHOSEN_STYLE: Style = Style::new().green();
+
+fn main() -> std::io::Result<()> {
+    let header = format!(
+        "{}\nUp and down to choose, enter to confirm",
+        HEADER_STYLE.render("Pick a flavour")
+    );
+    let picker = App::new(Picker::default())
+        .with_inline(true)
+        .with_header(header)
+        .run()?;
+    println!("You picked {}", FLAVOURS[picker.selected]);
+    Ok(())
+}
+
+const FLAVOURS: [&str; 3] = ["Vanilla", "Chocolate", "Strawberry"];
+
+#[derive(Default)]
+struct Picker {
+    selected: usize,
+}
+
+impl Model for Picker {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if let Some(key) = msg.cast::<Key>() {
+            match key.code {
+                KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Down => self.selected = (self.selected + 1).min(FLAVOURS.len() - 1),
+                KeyCode::Enter => return (self, Some(Msg::new(Quit))),
+                _ => {}
+            }
+        }
+
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        FLAVOURS
+            .iter()
+            .enumerate()
+            .map(|(n, flavour)| match n == self.selected {
+                true => CHOSEN_STYLE.render(format!("> {flavour}")),
+                false => format!("  {flavour}"),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
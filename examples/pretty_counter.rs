@@ -5,7 +5,8 @@ const COUNTER_STYLE: Style = Style::new().yellow().bold();
 
 fn main() -> std::io::Result<()> {
     let model = Counter::default();
-    App::new(model).run()
+    App::new(model).run()?;
+    Ok(())
 }
 
 #[derive(Default)]
@@ -28,18 +29,13 @@ impl Model for Counter {
     }
 
     fn view(&self) -> String {
-        let mut s = String::new();
         let (_, rows) = terminal_size().unwrap();
 
-        s.push_str(&"\n".repeat(rows as usize / 2));
-
         let content = format!(
             "{} {}",
             &TITLE_STYLE.render("Count:"),
             &COUNTER_STYLE.render(self.count.to_string())
         );
-        s.push_str(&Style::new().center().render(content));
-
-        s
+        vertical_center(&Style::new().center().render(content), rows as usize)
     }
 }
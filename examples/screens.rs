@@ -0,0 +1,66 @@
+use sketch::*;
+
+const TITLE_STYLE: Style = Style::new().bold();
+const MUTED_STYLE: Style = Style::new().dim();
+
+fn main() -> std::io::Result<()> {
+    let screens = ScreenStack::new(Screen::Menu { deleted: 0 });
+    App::new(screens).run()?;
+    Ok(())
+}
+
+enum Screen {
+    Menu { deleted: usize },
+    Confirm,
+}
+
+/// The answer from the confirm screen, given to the menu once it is popped.
+struct Confirmed(bool);
+impl Message for Confirmed {}
+
+impl Model for Screen {
+    fn update(self, msg: &Msg) -> (Self, Option<Msg>) {
+        match self {
+            Screen::Menu { deleted } => {
+                if let Some(Confirmed(true)) = msg.cast::<Confirmed>() {
+                    return (
+                        Screen::Menu {
+                            deleted: deleted + 1,
+                        },
+                        None,
+                    );
+                }
+                let msg = msg.cast::<Key>().and_then(|key| match key.code {
+                    KeyCode::Char('d') => Some(Msg::new(PushScreen::new(Screen::Confirm))),
+                    KeyCode::Char('q') => Some(Msg::new(Quit)),
+                    _ => None,
+                });
+                (self, msg)
+            }
+            Screen::Confirm => {
+                let msg = msg.cast::<Key>().and_then(|key| match key.code {
+                    KeyCode::Char('y') => Some(Confirmed(true)),
+                    KeyCode::Char('n') | KeyCode::Esc => Some(Confirmed(false)),
+                    _ => None,
+                });
+                let msg = msg.map(|answer| Msg::new(PopScreen::with_result(Msg::new(answer))));
+                (self, msg)
+            }
+        }
+    }
+
+    fn view(&self) -> String {
+        match self {
+            Screen::Menu { deleted } => format!(
+                "{}\n\nDeleted {deleted} files\n\n{}",
+                TITLE_STYLE.render("Files"),
+                MUTED_STYLE.render("d delete • q quit"),
+            ),
+            Screen::Confirm => format!(
+                "{}\n\n{}",
+                TITLE_STYLE.render("Delete this file?"),
+                MUTED_STYLE.render("y yes • n no"),
+            ),
+        }
+    }
+}
@@ -1,45 +1,37 @@
-use sketch::{Key, KeyCode, Msg, Quit, Style};
-
-const TEXT_STYLE: Style = Style::new();
+use sketch::{widgets::TextInput, CursorShape, Key, KeyCode, Msg, Quit, SetCursorShape};
 
 fn main() -> std::io::Result<()> {
-    let model = Model::default();
-    sketch::App::new(model).run()
+    let model = Model {
+        input: TextInput::new().with_prompt("Name: "),
+    };
+    let model = sketch::App::new(model).run()?;
+    println!("Hello {}", model.input.value());
+    Ok(())
 }
 
-#[derive(Debug, Default)]
 struct Model {
-    text: String,
-    cursor: usize,
+    input: TextInput,
 }
 
 impl sketch::Model for Model {
+    fn startup(&self) -> Option<Msg> {
+        // Use a bar cursor while typing.
+        Some(Msg::new(SetCursorShape(CursorShape::Bar)))
+    }
+
     fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
         if let Some(key) = msg.cast::<Key>() {
-            match key.code {
-                KeyCode::Char('c') if key.with_control() => return (self, Some(Msg::new(Quit))),
-                KeyCode::Char(c) => {
-                    self.text.push(c);
-                    self.cursor += 1;
-                }
-                KeyCode::Backspace if self.cursor > 0 => {
-                    self.text.remove(self.cursor - 1);
-                    self.cursor -= 1;
-                }
-                KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
-                KeyCode::Right if self.cursor < self.text.len() - 1 => self.cursor += 1,
-                KeyCode::Enter => {
-                    self.text.push('\n');
-                    self.cursor += 1
-                }
-                _ => {}
+            if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                return (self, Some(Msg::new(Quit)));
             }
         }
 
-        (self, None)
+        let (input, msg) = self.input.update(msg);
+        self.input = input;
+        (self, msg)
     }
 
     fn view(&self) -> String {
-        TEXT_STYLE.render(&self.text)
+        sketch::Model::view(&self.input)
     }
 }
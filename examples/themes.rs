@@ -0,0 +1,65 @@
+use sketch::*;
+
+fn main() -> std::io::Result<()> {
+    let model = Themes {
+        dark: true,
+        theme: dark_theme(),
+    };
+    App::new(model).run()?;
+    Ok(())
+}
+
+fn dark_theme() -> Theme {
+    Theme::default()
+        .with("title", Style::new().yellow().bold())
+        .with("text", Style::new().white().bg(Color::Black))
+        .with("muted", Style::new().dark_grey())
+}
+
+fn light_theme() -> Theme {
+    Theme::default()
+        .with("title", Style::new().dark_blue().bold())
+        .with("text", Style::new().black().bg(Color::White))
+        .with("muted", Style::new().grey())
+}
+
+struct Themes {
+    dark: bool,
+    theme: Theme,
+}
+
+impl Model for Themes {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if let Some(key) = msg.cast::<Key>() {
+            match key.code {
+                KeyCode::Char('t') if key.is_press() => {
+                    self.dark = !self.dark;
+                    self.theme = if self.dark {
+                        dark_theme()
+                    } else {
+                        light_theme()
+                    };
+                }
+                KeyCode::Char('q') => return (self, Some(Msg::new(Quit))),
+                KeyCode::Char('c') if key.with_control() => return (self, Some(Msg::new(Quit))),
+                _ => {}
+            }
+        }
+
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        let name = if self.dark { "Dark" } else { "Light" };
+        format!(
+            "{}\n\n{}\n\n{}",
+            self.theme.get("title").render(format!("{name} theme")),
+            self.theme
+                .get("text")
+                .render(" Styles are looked up by name. "),
+            self.theme
+                .get("muted")
+                .render("Press t to switch theme, q to quit"),
+        )
+    }
+}
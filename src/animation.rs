@@ -0,0 +1,154 @@
+/// Easing functions for use with [`Animation`].
+///
+/// Each takes how far through an animation it is, from `0.0` at the start to `1.0` at the end,
+/// and returns how far between the start and end values it should be at that point.
+pub mod ease {
+    /// Moves at a constant speed.
+    pub fn linear(t: f64) -> f64 {
+        t
+    }
+
+    /// Starts slowly and speeds up.
+    pub fn ease_in(t: f64) -> f64 {
+        t * t * t
+    }
+
+    /// Starts quickly and slows down.
+    pub fn ease_out(t: f64) -> f64 {
+        1.0 - (1.0 - t).powi(3)
+    }
+
+    /// Starts slowly, speeds up, then slows down again.
+    pub fn ease_in_out(t: f64) -> f64 {
+        if t < 0.5 {
+            4.0 * t * t * t
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+        }
+    }
+}
+
+/// A value moving from one number to another over a number of [`Tick`](crate::Tick)s.
+///
+/// Call [`Animation::tick`] when your model is given a [`Tick`](crate::Tick) message and use
+/// [`Animation::value`] in your view.
+///
+/// ```
+/// # use sketch::*;
+/// let mut animation = Animation::new(0.0, 100.0, 4).with_easing(ease::linear);
+/// animation.tick();
+/// assert_eq!(animation.value(), 25.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Animation {
+    from: f64,
+    to: f64,
+    ticks: u32,
+    elapsed: u32,
+    easing: fn(f64) -> f64,
+}
+
+impl Animation {
+    /// Create an [`Animation`] from `from` to `to` lasting `ticks` ticks.
+    pub fn new(from: f64, to: f64, ticks: u32) -> Self {
+        Self {
+            from,
+            to,
+            ticks,
+            elapsed: 0,
+            easing: ease::ease_in_out,
+        }
+    }
+
+    /// Set the easing function, such as one from [`ease`]. Defaults to [`ease::ease_in_out`].
+    pub fn with_easing(mut self, easing: fn(f64) -> f64) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Move the animation on by one tick, doing nothing once it has finished.
+    pub fn tick(&mut self) {
+        self.elapsed = (self.elapsed + 1).min(self.ticks);
+    }
+
+    /// How far through the animation it is, from `0.0` to `1.0`, before easing.
+    pub fn progress(&self) -> f64 {
+        if self.ticks == 0 {
+            return 1.0;
+        }
+        self.elapsed as f64 / self.ticks as f64
+    }
+
+    /// Get the current value.
+    pub fn value(&self) -> f64 {
+        if self.finished() {
+            return self.to;
+        }
+        self.from + (self.to - self.from) * (self.easing)(self.progress())
+    }
+
+    /// Whether the animation has reached its end value.
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.ticks
+    }
+
+    /// Start the animation again from its current value to `to`.
+    ///
+    /// This is useful when the target changes part way through, such as a progress bar.
+    pub fn retarget(&mut self, to: f64) {
+        self.from = self.value();
+        self.to = to;
+        self.elapsed = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ease_endpoints() {
+        for easing in [
+            ease::linear,
+            ease::ease_in,
+            ease::ease_out,
+            ease::ease_in_out,
+        ] {
+            assert_eq!(easing(0.0), 0.0);
+            assert_eq!(easing(1.0), 1.0);
+        }
+        assert_eq!(ease::ease_in_out(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_animation_finishes() {
+        let mut animation = Animation::new(10.0, 20.0, 2);
+        assert_eq!(animation.value(), 10.0);
+        animation.tick();
+        assert_eq!(animation.value(), 15.0);
+        assert!(!animation.finished());
+        animation.tick();
+        animation.tick();
+        assert!(animation.finished());
+        assert_eq!(animation.value(), 20.0);
+    }
+
+    #[test]
+    fn test_animation_retarget() {
+        let mut animation = Animation::new(0.0, 10.0, 2).with_easing(ease::linear);
+        animation.tick();
+        animation.retarget(0.0);
+        assert_eq!(animation.value(), 5.0);
+        assert!(!animation.finished());
+        animation.tick();
+        animation.tick();
+        assert_eq!(animation.value(), 0.0);
+    }
+
+    #[test]
+    fn test_zero_ticks_is_finished() {
+        let animation = Animation::new(0.0, 1.0, 0);
+        assert!(animation.finished());
+        assert_eq!(animation.value(), 1.0);
+    }
+}
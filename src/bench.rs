@@ -0,0 +1,23 @@
+//! Internals used by the benches in `benches/`, these aren't part of the public API and can change
+//! at any time.
+
+use crate::{render::Renderer, ClearStrategy};
+
+/// Draws frames the way an app does, without a terminal.
+pub struct Frames(Renderer);
+
+impl Frames {
+    /// Create a new [`Frames`] which draws with `strategy`.
+    pub fn new(strategy: ClearStrategy) -> Self {
+        Self(Renderer::new(strategy))
+    }
+
+    /// Draw the next frame, returning what would be written to the terminal.
+    pub fn draw(&mut self, view: &str) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.0
+            .draw(&mut out, view, None)
+            .expect("writing to a Vec can't fail");
+        out
+    }
+}
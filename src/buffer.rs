@@ -0,0 +1,267 @@
+use crate::{
+    capabilities,
+    text::{char_width, RESET},
+    Capabilities, Style,
+};
+
+/// A single cell of a [`Buffer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    /// The character in this cell, or `None` if the cell is covered by the wide character to its
+    /// left.
+    symbol: Option<char>,
+    style: Style,
+}
+
+impl Cell {
+    /// A space with the given style.
+    fn blank(style: Style) -> Self {
+        Self {
+            symbol: Some(' '),
+            style,
+        }
+    }
+
+    /// The character in this cell, or `None` if it is the right half of a wide character.
+    pub fn symbol(&self) -> Option<char> {
+        self.symbol
+    }
+
+    /// The style of this cell.
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// The number of columns this cell's character takes up.
+    fn width(&self) -> usize {
+        self.symbol.map_or(0, char_width)
+    }
+}
+
+/// A grid of styled cells which can be drawn to at any position and then rendered to a string.
+///
+/// Wide characters such as CJK and emoji take up two cells, the second of which is covered by the
+/// first. Writing over either half of a wide character replaces the other half with a space so
+/// the rendered output never contains half a character.
+///
+/// ```
+/// # use sketch::{Buffer, Style};
+/// let mut buffer = Buffer::new(6, 2);
+/// buffer.set_str(0, 0, "你好", Style::new());
+/// buffer.set(1, 0, 'a', Style::new());
+/// assert_eq!(buffer.render(), " a好  \n      ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Buffer {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Buffer {
+    /// Create a new buffer filled with spaces.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::blank(Style::new()); width * height],
+        }
+    }
+
+    /// The number of columns in the buffer.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows in the buffer.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the cell at a position, or `None` if it is outside the buffer.
+    pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+        self.index(x, y).map(|i| &self.cells[i])
+    }
+
+    /// Write a character at a position, returning the number of columns it took up.
+    ///
+    /// Characters outside the buffer are ignored, as are zero width characters. A wide character
+    /// which would only half fit on the last column is replaced with a space.
+    pub fn set(&mut self, x: usize, y: usize, c: char, style: Style) -> usize {
+        let Some(i) = self.index(x, y) else {
+            return 0;
+        };
+
+        match char_width(c) {
+            0 => 0,
+            2 if x + 1 < self.width => {
+                self.clear_overlap(i, x);
+                self.clear_overlap(i + 1, x + 1);
+                self.cells[i + 1] = Cell {
+                    symbol: None,
+                    style: style.clone(),
+                };
+                self.cells[i] = Cell {
+                    symbol: Some(c),
+                    style,
+                };
+                2
+            }
+            1 => {
+                self.clear_overlap(i, x);
+                self.cells[i] = Cell {
+                    symbol: Some(c),
+                    style,
+                };
+                1
+            }
+            _ => {
+                self.clear_overlap(i, x);
+                self.cells[i] = Cell::blank(style);
+                1
+            }
+        }
+    }
+
+    /// Write a string starting at a position, returning the number of columns it took up.
+    ///
+    /// The string is not wrapped, anything past the end of the row is cut off.
+    pub fn set_str(&mut self, x: usize, y: usize, text: &str, style: Style) -> usize {
+        let mut cols = 0;
+        for c in text.chars() {
+            if x + cols >= self.width {
+                break;
+            }
+            cols += self.set(x + cols, y, c, style.clone());
+        }
+        cols
+    }
+
+    /// Fill the whole buffer with spaces.
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::blank(Style::new()));
+    }
+
+    /// Render the buffer to a string with a line for each row.
+    pub fn render(&self) -> String {
+        self.render_with(capabilities())
+    }
+
+    fn render_with(&self, capabilities: Capabilities) -> String {
+        let mut result = String::new();
+
+        for (y, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            if y > 0 {
+                result.push('\n');
+            }
+
+            let mut active = String::new();
+            for cell in row {
+                let Some(symbol) = cell.symbol else {
+                    continue;
+                };
+                let codes = cell.style.codes(capabilities);
+                if codes != active {
+                    if !active.is_empty() {
+                        result.push_str(RESET);
+                    }
+                    result.push_str(&codes);
+                    active = codes;
+                }
+                result.push(symbol);
+            }
+            if !active.is_empty() {
+                result.push_str(RESET);
+            }
+        }
+
+        result
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// Replace the other half of any wide character overlapping the cell at `i` with a space.
+    fn clear_overlap(&mut self, i: usize, x: usize) {
+        let cell = &self.cells[i];
+        if cell.symbol.is_none() && x > 0 {
+            let left = &mut self.cells[i - 1];
+            *left = Cell::blank(left.style.clone());
+        } else if cell.width() == 2 && x + 1 < self.width {
+            let right = &mut self.cells[i + 1];
+            *right = Cell::blank(right.style.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(buffer: &Buffer, y: usize) -> String {
+        (0..buffer.width())
+            .filter_map(|x| buffer.get(x, y).unwrap().symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_wide_char_covers_two_cells() {
+        let mut buffer = Buffer::new(4, 1);
+        assert_eq!(buffer.set(1, 0, '你', Style::new()), 2);
+        assert_eq!(buffer.get(1, 0).unwrap().symbol(), Some('你'));
+        assert_eq!(buffer.get(2, 0).unwrap().symbol(), None);
+        assert_eq!(row(&buffer, 0), " 你 ");
+    }
+
+    #[test]
+    fn test_narrow_over_left_half() {
+        let mut buffer = Buffer::new(4, 1);
+        buffer.set_str(0, 0, "你好", Style::new());
+        buffer.set(0, 0, 'a', Style::new());
+        assert_eq!(row(&buffer, 0), "a 好");
+    }
+
+    #[test]
+    fn test_narrow_over_right_half() {
+        let mut buffer = Buffer::new(4, 1);
+        buffer.set_str(0, 0, "你好", Style::new());
+        buffer.set(1, 0, 'a', Style::new());
+        assert_eq!(row(&buffer, 0), " a好");
+        buffer.set(3, 0, 'b', Style::new());
+        assert_eq!(row(&buffer, 0), " a b");
+    }
+
+    #[test]
+    fn test_wide_over_offset_wide() {
+        let mut buffer = Buffer::new(6, 1);
+        buffer.set_str(0, 0, "你好世", Style::new());
+        buffer.set(1, 0, '界', Style::new());
+        assert_eq!(row(&buffer, 0), " 界 世");
+        buffer.set(4, 0, '👍', Style::new());
+        assert_eq!(row(&buffer, 0), " 界 👍");
+    }
+
+    #[test]
+    fn test_wide_on_last_column() {
+        let mut buffer = Buffer::new(3, 1);
+        assert_eq!(buffer.set_str(0, 0, "ab你", Style::new()), 3);
+        assert_eq!(row(&buffer, 0), "ab ");
+    }
+
+    #[test]
+    fn test_outside_is_ignored() {
+        let mut buffer = Buffer::new(2, 1);
+        assert_eq!(buffer.set(2, 0, 'a', Style::new()), 0);
+        assert_eq!(buffer.set(0, 1, 'a', Style::new()), 0);
+        assert_eq!(buffer.set_str(1, 0, "abc", Style::new()), 1);
+        assert_eq!(row(&buffer, 0), " a");
+    }
+
+    #[test]
+    fn test_render_styles() {
+        let mut buffer = Buffer::new(3, 1);
+        buffer.set(1, 0, 'a', Style::new().reverse());
+        assert_eq!(buffer.render_with(Capabilities::all()), " \x1b[7ma\x1b[0m ");
+    }
+}
@@ -0,0 +1,330 @@
+use std::sync::{OnceLock, RwLock};
+
+/// The capabilities in use, or `None` if they haven't been set or detected yet.
+static CAPABILITIES: RwLock<Option<Capabilities>> = RwLock::new(None);
+
+/// What the terminal is able to display and which features it supports.
+///
+/// [`Style::render`](crate::Style::render) leaves out any attributes the terminal doesn't
+/// support so text stays readable on limited terminals such as the Linux console, which shows
+/// italics as a color or not at all. The other capabilities are for apps and widgets to check
+/// with [`capabilities`] so they can adapt, such as not showing a link which can't be clicked.
+///
+/// [`App::run`](crate::App::run) guesses them from the environment with
+/// [`Capabilities::from_env`] if they haven't been set already, set them yourself with
+/// [`App::with_capabilities`](crate::App::with_capabilities) or [`set_capabilities`] if the
+/// guess is wrong.
+///
+/// ```
+/// # use sketch::Capabilities;
+/// let capabilities = Capabilities {
+///     italic: false,
+///     ..Capabilities::all()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Can text be colored. This is off if the `NO_COLOR` environment variable is set, see
+    /// <https://no-color.org>.
+    pub color: bool,
+    /// Can text be bold.
+    pub bold: bool,
+    /// Can text be dim.
+    pub dim: bool,
+    /// Can text be italic.
+    pub italic: bool,
+    /// Can text be crossed out.
+    pub crossed_out: bool,
+    /// Can 24-bit [`Color::Rgb`](crate::Color::Rgb) colors be shown, rather than being
+    /// rounded to the nearest of 256 colors.
+    pub truecolor: bool,
+    /// Can OSC 8 hyperlinks be clicked.
+    pub hyperlinks: bool,
+    /// Can the system clipboard be set with [`SetClipboard`](crate::SetClipboard).
+    pub clipboard: bool,
+    /// Does the terminal support the kitty keyboard protocol, which reports keys such as
+    /// ctrl+enter and key releases that otherwise can't be told apart.
+    pub kitty_keyboard: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl Capabilities {
+    /// Every capability is supported.
+    pub const fn all() -> Self {
+        Self {
+            color: true,
+            bold: true,
+            dim: true,
+            italic: true,
+            crossed_out: true,
+            truecolor: true,
+            hyperlinks: true,
+            clipboard: true,
+            kitty_keyboard: true,
+        }
+    }
+
+    /// No capabilities are supported.
+    pub const fn none() -> Self {
+        Self {
+            color: false,
+            bold: false,
+            dim: false,
+            italic: false,
+            crossed_out: false,
+            truecolor: false,
+            hyperlinks: false,
+            clipboard: false,
+            kitty_keyboard: false,
+        }
+    }
+
+    /// Guess the capabilities from environment variables such as `TERM`, `COLORTERM` and
+    /// `TERM_PROGRAM`.
+    ///
+    /// The text attributes are guessed with [`Capabilities::from_term`]. The other capabilities
+    /// are only assumed for terminals which are known to support them, except for truecolor
+    /// which is also assumed if `COLORTERM` is `truecolor` or `24bit`. Color is turned off if
+    /// `NO_COLOR` is set to anything other than an empty string.
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let term = var("TERM").unwrap_or_default();
+        let program = program_from_vars(&var).unwrap_or_default();
+        let colorterm = var("COLORTERM").unwrap_or_default();
+
+        let kitty_keyboard = matches!(program.as_str(), "kitty" | "foot" | "WezTerm" | "ghostty");
+        let modern = kitty_keyboard
+            || matches!(
+                program.as_str(),
+                "alacritty" | "iTerm.app" | "vscode" | "Windows Terminal"
+            )
+            || (program == "VTE" && vte_version(&var).is_some_and(|v| v >= 5000));
+
+        let base = match term.as_str() {
+            "" => Self::all(),
+            term => Self::from_term(term),
+        };
+        Self {
+            color: base.color && !no_color(&var),
+            truecolor: base.truecolor
+                && (modern || matches!(colorterm.as_str(), "truecolor" | "24bit")),
+            hyperlinks: base.hyperlinks && modern,
+            clipboard: base.clipboard && modern,
+            kitty_keyboard: base.kitty_keyboard && kitty_keyboard,
+            ..base
+        }
+    }
+
+    /// Every text attribute is supported, but none of the other features.
+    const fn without_features() -> Self {
+        Self {
+            truecolor: false,
+            hyperlinks: false,
+            clipboard: false,
+            kitty_keyboard: false,
+            ..Self::all()
+        }
+    }
+
+    /// Guess the capabilities from the name of a terminal such as the value of `TERM`.
+    ///
+    /// Unknown terminals are assumed to support everything.
+    pub fn from_term(term: &str) -> Self {
+        match term {
+            "dumb" => Self::none(),
+            "linux" => Self {
+                italic: false,
+                crossed_out: false,
+                ..Self::without_features()
+            },
+            "ansi" | "vt100" | "vt102" | "vt220" => Self {
+                dim: false,
+                italic: false,
+                crossed_out: false,
+                ..Self::without_features()
+            },
+            _ => Self::all(),
+        }
+    }
+}
+
+/// Guess the name of the terminal emulator the program is running in from environment
+/// variables such as `TERM_PROGRAM`, or `None` if it isn't known.
+///
+/// This is only for information, such as to include in bug reports, and is what
+/// [`Capabilities::from_env`] uses to decide which features are supported. When running in a
+/// multiplexer such as tmux this is usually the multiplexer rather than the terminal outside it.
+///
+/// ```
+/// # use sketch::terminal_program;
+/// match terminal_program() {
+///     Some(program) => println!("Running in {program}"),
+///     None => println!("Running in an unknown terminal"),
+/// }
+/// ```
+pub fn terminal_program() -> Option<String> {
+    program_from_vars(&|name: &str| std::env::var(name).ok())
+}
+
+fn program_from_vars(var: &impl Fn(&str) -> Option<String>) -> Option<String> {
+    if let Some(program) = var("TERM_PROGRAM").filter(|p| !p.is_empty()) {
+        return Some(program);
+    }
+    if var("KITTY_WINDOW_ID").is_some() {
+        return Some(String::from("kitty"));
+    }
+    if var("WT_SESSION").is_some() {
+        return Some(String::from("Windows Terminal"));
+    }
+
+    let term = var("TERM").unwrap_or_default();
+    let program = match term.as_str() {
+        "xterm-kitty" => "kitty",
+        "xterm-ghostty" => "ghostty",
+        "alacritty" => "alacritty",
+        "linux" => "Linux console",
+        term if term.starts_with("foot") => "foot",
+        term if term.starts_with("tmux") => "tmux",
+        // Terminals based on VTE such as GNOME Terminal don't say which they are.
+        _ if var("VTE_VERSION").is_some() => "VTE",
+        _ => return None,
+    };
+    Some(program.to_string())
+}
+
+/// Does `NO_COLOR` ask for text without color.
+fn no_color(var: &impl Fn(&str) -> Option<String>) -> bool {
+    var("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+fn vte_version(var: &impl Fn(&str) -> Option<String>) -> Option<u32> {
+    var("VTE_VERSION")?.parse().ok()
+}
+
+/// Get the capabilities [`Style::render`](crate::Style::render) is using.
+///
+/// Unless they are set, or detected while an app is running, every capability is assumed to be
+/// supported except color if `NO_COLOR` is set. Use [`set_capabilities`] or
+/// [`App::with_capabilities`](crate::App::with_capabilities) to show color anyway.
+pub fn capabilities() -> Capabilities {
+    let capabilities = CAPABILITIES.read().unwrap_or_else(|e| e.into_inner());
+    capabilities.unwrap_or_else(|| Capabilities {
+        color: !env_no_color(),
+        ..Capabilities::all()
+    })
+}
+
+/// Does the `NO_COLOR` environment variable ask for text without color, only read once.
+fn env_no_color() -> bool {
+    static NO_COLOR: OnceLock<bool> = OnceLock::new();
+    *NO_COLOR.get_or_init(|| no_color(&|name: &str| std::env::var(name).ok()))
+}
+
+/// Set the capabilities [`Style::render`](crate::Style::render) uses for the whole program.
+pub fn set_capabilities(capabilities: Capabilities) {
+    *CAPABILITIES.write().unwrap_or_else(|e| e.into_inner()) = Some(capabilities);
+}
+
+/// Sets the capabilities while an [`App`](crate::App) is running, or guesses them from the
+/// environment if they haven't been set, putting back the previous ones when dropped.
+pub(crate) struct CapabilitiesGuard(Option<Capabilities>);
+
+impl CapabilitiesGuard {
+    pub(crate) fn new(capabilities: Option<Capabilities>) -> Self {
+        let mut current = CAPABILITIES.write().unwrap_or_else(|e| e.into_inner());
+        let previous = *current;
+        *current = Some(
+            capabilities
+                .or(previous)
+                .unwrap_or_else(Capabilities::from_env),
+        );
+        Self(previous)
+    }
+}
+
+impl Drop for CapabilitiesGuard {
+    fn drop(&mut self) {
+        *CAPABILITIES.write().unwrap_or_else(|e| e.into_inner()) = self.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_term() {
+        assert_eq!(
+            Capabilities::from_term("xterm-256color"),
+            Capabilities::all()
+        );
+        assert_eq!(Capabilities::from_term("dumb"), Capabilities::none());
+        assert!(!Capabilities::from_term("linux").italic);
+        assert!(Capabilities::from_term("linux").bold);
+        assert!(!Capabilities::from_term("linux").truecolor);
+    }
+
+    fn from_vars(vars: &[(&str, &str)]) -> Capabilities {
+        Capabilities::from_vars(|name| {
+            let (_, value) = vars.iter().find(|(n, _)| *n == name)?;
+            Some(value.to_string())
+        })
+    }
+
+    fn program(vars: &[(&str, &str)]) -> Option<String> {
+        program_from_vars(&|name: &str| {
+            let (_, value) = vars.iter().find(|(n, _)| *n == name)?;
+            Some(value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_terminal_program() {
+        let wezterm = program(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]);
+        assert_eq!(wezterm.as_deref(), Some("WezTerm"));
+        let kitty = program(&[("TERM", "xterm-kitty"), ("TERM_PROGRAM", "")]);
+        assert_eq!(kitty.as_deref(), Some("kitty"));
+        let gnome = program(&[("TERM", "xterm-256color"), ("VTE_VERSION", "7200")]);
+        assert_eq!(gnome.as_deref(), Some("VTE"));
+        assert_eq!(program(&[("TERM", "xterm-256color")]), None);
+        assert_eq!(program(&[]), None);
+    }
+
+    #[test]
+    fn test_from_vars_truecolor() {
+        assert!(!from_vars(&[("TERM", "xterm-256color")]).truecolor);
+        let colorterm = from_vars(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]);
+        assert!(colorterm.truecolor);
+        assert!(!colorterm.hyperlinks);
+        let linux = from_vars(&[("TERM", "linux"), ("COLORTERM", "24bit")]);
+        assert!(!linux.truecolor);
+    }
+
+    #[test]
+    fn test_from_vars_no_color() {
+        assert!(from_vars(&[("TERM", "xterm-256color")]).color);
+        assert!(!from_vars(&[("TERM", "xterm-256color"), ("NO_COLOR", "1")]).color);
+        assert!(from_vars(&[("TERM", "xterm-256color"), ("NO_COLOR", "")]).color);
+        let no_color = from_vars(&[("TERM", "xterm-kitty"), ("NO_COLOR", "1")]);
+        assert!(no_color.bold && no_color.hyperlinks);
+    }
+
+    #[test]
+    fn test_from_vars_known_terminals() {
+        let kitty = from_vars(&[("TERM", "xterm-kitty")]);
+        assert_eq!(kitty, Capabilities::all());
+        let wezterm = from_vars(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]);
+        assert!(wezterm.kitty_keyboard && wezterm.hyperlinks);
+        let vte = from_vars(&[("TERM", "xterm-256color"), ("VTE_VERSION", "7200")]);
+        assert!(vte.clipboard && !vte.kitty_keyboard);
+        assert_eq!(from_vars(&[("TERM", "dumb")]), Capabilities::none());
+    }
+}
@@ -0,0 +1,474 @@
+use crate::msg::{Focus, Key, Mouse, Msg, Quit, Resize, TerminalBackground};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::Sender,
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+/// How long to wait for an event before checking if the thread should stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How many reads can fail in a row before the input is treated as closed.
+const MAX_ERRORS: usize = 20;
+
+/// The ids of the running event threads. Only the last one reads events so an app run inside
+/// another gets all the input until it returns.
+static READERS: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// How long to wait for the terminal to reply to a query.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Tracks whether the terminal is expected to reply to a query.
+#[derive(Clone, Default)]
+pub(crate) struct Replies(Arc<Mutex<Option<Instant>>>);
+
+impl Replies {
+    /// Start expecting a reply from the terminal.
+    pub(crate) fn expect(&self) {
+        *self.0.lock().unwrap() = Some(Instant::now() + REPLY_TIMEOUT);
+    }
+
+    fn is_expected(&self) -> bool {
+        self.0
+            .lock()
+            .unwrap()
+            .is_some_and(|deadline| Instant::now() < deadline)
+    }
+
+    fn received(&self) {
+        *self.0.lock().unwrap() = None;
+    }
+}
+
+/// A thread sending terminal events as messages, stopped when dropped.
+pub(crate) struct EventThread {
+    id: usize,
+    stop: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+    /// Held by the thread while it is reading events.
+    reading: Arc<Mutex<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl EventThread {
+    /// Stop reading events until [`EventThread::resume`] is called, such as while another process
+    /// is using the terminal.
+    ///
+    /// Once this returns no more input will be read, anything typed while paused is left for
+    /// whoever reads the terminal next.
+    pub(crate) fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+        // Wait for any read in progress to finish.
+        drop(self.reading.lock().unwrap_or_else(|e| e.into_inner()));
+    }
+
+    /// Start reading events again after [`EventThread::pause`].
+    pub(crate) fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+}
+
+impl Drop for EventThread {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        READERS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|&id| id != self.id);
+    }
+}
+
+/// Where terminal events are read from.
+pub(crate) trait EventSource: Send + 'static {
+    /// Wait up to `timeout` for an event to be available.
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool>;
+    /// Read the next event, blocking until there is one.
+    fn read(&mut self) -> io::Result<Event>;
+}
+
+/// Reads events from the terminal using crossterm.
+pub(crate) struct TerminalEvents;
+
+impl EventSource for TerminalEvents {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        event::poll(timeout)
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        event::read()
+    }
+}
+
+/// The result of trying to read an event with [`next_event`].
+#[derive(Debug)]
+enum Next {
+    Event(Event),
+    /// There was no event, or reading one failed in a way that may work next time.
+    Idle,
+    /// The input has closed and no more events will be read.
+    Closed,
+}
+
+/// Read the next event, keeping count of how many reads have failed in a row in `errors`.
+fn next_event(source: &mut impl EventSource, errors: &mut usize) -> Next {
+    let result = match source.poll(POLL_INTERVAL) {
+        Ok(false) => return Next::Idle,
+        Ok(true) => source.read(),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(event) => {
+            *errors = 0;
+            Next::Event(event)
+        }
+        Err(e) if is_closed(&e) => Next::Closed,
+        Err(_) => {
+            *errors += 1;
+            if *errors >= MAX_ERRORS {
+                return Next::Closed;
+            }
+            // Give whatever went wrong a moment to clear up.
+            std::thread::sleep(POLL_INTERVAL);
+            Next::Idle
+        }
+    }
+}
+
+/// Keys read closer together than this are assumed to not have been typed by hand.
+const BURST_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Finds keys which arrive too quickly to have been typed, such as from pasting without
+/// bracketed paste.
+#[derive(Default)]
+struct Bursts {
+    last: Option<Instant>,
+}
+
+impl Bursts {
+    /// Is a key read at `now` part of a burst, given whether more input is already waiting.
+    fn is_burst(&mut self, now: Instant, more_waiting: bool) -> bool {
+        let burst = more_waiting
+            || self
+                .last
+                .is_some_and(|last| now.duration_since(last) < BURST_INTERVAL);
+        self.last = Some(now);
+        burst
+    }
+}
+
+/// Does this error mean the input has closed for good.
+fn is_closed(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::UnexpectedEof | io::ErrorKind::BrokenPipe | io::ErrorKind::NotConnected
+    )
+}
+
+/// Spawn a thread sending the events from `source` to `tx`.
+///
+/// If the input closes a [`Quit`] message is sent so the app stops cleanly.
+pub(crate) fn spawn_event_thread(
+    tx: Sender<Msg>,
+    mut source: impl EventSource,
+    replies: Replies,
+) -> EventThread {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    READERS.lock().unwrap_or_else(|e| e.into_inner()).push(id);
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let paused = Arc::new(AtomicBool::new(false));
+    let reading = Arc::new(Mutex::new(()));
+    let handle = std::thread::spawn({
+        let stop = stop.clone();
+        let paused = paused.clone();
+        let reading = reading.clone();
+        move || {
+            let mut reply = ReplyParser::default();
+            let mut errors = 0;
+            let mut bursts = Bursts::default();
+
+            while !stop.load(Ordering::Relaxed) {
+                let is_reader =
+                    READERS.lock().unwrap_or_else(|e| e.into_inner()).last() == Some(&id);
+                let lock = reading.lock().unwrap_or_else(|e| e.into_inner());
+                if !is_reader || paused.load(Ordering::Relaxed) {
+                    drop(lock);
+                    std::thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+
+                let event = match next_event(&mut source, &mut errors) {
+                    Next::Event(event) => event,
+                    // Nothing more has arrived for a while, so a partly read reply must have been
+                    // keys, such as an escape, which would otherwise be held until the next one.
+                    Next::Idle if reply.is_active() => {
+                        drop(lock);
+                        let mut keys = reply.flush().into_iter();
+                        if keys.any(|key| tx.send(Msg::new(Key::from(key))).is_err()) {
+                            break;
+                        }
+                        continue;
+                    }
+                    Next::Idle => continue,
+                    Next::Closed => {
+                        let _ = tx.send(Msg::new(Quit));
+                        break;
+                    }
+                };
+                let burst = matches!(event, Event::Key(_)).then(|| {
+                    let more_waiting = source.poll(Duration::ZERO).unwrap_or(false);
+                    bursts.is_burst(Instant::now(), more_waiting)
+                });
+                drop(lock);
+
+                let event = match event {
+                    Event::Key(key) if reply.is_active() || replies.is_expected() => {
+                        let msgs = match reply.feed(key) {
+                            Feed::Pending => vec![],
+                            Feed::Done(content) => {
+                                replies.received();
+                                reply_msg(&content).into_iter().collect()
+                            }
+                            Feed::Rejected(keys) => keys
+                                .into_iter()
+                                .map(|key| Msg::new(Key::from(key)))
+                                .collect(),
+                        };
+                        if msgs.into_iter().any(|msg| tx.send(msg).is_err()) {
+                            break;
+                        }
+                        continue;
+                    }
+                    event => event,
+                };
+
+                let msg = match event {
+                    Event::FocusGained => Msg::new(Focus::Gained),
+                    Event::FocusLost => Msg::new(Focus::Lost),
+                    Event::Key(event) => {
+                        Msg::new(Key::from(event).with_burst(burst.unwrap_or(false)))
+                    }
+                    Event::Mouse(event) => Msg::new(Mouse::from(event)),
+                    Event::Resize(width, height) => Msg::new(Resize {
+                        width: crate::terminal::stage_width(width),
+                        height,
+                    }),
+
+                    #[cfg(feature = "paste")]
+                    Event::Paste(value) => Msg::new(crate::msg::Paste(value)),
+                    #[cfg(not(feature = "paste"))]
+                    Event::Paste(_) => continue,
+                };
+
+                // The app has stopped if the receiver is gone.
+                if tx.send(msg).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    EventThread {
+        id,
+        stop,
+        paused,
+        reading,
+        handle: Some(handle),
+    }
+}
+
+/// Turn the content of an OSC reply in to a message.
+fn reply_msg(content: &str) -> Option<Msg> {
+    #[cfg(feature = "paste")]
+    if let Some(text) = crate::osc::parse_clipboard(content) {
+        return Some(Msg::new(crate::msg::Clipboard(text)));
+    }
+    crate::osc::parse_background(content).map(|color| Msg::new(TerminalBackground(color)))
+}
+
+/// The result of feeding a key to a [`ReplyParser`].
+#[derive(Debug, PartialEq)]
+enum Feed {
+    /// The key was part of a reply which is not yet complete.
+    Pending,
+    /// A reply was completed, holding the content between the `ESC ]` and the terminator.
+    Done(String),
+    /// The keys were not a reply and should be handled as normal input.
+    Rejected(Vec<KeyEvent>),
+}
+
+/// Reassembles OSC replies from the terminal.
+///
+/// Crossterm does not understand OSC sequences so a reply such as `ESC ] 52;c;aGk= BEL` is read
+/// as alt+`]`, a key for each character and then ctrl+`g` for the BEL, or alt+`\` for an `ESC \`
+/// terminator.
+#[derive(Default)]
+struct ReplyParser {
+    keys: Vec<KeyEvent>,
+    content: String,
+}
+
+impl ReplyParser {
+    fn is_active(&self) -> bool {
+        !self.keys.is_empty()
+    }
+
+    /// Give up on the reply being read, returning the keys that were held for it.
+    fn flush(&mut self) -> Vec<KeyEvent> {
+        self.content.clear();
+        std::mem::take(&mut self.keys)
+    }
+
+    fn feed(&mut self, key: KeyEvent) -> Feed {
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Char(']') if alt && !self.is_active() => {}
+            KeyCode::Char('g') if control && self.is_active() => {
+                self.keys.clear();
+                return Feed::Done(std::mem::take(&mut self.content));
+            }
+            KeyCode::Char('\\') if alt && self.is_active() => {
+                self.keys.clear();
+                return Feed::Done(std::mem::take(&mut self.content));
+            }
+            KeyCode::Char(c) if self.is_active() && !alt && !control => self.content.push(c),
+            _ => {
+                self.keys.push(key);
+                self.content.clear();
+                return Feed::Rejected(std::mem::take(&mut self.keys));
+            }
+        }
+
+        self.keys.push(key);
+        Feed::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// An event source returning a list of results.
+    struct FakeEvents(VecDeque<io::Result<Event>>);
+
+    impl EventSource for FakeEvents {
+        fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+            if self.0.is_empty() {
+                std::thread::sleep(timeout);
+            }
+            Ok(!self.0.is_empty())
+        }
+
+        fn read(&mut self) -> io::Result<Event> {
+            self.0.pop_front().unwrap()
+        }
+    }
+
+    #[test]
+    fn test_read_continues_after_error() {
+        let mut source = FakeEvents(VecDeque::from([
+            Err(io::Error::from(io::ErrorKind::Interrupted)),
+            Err(io::Error::other("hiccup")),
+            Ok(Event::FocusGained),
+        ]));
+        let mut errors = 0;
+
+        assert!(matches!(next_event(&mut source, &mut errors), Next::Idle));
+        assert!(matches!(next_event(&mut source, &mut errors), Next::Idle));
+        assert_eq!(errors, 2);
+        assert!(matches!(
+            next_event(&mut source, &mut errors),
+            Next::Event(Event::FocusGained)
+        ));
+        assert_eq!(errors, 0);
+        assert!(matches!(next_event(&mut source, &mut errors), Next::Idle));
+    }
+
+    #[test]
+    fn test_bursts() {
+        let mut bursts = Bursts::default();
+        let start = Instant::now();
+        assert!(!bursts.is_burst(start, false));
+        assert!(bursts.is_burst(start + Duration::from_millis(1), false));
+        assert!(!bursts.is_burst(start + Duration::from_millis(500), false));
+        // The first key of a paste has the rest waiting behind it.
+        assert!(bursts.is_burst(start + Duration::from_millis(1000), true));
+    }
+
+    #[test]
+    fn test_read_stops_when_closed() {
+        let mut source = FakeEvents(VecDeque::from([Err(io::Error::from(
+            io::ErrorKind::UnexpectedEof,
+        ))]));
+        assert!(matches!(next_event(&mut source, &mut 0), Next::Closed));
+    }
+
+    #[test]
+    fn test_unfinished_reply_is_sent_as_keys() {
+        let start = KeyEvent::new(KeyCode::Char(']'), KeyModifiers::ALT);
+        let source = FakeEvents(VecDeque::from([Ok(Event::Key(start))]));
+        let replies = Replies::default();
+        replies.expect();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let thread = spawn_event_thread(tx, source, replies);
+
+        let msg = rx.recv_timeout(Duration::from_secs(1)).unwrap();
+        let key = msg.cast::<Key>().unwrap();
+        assert_eq!(key.code, KeyCode::Char(']'));
+        assert!(key.with_alt());
+        drop(thread);
+    }
+
+    fn feed_str(parser: &mut ReplyParser, s: &str) {
+        for c in s.chars() {
+            assert_eq!(
+                parser.feed(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)),
+                Feed::Pending
+            );
+        }
+    }
+
+    #[test]
+    fn test_reply_with_bel() {
+        let mut parser = ReplyParser::default();
+        let start = KeyEvent::new(KeyCode::Char(']'), KeyModifiers::ALT);
+        assert_eq!(parser.feed(start), Feed::Pending);
+        feed_str(&mut parser, "52;c;aGk=");
+        let end = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL);
+        assert_eq!(parser.feed(end), Feed::Done(String::from("52;c;aGk=")));
+        assert!(!parser.is_active());
+    }
+
+    #[test]
+    fn test_reply_with_st() {
+        let mut parser = ReplyParser::default();
+        parser.feed(KeyEvent::new(KeyCode::Char(']'), KeyModifiers::ALT));
+        feed_str(&mut parser, "52;c;");
+        let end = KeyEvent::new(KeyCode::Char('\\'), KeyModifiers::ALT);
+        assert_eq!(parser.feed(end), Feed::Done(String::from("52;c;")));
+    }
+
+    #[test]
+    fn test_non_reply_is_rejected() {
+        let mut parser = ReplyParser::default();
+        let start = KeyEvent::new(KeyCode::Char(']'), KeyModifiers::ALT);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        parser.feed(start);
+        assert_eq!(parser.feed(enter), Feed::Rejected(vec![start, enter]));
+        assert!(!parser.is_active());
+    }
+}
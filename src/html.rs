@@ -0,0 +1,262 @@
+use crate::text::{self, Token};
+use std::fmt::Write;
+
+/// The colors used for the first 16 ANSI colors, the same as xterm's defaults.
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0xcd, 0x00, 0x00),
+    (0x00, 0xcd, 0x00),
+    (0xcd, 0xcd, 0x00),
+    (0x00, 0x00, 0xee),
+    (0xcd, 0x00, 0xcd),
+    (0x00, 0xcd, 0xcd),
+    (0xe5, 0xe5, 0xe5),
+    (0x7f, 0x7f, 0x7f),
+    (0xff, 0x00, 0x00),
+    (0x00, 0xff, 0x00),
+    (0xff, 0xff, 0x00),
+    (0x5c, 0x5c, 0xff),
+    (0xff, 0x00, 0xff),
+    (0x00, 0xff, 0xff),
+    (0xff, 0xff, 0xff),
+];
+
+/// Convert text styled with ANSI escape codes, such as a frame from
+/// [`App::capture_frame`](crate::App::capture_frame), to HTML with inline styles.
+///
+/// The result is a `<pre>` element with a `<span>` for each differently styled piece of text.
+/// Escape codes other than styling are left out.
+///
+/// ```
+/// # use sketch::ansi_to_html;
+/// let html = ansi_to_html("\x1b[1mhi\x1b[0m <3");
+/// assert_eq!(html, "<pre><span style=\"font-weight:bold\">hi</span> &lt;3</pre>");
+/// ```
+pub fn ansi_to_html(text: &str) -> String {
+    let mut html = String::from("<pre>");
+    let mut style = SpanStyle::default();
+    // The CSS of the span currently open.
+    let mut open: Option<String> = None;
+
+    for token in text::tokens(text) {
+        match token {
+            Token::Escape(code) => {
+                if let Some(params) = text::sgr_params(code) {
+                    style.apply(&params);
+                }
+            }
+            Token::Char(c) => {
+                let css = style.css();
+                if css != open {
+                    if open.is_some() {
+                        html.push_str("</span>");
+                    }
+                    if let Some(css) = &css {
+                        write!(html, "<span style=\"{css}\">").unwrap();
+                    }
+                    open = css;
+                }
+                match c {
+                    '&' => html.push_str("&amp;"),
+                    '<' => html.push_str("&lt;"),
+                    '>' => html.push_str("&gt;"),
+                    '"' => html.push_str("&quot;"),
+                    c => html.push(c),
+                }
+            }
+        }
+    }
+
+    if open.is_some() {
+        html.push_str("</span>");
+    }
+    html.push_str("</pre>");
+    html
+}
+
+/// The styling set by SGR escape codes.
+#[derive(Debug, Default)]
+struct SpanStyle {
+    fg: Option<(u8, u8, u8)>,
+    bg: Option<(u8, u8, u8)>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    reverse: bool,
+    crossed_out: bool,
+    overline: bool,
+}
+
+impl SpanStyle {
+    /// Update the style with the parameters of an SGR escape code.
+    fn apply(&mut self, params: &[u16]) {
+        let mut params = params.iter().copied();
+        while let Some(param) = params.next() {
+            match param {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.reverse = true,
+                9 => self.crossed_out = true,
+                22 => (self.bold, self.dim) = (false, false),
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.reverse = false,
+                29 => self.crossed_out = false,
+                53 => self.overline = true,
+                55 => self.overline = false,
+                30..=37 => self.fg = Some(PALETTE[param as usize - 30]),
+                38 => self.fg = extended_color(&mut params),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(PALETTE[param as usize - 40]),
+                48 => self.bg = extended_color(&mut params),
+                49 => self.bg = None,
+                // Underline colors aren't shown, but their arguments mustn't be read as codes.
+                58 => {
+                    extended_color(&mut params);
+                }
+                59 => {}
+                90..=97 => self.fg = Some(PALETTE[param as usize - 90 + 8]),
+                100..=107 => self.bg = Some(PALETTE[param as usize - 100 + 8]),
+                _ => {}
+            }
+        }
+    }
+
+    /// The inline CSS for this style, or `None` if the text is unstyled.
+    fn css(&self) -> Option<String> {
+        let hex = |(r, g, b): (u8, u8, u8)| format!("#{r:02x}{g:02x}{b:02x}");
+        let (mut fg, mut bg) = (self.fg.map(hex), self.bg.map(hex));
+        if self.reverse {
+            (fg, bg) = (
+                Some(bg.unwrap_or_else(|| "Canvas".to_string())),
+                Some(fg.unwrap_or_else(|| "CanvasText".to_string())),
+            );
+        }
+
+        let mut css = Vec::new();
+        if let Some(fg) = fg {
+            css.push(format!("color:{fg}"));
+        }
+        if let Some(bg) = bg {
+            css.push(format!("background-color:{bg}"));
+        }
+        if self.bold {
+            css.push("font-weight:bold".to_string());
+        }
+        if self.dim {
+            css.push("opacity:0.5".to_string());
+        }
+        if self.italic {
+            css.push("font-style:italic".to_string());
+        }
+        let decorations: Vec<&str> = [
+            (self.underline, "underline"),
+            (self.overline, "overline"),
+            (self.crossed_out, "line-through"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, decoration)| enabled.then_some(decoration))
+        .collect();
+        if !decorations.is_empty() {
+            css.push(format!("text-decoration:{}", decorations.join(" ")));
+        }
+
+        (!css.is_empty()).then(|| css.join(";"))
+    }
+}
+
+/// Read a 256 color (`5;n`) or RGB color (`2;r;g;b`) following a `38` or `48` parameter.
+fn extended_color(params: &mut impl Iterator<Item = u16>) -> Option<(u8, u8, u8)> {
+    let mut next = || params.next().map(|p| p.min(255) as u8);
+    match next()? {
+        5 => Some(color_256(next()?)),
+        2 => Some((next()?, next()?, next()?)),
+        _ => None,
+    }
+}
+
+/// The color of one of the 256 ANSI colors.
+fn color_256(n: u8) -> (u8, u8, u8) {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match n {
+        0..=15 => PALETTE[n as usize],
+        16..=231 => {
+            let n = n - 16;
+            (
+                LEVELS[n as usize / 36],
+                LEVELS[n as usize / 6 % 6],
+                LEVELS[n as usize % 6],
+            )
+        }
+        _ => {
+            let grey = 8 + 10 * (n - 232);
+            (grey, grey, grey)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_is_escaped() {
+        assert_eq!(
+            ansi_to_html("a & \"b\""),
+            "<pre>a &amp; &quot;b&quot;</pre>"
+        );
+    }
+
+    #[test]
+    fn test_colors() {
+        let html = ansi_to_html("\x1b[31ma\x1b[38;5;196mb\x1b[48;2;1;2;3mc\x1b[39;49md");
+        assert_eq!(
+            html,
+            "<pre><span style=\"color:#cd0000\">a</span>\
+             <span style=\"color:#ff0000\">b</span>\
+             <span style=\"color:#ff0000;background-color:#010203\">c</span>d</pre>"
+        );
+    }
+
+    #[test]
+    fn test_same_style_shares_span() {
+        let html = ansi_to_html("\x1b[1ma\x1b[0m\x1b[1mb\x1b[0m\nc");
+        assert_eq!(
+            html,
+            "<pre><span style=\"font-weight:bold\">ab</span>\nc</pre>"
+        );
+    }
+
+    #[test]
+    fn test_reverse_without_colors() {
+        let html = ansi_to_html("\x1b[7ma");
+        assert_eq!(
+            html,
+            "<pre><span style=\"color:Canvas;background-color:CanvasText\">a</span></pre>"
+        );
+    }
+
+    #[test]
+    fn test_text_decorations() {
+        let html = ansi_to_html("\x1b[4;53ma\x1b[24mb");
+        assert_eq!(
+            html,
+            "<pre><span style=\"text-decoration:underline overline\">a</span>\
+             <span style=\"text-decoration:overline\">b</span></pre>"
+        );
+    }
+
+    #[test]
+    fn test_underline_color_is_skipped() {
+        assert_eq!(
+            ansi_to_html("\x1b[4m\x1b[58;5;9mx"),
+            "<pre><span style=\"text-decoration:underline\">x</span></pre>"
+        );
+        assert_eq!(ansi_to_html("\x1b[58;5;1mx\x1b[59m"), "<pre>x</pre>");
+        assert_eq!(ansi_to_html("\x1b[58;2;1;2;3mx"), "<pre>x</pre>");
+    }
+}
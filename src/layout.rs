@@ -0,0 +1,492 @@
+use crate::{
+    text::{self, wrap_text},
+    Style,
+};
+
+/// An area of the screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    /// The column of the left edge.
+    pub x: u16,
+    /// The row of the top edge.
+    pub y: u16,
+    /// The number of columns.
+    pub width: u16,
+    /// The number of rows.
+    pub height: u16,
+}
+
+impl Rect {
+    /// Create a new [`Rect`].
+    pub const fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Does the area contain no cells.
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Is the cell at `(x, y)` inside the area.
+    pub const fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.x && x - self.x < self.width && y >= self.y && y - self.y < self.height
+    }
+}
+
+/// Lay out two blocks of text side by side.
+///
+/// The left block is wrapped to `left_width` columns and padded so the right block always starts
+/// in the same column. The columns are separated by `gutter` columns, with `sep` drawn in the
+/// middle of them if given. When one block has fewer lines than the other it is padded with
+/// empty lines.
+///
+/// ```
+/// # use sketch::two_columns;
+/// let text = two_columns("a\nb", "c", 3, 3, Some('│'));
+/// assert_eq!(text, "a   │ c\nb   │ ");
+/// ```
+pub fn two_columns(
+    left: &str,
+    right: &str,
+    left_width: usize,
+    gutter: usize,
+    sep: Option<char>,
+) -> String {
+    let left = wrap_text(left, left_width);
+    let right: Vec<&str> = right.split('\n').collect();
+
+    let gutter = match sep {
+        Some(sep) => {
+            let before = gutter.saturating_sub(1) / 2;
+            let after = gutter.saturating_sub(1 + before);
+            format!("{}{sep}{}", " ".repeat(before), " ".repeat(after))
+        }
+        None => " ".repeat(gutter),
+    };
+
+    let height = left.len().max(right.len());
+    let mut lines = Vec::with_capacity(height);
+    for n in 0..height {
+        let left = left.get(n).map_or("", String::as_str);
+        let right = right.get(n).copied().unwrap_or("");
+        let padding = left_width.saturating_sub(text::width(left));
+        lines.push(format!("{left}{}{gutter}{right}", " ".repeat(padding)));
+    }
+
+    lines.join("\n")
+}
+
+/// Lay out a line `width` columns wide with `left` at the left edge, `center` in the middle and
+/// `right` at the right edge, such as for a status bar.
+///
+/// The center is kept in the true center of the line unless that would overlap one of the other
+/// zones, in which case it moves as little as possible. When the zones don't all fit, the least
+/// important is cut short with a `…`: the center first, then the right and then the left. There
+/// is always at least one column between zones that aren't empty.
+///
+/// Each zone should be a single line, escape codes don't count towards its width.
+///
+/// ```
+/// # use sketch::three_zones;
+/// assert_eq!(three_zones(11, "a", "b", "c"), "a    b    c");
+/// assert_eq!(three_zones(10, "left", "center", "r"), "left ce… r");
+/// ```
+pub fn three_zones(width: usize, left: &str, center: &str, right: &str) -> String {
+    let left = truncate(left, width);
+    let left_width = text::width(&left);
+    let gap = |zone_width: usize| usize::from(zone_width > 0);
+    let right = truncate(right, width.saturating_sub(left_width + gap(left_width)));
+    let right_width = text::width(&right);
+
+    // The columns the center can use without touching the other zones.
+    let start = (left_width + gap(left_width)).min(width);
+    let end = width.saturating_sub(right_width + gap(right_width));
+    let center = truncate(center, end.saturating_sub(start));
+    let center_width = text::width(&center);
+    let center_start =
+        ((width - center_width) / 2).clamp(start, end.saturating_sub(center_width).max(start));
+
+    let before = center_start - left_width;
+    let after = width.saturating_sub(center_start + center_width + right_width);
+    format!(
+        "{left}{}{center}{}{right}",
+        " ".repeat(before),
+        " ".repeat(after)
+    )
+}
+
+/// Cut a line down to `width` columns, ending it with a `…` if anything was cut off.
+pub(crate) fn truncate(line: &str, width: usize) -> String {
+    match width {
+        _ if text::width(line) <= width => line.to_string(),
+        0 => String::new(),
+        width => text::slice_columns(line, 0, width - 1) + "…",
+    }
+}
+
+/// Draw `block` over `base` in `area`, such as a dialog over the rest of the app.
+///
+/// The block is cut or padded with spaces to the size of the area, and the base is padded with
+/// empty lines if it is shorter than the bottom of the area. What is left of the base on either
+/// side of the area keeps its styling, escape codes don't count towards the width of either.
+///
+/// ```
+/// # use sketch::{overlay, Rect};
+/// let text = overlay("abcd\nefgh", "xy", Rect::new(1, 1, 2, 1));
+/// assert_eq!(text, "abcd\ne\x1b[0mxy\x1b[0mh");
+/// ```
+pub fn overlay(base: &str, block: &str, area: Rect) -> String {
+    if area.is_empty() {
+        return base.to_string();
+    }
+
+    let (x, width) = (area.x as usize, area.width as usize);
+    let top = area.y as usize;
+    let bottom = top + area.height as usize;
+    let mut lines: Vec<String> = base.split('\n').map(str::to_string).collect();
+    if lines.len() < bottom {
+        lines.resize(bottom, String::new());
+    }
+
+    let mut block = block.split('\n');
+    for line in &mut lines[top..bottom] {
+        let content = text::slice_columns(block.next().unwrap_or(""), 0, width);
+        let content_padding = width.saturating_sub(text::width(&content));
+        let left = text::slice_columns(line, 0, x);
+        let left_padding = x.saturating_sub(text::width(&left));
+        let line_width = text::width(line);
+        let right = match line_width > x + width {
+            true => text::slice_columns(line, x + width, line_width - x - width),
+            false => String::new(),
+        };
+        *line = format!(
+            "{left}{}{}{content}{}{}{right}",
+            " ".repeat(left_padding),
+            text::RESET,
+            " ".repeat(content_padding),
+            text::RESET,
+        );
+    }
+
+    lines.join("\n")
+}
+
+/// Join blocks of text side by side.
+///
+/// Each block is padded to the width of its widest line so the next block always starts in the
+/// same column, and blocks with fewer lines than the tallest are padded with empty lines. If
+/// `sep` is given, that character is drawn in its style between each pair of blocks for the full
+/// height of the tallest block.
+///
+/// ```
+/// # use sketch::{join_horizontal, Style};
+/// let text = join_horizontal(&["ab\nc", "1\n2\n3"], Some(('|', &Style::new())));
+/// assert_eq!(text, "ab|\x1b[0m1\nc |\x1b[0m2\n  |\x1b[0m3");
+/// ```
+pub fn join_horizontal(blocks: &[&str], sep: Option<(char, &Style)>) -> String {
+    let sep = sep.map(|(c, style)| style.render_in(c.to_string(), 1));
+    let blocks: Vec<Vec<&str>> = blocks.iter().map(|b| b.split('\n').collect()).collect();
+    let widths: Vec<usize> = blocks
+        .iter()
+        .map(|lines| lines.iter().map(|l| text::width(l)).max().unwrap_or(0))
+        .collect();
+    let height = blocks.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut lines = Vec::with_capacity(height);
+    for n in 0..height {
+        let mut line = String::new();
+        for (i, (block, width)) in blocks.iter().zip(&widths).enumerate() {
+            if i > 0 {
+                line.push_str(sep.as_deref().unwrap_or(""));
+            }
+            let part = block.get(n).copied().unwrap_or("");
+            line.push_str(part);
+            // The last block doesn't need padding as nothing comes after it.
+            if i + 1 < blocks.len() {
+                line.push_str(&" ".repeat(width - text::width(part)));
+            }
+        }
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+/// Join blocks of text one above the other.
+///
+/// If `sep` is given, a rule of that character is drawn in its style between each pair of
+/// blocks, as wide as the widest line of any block.
+///
+/// ```
+/// # use sketch::{join_vertical, Style};
+/// let text = join_vertical(&["abc", "1"], Some(('─', &Style::new())));
+/// assert_eq!(text, "abc\n───\x1b[0m\n1");
+/// ```
+pub fn join_vertical(blocks: &[&str], sep: Option<(char, &Style)>) -> String {
+    let sep = sep.map(|(c, style)| {
+        let width = blocks
+            .iter()
+            .flat_map(|b| b.split('\n'))
+            .map(text::width)
+            .max()
+            .unwrap_or(0);
+        let rule = c.to_string().repeat(width / text::char_width(c).max(1));
+        style.render_in(&rule, width)
+    });
+
+    let mut text = String::new();
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            text.push('\n');
+            if let Some(sep) = &sep {
+                text.push_str(sep);
+                text.push('\n');
+            }
+        }
+        text.push_str(block);
+    }
+    text
+}
+
+/// Indent each line of a block of text by `spaces` columns, such as to nest it under a heading.
+///
+/// Empty lines are left empty. The indentation is never styled, even when a line continues the
+/// styling of the line above it.
+///
+/// ```
+/// # use sketch::indent;
+/// assert_eq!(indent("a\n\nb", 2), "  a\n\n  b");
+/// ```
+pub fn indent(block: &str, spaces: usize) -> String {
+    indent_lines(block, spaces, false)
+}
+
+/// Indent each line of a block of text except the first by `spaces` columns, such as for the
+/// continuation of a list item after its bullet.
+///
+/// This works the same as [`indent`] for the other lines.
+///
+/// ```
+/// # use sketch::hanging_indent;
+/// assert_eq!(hanging_indent("- one\ntwo", 2), "- one\n  two");
+/// ```
+pub fn hanging_indent(block: &str, spaces: usize) -> String {
+    indent_lines(block, spaces, true)
+}
+
+fn indent_lines(block: &str, spaces: usize, skip_first: bool) -> String {
+    if spaces == 0 {
+        return block.to_string();
+    }
+
+    let padding = " ".repeat(spaces);
+    let mut result = String::with_capacity(block.len());
+    let mut active = String::new();
+    for (n, line) in block.split('\n').enumerate() {
+        if n > 0 {
+            result.push('\n');
+        }
+        if !line.is_empty() && (n > 0 || !skip_first) {
+            if active.is_empty() {
+                result.push_str(&padding);
+            } else {
+                // Reset so the padding isn't styled, then carry on with the styling.
+                result.push_str(text::RESET);
+                result.push_str(&padding);
+                result.push_str(&active);
+            }
+        }
+        result.push_str(line);
+        for token in text::tokens(line) {
+            if let text::Token::Escape(code) = token {
+                text::track_style(&mut active, code);
+            }
+        }
+    }
+    result
+}
+
+/// Where to place content vertically for [`place_vertical`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalAlign {
+    /// Place the content at the top.
+    #[default]
+    Top,
+    /// Place the content in the middle.
+    Center,
+    /// Place the content at the bottom.
+    Bottom,
+}
+
+/// Pad the top of `content` so it is vertically centered within `total_height` lines.
+///
+/// Content taller than `total_height` is left as it is.
+///
+/// ```
+/// # use sketch::vertical_center;
+/// assert_eq!(vertical_center("a\nb", 6), "\n\na\nb");
+/// ```
+pub fn vertical_center(content: &str, total_height: usize) -> String {
+    place_vertical(content, total_height, VerticalAlign::Center)
+}
+
+/// Pad the top of `content` so it is placed within `total_height` lines.
+///
+/// Content taller than `total_height` is left as it is.
+///
+/// ```
+/// # use sketch::{place_vertical, VerticalAlign};
+/// let text = place_vertical("a", 3, VerticalAlign::Bottom);
+/// assert_eq!(text, "\n\na");
+/// ```
+pub fn place_vertical(content: &str, total_height: usize, align: VerticalAlign) -> String {
+    let free = total_height.saturating_sub(content.split('\n').count());
+    let top = match align {
+        VerticalAlign::Top => 0,
+        VerticalAlign::Center => free / 2,
+        VerticalAlign::Bottom => free,
+    };
+    format!("{}{content}", "\n".repeat(top))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indent() {
+        assert_eq!(indent("a\n\nb", 2), "  a\n\n  b");
+        assert_eq!(indent("a\nb", 0), "a\nb");
+    }
+
+    #[test]
+    fn test_indent_styled_lines() {
+        let block = "\x1b[1ma\x1b[0m\n\x1b[41mb\nc\x1b[0m";
+        assert_eq!(
+            indent(block, 1),
+            " \x1b[1ma\x1b[0m\n \x1b[41mb\n\x1b[0m \x1b[41mc\x1b[0m"
+        );
+        assert_eq!(
+            hanging_indent(block, 1),
+            "\x1b[1ma\x1b[0m\n \x1b[41mb\n\x1b[0m \x1b[41mc\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_two_columns_without_separator() {
+        let text = two_columns("one\ntwo", "1\n2", 5, 2, None);
+        assert_eq!(text, "one    1\ntwo    2");
+    }
+
+    #[test]
+    fn test_two_columns_wraps_left() {
+        let text = two_columns("left side", "right", 4, 1, Some('|'));
+        assert_eq!(text, "left|right\nside|");
+    }
+
+    #[test]
+    fn test_two_columns_pads_shorter_left() {
+        let text = two_columns("a", "1\n2\n3", 2, 3, Some('│'));
+        assert_eq!(text, "a  │ 1\n   │ 2\n   │ 3");
+    }
+
+    #[test]
+    fn test_two_columns_ignores_escapes() {
+        let text = two_columns("\x1b[1mab\x1b[0m\nc", "x\ny", 3, 1, None);
+        assert_eq!(text, "\x1b[1mab\x1b[0m  x\nc   y");
+    }
+
+    #[test]
+    fn test_three_zones_moves_center_away_from_long_left() {
+        assert_eq!(three_zones(10, "left", "c", "r"), "left c   r");
+    }
+
+    #[test]
+    fn test_three_zones_drops_center_then_truncates_right() {
+        assert_eq!(three_zones(8, "left", "mid", "right side"), "left ri…");
+        assert_eq!(three_zones(6, "a long left", "mid", "right"), "a lon…");
+        assert_eq!(three_zones(0, "left", "mid", "right"), "");
+    }
+
+    #[test]
+    fn test_three_zones_is_width_aware() {
+        assert_eq!(
+            three_zones(7, "\x1b[1m世\x1b[0m", "", "界"),
+            "\x1b[1m世\x1b[0m   界"
+        );
+        assert_eq!(
+            three_zones(7, "a", "\x1b[2m世界世界\x1b[0m", "b"),
+            "a \x1b[2m世\x1b[0m… b"
+        );
+    }
+
+    #[test]
+    fn test_overlay_pads_base_and_block() {
+        let text = overlay("ab", "x\nyy", Rect::new(3, 0, 2, 3));
+        assert_eq!(
+            text,
+            "ab \x1b[0mx \x1b[0m\n   \x1b[0myy\x1b[0m\n   \x1b[0m  \x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_overlay_keeps_base_styling() {
+        let text = overlay("\x1b[1m世界abc\x1b[0m", "xy", Rect::new(1, 0, 3, 1));
+        assert_eq!(text, "\x1b[1m \x1b[0m\x1b[0mxy \x1b[0m\x1b[1mabc\x1b[0m");
+    }
+
+    #[test]
+    fn test_rect_contains() {
+        let rect = Rect::new(2, 1, 3, 2);
+        assert!(rect.contains(2, 1));
+        assert!(rect.contains(4, 2));
+        assert!(!rect.contains(5, 1));
+        assert!(!rect.contains(1, 1));
+        assert!(!rect.contains(2, 3));
+    }
+
+    #[test]
+    fn test_vertical_center() {
+        assert_eq!(vertical_center("a", 5), "\n\na");
+        assert_eq!(vertical_center("a\nb", 5), "\na\nb");
+    }
+
+    #[test]
+    fn test_vertical_center_taller_than_height() {
+        assert_eq!(vertical_center("a\nb\nc", 2), "a\nb\nc");
+        assert_eq!(
+            place_vertical("a\nb\nc", 2, VerticalAlign::Bottom),
+            "a\nb\nc"
+        );
+    }
+
+    #[test]
+    fn test_place_vertical_top() {
+        assert_eq!(place_vertical("a", 5, VerticalAlign::Top), "a");
+    }
+
+    #[test]
+    fn test_join_horizontal_without_separator() {
+        let text = join_horizontal(&["one\ntwo", "1", "\x1b[1mx\x1b[0m"], None);
+        assert_eq!(text, "one1\x1b[1mx\x1b[0m\ntwo ");
+    }
+
+    #[test]
+    fn test_join_horizontal_separator_is_full_height() {
+        let sep = Style::new().dim();
+        let text = join_horizontal(&["a", "b\nc"], Some(('│', &sep)));
+        assert_eq!(text, "a\x1b[2m│\x1b[0mb\n \x1b[2m│\x1b[0mc");
+    }
+
+    #[test]
+    fn test_join_vertical() {
+        assert_eq!(join_vertical(&["a", "bc"], None), "a\nbc");
+        let text = join_vertical(&["a\nbcd", "e", "f"], Some(('-', &Style::new())));
+        assert_eq!(text, "a\nbcd\n---\x1b[0m\ne\n---\x1b[0m\nf");
+    }
+}
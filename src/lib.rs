@@ -12,7 +12,8 @@
 //!
 //! fn main() -> std::io::Result<()> {
 //!     let model = Counter::default();
-//!     App::new(model).run()
+//!     App::new(model).run()?;
+//!     Ok(())
 //! }
 //!
 //! #[derive(Default)]
@@ -48,8 +49,9 @@
 //!
 //! The point of this function is to use this [`Msg`] to create a new model to be used for the next
 //! render. The function can also optionally return another [`Msg`]. If another message is returned
-//! it will be given to [`Model::update`] and continue to run them until a message is not returned.
-//! The app will render once all returned messages are run.
+//! it will be given to [`Model::update`] and continue to run them until a message is not returned,
+//! or [`App::with_max_chained_messages`] is reached. The app will render once all returned
+//! messages are run.
 //!
 //! ## [`Model::view`]
 //!
@@ -61,15 +63,47 @@
 //! This function runs on startup and if a message is returned it will be run as the first message
 //! for [`Model::update`].
 //!
+//! The first frame is drawn before that message is handled, but `startup` itself runs before
+//! anything is drawn. Return a [`Task`] for slow work such as reading a config file so it runs on
+//! another thread, with the view showing a loading state until the task's message arrives.
+//!
+//! ## [`Model::on_quit`]
+//!
+//! This function runs once the app quits because of a [`Quit`] message, while the app is still
+//! being displayed. It is a good place to save any state. It is not run if the app panics.
+//!
 //! ## Built-in messages
 //!
 //! The following are the built-in messages.
 //!
 //! * [`Quit`]: Send to quit the app.
+//! * [`Redraw`]: Send to redraw the app.
+//! * [`Tick`]: Send to move animations on to their next frame.
+//! * [`Bell`]: Send to ring the terminal bell.
+//! * [`Notify`]: Send to show a desktop notification.
+//! * [`PrintAbove`]: Send to print a line above an inline app.
+//! * [`RawSequence`]: Send to write an escape sequence to the terminal.
+//! * [`SetCursorShape`]: Send to change the shape of the cursor.
+//! * [`TerminalBackground`]: The terminal's background color, see
+//!   [`App::with_background_query`].
+//! * [`SetClipboard`]: Send to copy text to the system clipboard.
+//! * [`Task`]: Send to run a closure on another thread, sending the message it returns.
+//! * [`Batch`]: Send to send several messages at once.
+//! * [`PushScreen`]: Send to show a screen on top of a [`ScreenStack`].
+//! * [`PopScreen`]: Send to go back to the previous screen of a [`ScreenStack`].
+//! * [`Exec`]: Send to run a command which takes over the terminal.
+//! * [`ExecFinished`]: The command from an [`Exec`] has exited.
+//! * [`DismissOverlay`]: The model's overlay should be hidden, see [`Model::overlay`].
+//! * [`ChainLimitReached`]: Too many messages were returned one after another, see
+//!   [`App::with_max_chained_messages`].
 //! * [`Key`]: Keyboard input.
-//! * [`Mouse`]: Mouse input.
+//! * [`Mouse`]: Mouse input, see [`App::with_mouse_capture`].
 //! * [`Focus`]: Focus changes.
 //! * [`Paste`]: Clipboard pastes. Only if the `paste` feature is enabeld.
+//! * [`RequestClipboard`]: Send to read the system clipboard. Only if the `paste` feature is
+//!   enabled.
+//! * [`Clipboard`]: The system clipboard after a [`RequestClipboard`]. Only if the `paste`
+//!   feature is enabled.
 //!
 //! ## Custom messages
 //!
@@ -93,93 +127,1001 @@
 
 #![deny(missing_docs)]
 
-use crossterm::{
-    cursor::MoveTo,
-    event::{self, Event},
-    execute,
-    style::Print,
-    terminal::{
-        disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
-        LeaveAlternateScreen,
-    },
-};
+use event::EventThread;
+use render::Renderer;
 use std::{
-    io::{self, Write},
+    io::{self, IsTerminal, Write},
+    ops::ControlFlow,
     sync::mpsc::{channel, Receiver, Sender},
+    time::{Duration, Instant},
 };
+use terminal::{StageGuard, TerminalGuard};
 
-pub use crossterm::terminal::size as terminal_size;
+pub use animation::*;
+pub use buffer::*;
+pub use capabilities::*;
+pub use html::ansi_to_html;
+pub use layout::*;
+pub use line::*;
+pub use markup::*;
 pub use msg::*;
+pub use render::{ClearStrategy, ControlChars};
+pub use schedule::Scheduler;
+pub use screen::*;
 pub use style::*;
+pub use terminal::terminal_size;
+pub use text::{mirror, paginate, reflow, wrap_text};
+pub use theme::*;
+pub use zone::*;
 
+mod animation;
+#[doc(hidden)]
+pub mod bench;
+mod buffer;
+mod capabilities;
+mod event;
+mod html;
+mod layout;
+mod line;
+mod markup;
 mod msg;
+mod osc;
+mod render;
+mod schedule;
+mod screen;
 mod style;
+mod terminal;
+mod text;
+mod theme;
+pub mod widgets;
+mod zone;
 
 /// A type to hold on to and run your [`Model`].
+///
+/// The app owns the model while it runs and gives it back from [`App::run`] once the app quits,
+/// so a larger program can set up a model, run it and then use its final state.
+///
+/// ```no_run
+/// # use sketch::*;
+/// # #[derive(Default)]
+/// # struct Picker { chosen: Option<String> }
+/// # impl Model for Picker {
+/// #     fn update(self, _: &Msg) -> (Self, Option<Msg>) { (self, None) }
+/// #     fn view(&self) -> String { String::new() }
+/// # }
+/// let picker = App::new(Picker::default()).run()?;
+/// println!("You picked {:?}", picker.chosen);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// While running, the model can only be reached through messages, use [`App::sender`] to send
+/// them from elsewhere in the program.
 pub struct App<M: Model> {
     model: M,
     message_sender: Sender<Msg>,
     message_receiver: Receiver<Msg>,
+    scheduler: Scheduler,
+    clear_strategy: ClearStrategy,
+    loop_mode: LoopMode,
+    piped_output: PipedOutput,
+    trim_trailing_whitespace: bool,
+    trim_trailing_lines: bool,
+    control_chars: ControlChars,
+    visual_bell: bool,
+    focused_notifications: bool,
+    focused: bool,
+    panic_hook: bool,
+    trailing_newline: bool,
+    inline: bool,
+    mouse_capture: bool,
+    header: Option<String>,
+    capabilities: Option<Capabilities>,
+    max_width: Option<u16>,
+    min_size: Option<(u16, u16)>,
+    vim_keys: Option<bool>,
+    background_query: bool,
+    auto_quit: Option<Duration>,
+    input_resets_auto_quit: bool,
+    double_escape_quit: Option<Duration>,
+    max_chained_messages: usize,
+    overlay_dismiss: OverlayDismiss,
+    /// Where the model's overlay is in the last frame, if one is shown.
+    overlay_area: Option<Rect>,
+    replies: event::Replies,
 }
 
+/// When an [`App`] draws frames.
+///
+/// Set with [`App::with_loop_mode`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LoopMode {
+    /// Wait for a message and draw once it and any others waiting have been handled.
+    ///
+    /// Nothing is done while there are no messages, so an idle app uses no CPU, but anything
+    /// that changes over time such as an animation must send a message for each frame, see
+    /// [`Scheduler`].
+    #[default]
+    Blocking,
+    /// Draw this many frames per second, handling whatever messages are waiting before each.
+    ///
+    /// The view is built every frame even when nothing has changed, which uses CPU while the
+    /// app is idle, and input can wait up to a frame before it is handled. In exchange frames
+    /// are evenly paced, which suits apps that are mostly animation.
+    Polling(u16),
+}
+
+/// What an [`App`] does when its output isn't a terminal, such as when piped in to another
+/// program.
+///
+/// Set with [`App::with_piped_output`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PipedOutput {
+    /// Print the final view once without any escape codes.
+    #[default]
+    Plain,
+    /// Print the final view once, keeping the escape codes for its styling.
+    Styled,
+    /// Run the app as normal, writing escape codes meant for a terminal to the output.
+    Interactive,
+}
+
+/// Which messages dismiss a [`Model::overlay`], by being replaced with a [`DismissOverlay`]
+/// message while one is shown.
+///
+/// Set with [`App::with_overlay_dismiss`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayDismiss {
+    /// Nothing dismisses the overlay, the model must hide it itself.
+    Never,
+    /// Pressing escape dismisses the overlay.
+    #[default]
+    Escape,
+    /// Pressing escape or sending [`Quit`] dismisses the overlay, so quitting only closes it
+    /// rather than the whole app.
+    EscapeAndQuit,
+}
+
+/// The most messages handled between frames, so a flood of messages can't stop the app drawing.
+const MAX_BATCHED_MESSAGES: usize = 256;
+
+/// The default for [`App::with_max_chained_messages`].
+const DEFAULT_MAX_CHAINED_MESSAGES: usize = 10_000;
+
+/// The [`Scheduler`] key of the [`Quit`] message sent by [`App::with_auto_quit`].
+const AUTO_QUIT_KEY: &str = "sketch::auto_quit";
+
+/// The [`Scheduler`] key of the escape held back by [`App::with_double_escape_quit`].
+const DOUBLE_ESCAPE_KEY: &str = "sketch::double_escape";
+
+/// Sent once the window from [`App::with_double_escape_quit`] has passed without a second escape,
+/// to give the model the escape that was held back.
+struct HeldEscape;
+impl Message for HeldEscape {}
+
 impl<M: Model> App<M> {
     /// Create a new [`App`].
     #[must_use = "Creating an app does nothing until you call App::run()"]
     pub fn new(model: M) -> Self {
-        let (message_sender, message_receiver) = channel();
+        let (sender, receiver) = channel();
+        Self::with_channel(model, sender, receiver)
+    }
+
+    /// Create a new [`App`] which receives its [`Msg`]s from an existing channel.
+    ///
+    /// This is useful when embedding an app in a larger program which already passes around the
+    /// sender. The `sender` must be for the same channel as the `receiver`, it is used for
+    /// everything the app sends itself, such as input from the terminal, so [`App::sender`]
+    /// returns a copy of it.
+    ///
+    /// Messages can be sent before the app is run, they are queued and given to
+    /// [`Model::update`] once it starts. The receiver is dropped when [`App::run`] returns, after
+    /// which sending fails and any messages left in the channel are lost.
+    ///
+    /// ```no_run
+    /// # use sketch::*;
+    /// # struct Counter;
+    /// # impl Model for Counter {
+    /// #     fn update(self, _: &Msg) -> (Self, Option<Msg>) { (self, None) }
+    /// #     fn view(&self) -> String { String::new() }
+    /// # }
+    /// let (sender, receiver) = std::sync::mpsc::channel();
+    /// sender.send(Msg::new(Redraw)).unwrap();
+    /// App::with_channel(Counter, sender, receiver).run()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use = "Creating an app does nothing until you call App::run()"]
+    pub fn with_channel(
+        model: M,
+        message_sender: Sender<Msg>,
+        message_receiver: Receiver<Msg>,
+    ) -> Self {
         Self {
             model,
+            scheduler: Scheduler::new(message_sender.clone()),
             message_sender,
             message_receiver,
+            clear_strategy: ClearStrategy::default(),
+            loop_mode: LoopMode::default(),
+            piped_output: PipedOutput::default(),
+            trim_trailing_whitespace: true,
+            trim_trailing_lines: false,
+            control_chars: ControlChars::default(),
+            visual_bell: false,
+            focused_notifications: false,
+            focused: true,
+            panic_hook: true,
+            trailing_newline: false,
+            inline: false,
+            mouse_capture: false,
+            header: None,
+            capabilities: None,
+            max_width: None,
+            min_size: None,
+            vim_keys: None,
+            background_query: false,
+            auto_quit: None,
+            input_resets_auto_quit: true,
+            double_escape_quit: None,
+            max_chained_messages: DEFAULT_MAX_CHAINED_MESSAGES,
+            overlay_dismiss: OverlayDismiss::default(),
+            overlay_area: None,
+            replies: event::Replies::default(),
         }
     }
 
+    /// Set which messages dismiss the model's [overlay](Model::overlay). Defaults to
+    /// [`OverlayDismiss::Escape`].
+    pub fn with_overlay_dismiss(mut self, dismiss: OverlayDismiss) -> Self {
+        self.overlay_dismiss = dismiss;
+        self
+    }
+
+    /// Set what happens when the output isn't a terminal. Defaults to [`PipedOutput::Plain`].
+    ///
+    /// Unless [`PipedOutput::Interactive`] is used, piped apps don't take over the terminal or
+    /// read any input. Instead [`Model::startup`] and every message already sent, along with
+    /// the messages returned after them, are given to [`Model::update`] until there are no more
+    /// or [`Quit`] is sent. The final view is then printed once, followed by a newline, and
+    /// [`Model::on_quit`] is called. [`Task`]s are run in place rather than on another thread,
+    /// other messages handled by the app itself, such as [`Bell`], do nothing and messages sent
+    /// later, such as from a [`Scheduler`], are never handled.
+    ///
+    /// This lets a tool be used in scripts, such as `mytool | grep error`.
+    pub fn with_piped_output(mut self, piped_output: PipedOutput) -> Self {
+        self.piped_output = piped_output;
+        self
+    }
+
+    /// Set when frames are drawn. Defaults to [`LoopMode::Blocking`], see [`LoopMode`] for the
+    /// tradeoffs.
+    pub fn with_loop_mode(mut self, mode: LoopMode) -> Self {
+        self.loop_mode = mode;
+        self
+    }
+
+    /// Set how the previous frame is cleared before drawing the next one.
+    ///
+    /// Defaults to [`ClearStrategy::PerLine`], see [`ClearStrategy`] for the tradeoffs.
+    /// [Inline](App::with_inline) apps always redraw every line.
+    pub fn with_clear_strategy(mut self, strategy: ClearStrategy) -> Self {
+        self.clear_strategy = strategy;
+        self
+    }
+
+    /// Set whether to remove whitespace from the end of each line before drawing it. Defaults to
+    /// `true`.
+    ///
+    /// This reduces how much is written to the terminal, such as the padding from alignment.
+    /// Spaces which are visible because of their styling, such as having a background color, are
+    /// always kept.
+    pub fn with_trim_trailing_whitespace(mut self, trim: bool) -> Self {
+        self.trim_trailing_whitespace = trim;
+        self
+    }
+
+    /// Set whether to remove empty lines from the end of the view before drawing it. Defaults to
+    /// `false`.
+    ///
+    /// This stops views built by joining sections from pushing the content up with blank lines
+    /// at the bottom.
+    pub fn with_trim_trailing_lines(mut self, trim: bool) -> Self {
+        self.trim_trailing_lines = trim;
+        self
+    }
+
+    /// Set what to do with control characters in the view, such as NUL or backspace, which would
+    /// otherwise corrupt the display.
+    ///
+    /// Defaults to [`ControlChars::Replace`], see [`ControlChars`] for the options. Newlines,
+    /// tabs and escape codes are always kept, use [`RawSequence`] to write anything else.
+    pub fn with_control_chars(mut self, policy: ControlChars) -> Self {
+        self.control_chars = policy;
+        self
+    }
+
+    /// Flash the screen instead of making a sound when a [`Bell`] message is sent.
+    ///
+    /// This is useful for environments where sound is unwanted. Defaults to `false`.
+    pub fn with_visual_bell(mut self, visual_bell: bool) -> Self {
+        self.visual_bell = visual_bell;
+        self
+    }
+
+    /// Set whether [`Notify`] messages show a notification while the terminal is in focus, rather
+    /// than only while it is out of focus. Defaults to `false`.
+    pub fn with_focused_notifications(mut self, focused: bool) -> Self {
+        self.focused_notifications = focused;
+        self
+    }
+
+    /// Set whether to install a panic hook that restores the terminal. Defaults to `true`.
+    ///
+    /// The hook wraps whatever hook is set when [`App::run`] is called, so to use your own panic
+    /// reporting (such as a crash reporter) set it before calling [`App::run`] and it will run
+    /// after the terminal is restored. The wrapped hook is put back once [`App::run`] returns.
+    /// When an app is run inside another only the outermost app installs the hook.
+    ///
+    /// Without the hook the terminal is still restored as a panic unwinds out of [`App::run`],
+    /// but only after the panic message is printed, so the message may be printed to the
+    /// alternate screen and lost.
+    pub fn with_panic_hook(mut self, panic_hook: bool) -> Self {
+        self.panic_hook = panic_hook;
+        self
+    }
+
+    /// Set whether to print an empty line once the app exits. Defaults to `false`.
+    ///
+    /// The cursor is always moved to the start of a line when the app exits so the shell's prompt
+    /// is printed cleanly, this adds a blank line before it as well.
+    pub fn with_trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// Set whether to read mouse input, which is sent to the model as [`Mouse`] messages.
+    /// Defaults to `false`.
+    ///
+    /// While the mouse is read most terminals only select text with shift held. See [`HitMap`]
+    /// for finding what was clicked.
+    pub fn with_mouse_capture(mut self, mouse_capture: bool) -> Self {
+        self.mouse_capture = mouse_capture;
+        self
+    }
+
+    /// Set whether to draw the app below the cursor instead of taking over the whole screen.
+    /// Defaults to `false`.
+    ///
+    /// Inline apps are drawn on the terminal's main screen, so they stay in its scrollback once
+    /// the app exits. This suits small prompts and progress displays which are part of a
+    /// command's output. Views taller than the terminal are cut off at the bottom.
+    pub fn with_inline(mut self, inline: bool) -> Self {
+        self.inline = inline;
+        self
+    }
+
+    /// Set text to print once above an [inline](App::with_inline) app when it starts, such as a
+    /// logo or instructions. Defaults to no header.
+    ///
+    /// The header isn't part of the view so it is never redrawn or cleared, even when the
+    /// terminal is resized, and stays in the scrollback above the app. It is not shown by apps
+    /// using the whole screen.
+    pub fn with_header(mut self, header: impl Into<String>) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+
+    /// Limit the app to `max_width` columns, centered on wider terminals. Defaults to no limit.
+    ///
+    /// The view is drawn as if the terminal were only `max_width` columns wide, so
+    /// [`terminal_size`] and [`Resize`] messages report at most this width while the app is
+    /// running. Terminals narrower than `max_width` use their full width. Mouse positions are
+    /// still relative to the whole terminal.
+    pub fn with_max_width(mut self, max_width: u16) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Set the smallest terminal the app can be drawn in. Defaults to no minimum.
+    ///
+    /// While the terminal is smaller a message saying so is shown instead of the model's view,
+    /// so the view doesn't need to handle sizes it can't lay out. The model is still updated and
+    /// its view is shown again once the terminal is large enough.
+    pub fn with_min_size(mut self, cols: u16, rows: u16) -> Self {
+        self.min_size = Some((cols, rows));
+        self
+    }
+
+    /// Quit the app once `delay` has passed, such as for a notification that closes itself.
+    /// Defaults to never quitting on its own.
+    ///
+    /// The countdown starts again whenever there is input, unless turned off with
+    /// [`App::with_auto_quit_reset`]. The app quits the same way as if [`Quit`] were sent, so
+    /// [`Model::on_quit`] is still called.
+    pub fn with_auto_quit(mut self, delay: Duration) -> Self {
+        self.auto_quit = Some(delay);
+        self
+    }
+
+    /// Set whether keyboard, mouse and paste input starts the countdown from
+    /// [`App::with_auto_quit`] again. Defaults to `true`.
+    pub fn with_auto_quit_reset(mut self, reset: bool) -> Self {
+        self.input_resets_auto_quit = reset;
+        self
+    }
+
+    /// Quit when escape is pressed twice within `window`, such as to guard against quitting by
+    /// accident. Defaults to escape being sent to the model like any other key.
+    ///
+    /// A single escape is still sent to the model as a [`Key`], but only once `window` has
+    /// passed without a second one, so keep it short. Escapes pressed with ctrl, shift or alt
+    /// held are sent straight away. The app quits the same way as if [`Quit`] were sent, so
+    /// [`Model::on_quit`] is still called.
+    pub fn with_double_escape_quit(mut self, window: Duration) -> Self {
+        self.double_escape_quit = Some(window);
+        self
+    }
+
+    /// Set the most messages handled one after another from a single message, by
+    /// [`Model::update`] returning another message each time. Defaults to 10,000.
+    ///
+    /// This stops a model which always returns a message, usually by mistake, from stopping the
+    /// app drawing or quitting. Once the limit is reached the next message in the chain is
+    /// dropped and a [`ChainLimitReached`] message is sent instead.
+    pub fn with_max_chained_messages(mut self, max: usize) -> Self {
+        self.max_chained_messages = max.max(1);
+        self
+    }
+
+    /// Set whether to ask the terminal for its background color on startup, which is sent to
+    /// the model as a [`TerminalBackground`] message. Defaults to `false`.
+    ///
+    /// This is supported by most modern terminals including xterm, kitty, Alacritty, WezTerm,
+    /// foot, iTerm2, GNOME Terminal and others based on VTE, and Windows Terminal. Terminals that
+    /// don't answer within a second are given up on and no message is sent, so the model should
+    /// have a sensible default.
+    ///
+    /// The answer also decides which color [`AdaptiveColor`]s are rendered in, without it the
+    /// background is assumed to be dark.
+    pub fn with_background_query(mut self, query: bool) -> Self {
+        self.background_query = query;
+        self
+    }
+
+    /// Set what the terminal is able to display, see [`Capabilities`].
+    ///
+    /// These are used for the whole program while the app is running, with the previous ones put
+    /// back once [`App::run`] returns. By default they are guessed with
+    /// [`Capabilities::from_env`] unless [`set_capabilities`] has been called.
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    /// Set whether `h`, `j`, `k` and `l` are treated as arrow keys by [`Key::is_left`] and
+    /// friends, see [`set_vim_keys`].
+    ///
+    /// This is used for the whole program while the app is running, with the previous setting put
+    /// back once [`App::run`] returns. Defaults to `false`.
+    pub fn with_vim_keys(mut self, enabled: bool) -> Self {
+        self.vim_keys = Some(enabled);
+        self
+    }
+
+    /// Get a reference to the model before the app is run.
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// Get a mutable reference to the model before the app is run.
+    pub fn model_mut(&mut self) -> &mut M {
+        &mut self.model
+    }
+
     /// Get a copy of the [`Sender`] for sending [`Msg`]s.
+    ///
+    /// The sender can be cloned and moved to other threads, messages sent from any thread are run
+    /// in the order they are received. Send [`Redraw`] to only redraw the app.
     pub fn sender(&self) -> Sender<Msg> {
         self.message_sender.clone()
     }
 
-    /// Run this [`App`] only returning once the [`Quit`] message has been sent.
-    pub fn run(mut self) -> std::io::Result<()> {
-        set_panic_hook();
-        enable_raw_mode()?;
+    /// Get a copy of the [`Scheduler`] for sending [`Msg`]s at a later time.
+    pub fn scheduler(&self) -> Scheduler {
+        self.scheduler.clone()
+    }
+
+    /// Get the frame the app would draw for the model as it is now, exactly as it would be
+    /// written to the terminal but without the codes to move the cursor between lines.
+    ///
+    /// This is useful for taking a "screenshot" of the app for documentation or a bug report,
+    /// see [`ansi_to_html`] to turn it in to HTML. The terminal's size is needed if the app has
+    /// a [maximum width](App::with_max_width) or [minimum size](App::with_min_size), in which
+    /// case this fails if there is no terminal.
+    ///
+    /// ```
+    /// # use sketch::*;
+    /// # struct Greeting;
+    /// # impl Model for Greeting {
+    /// #     fn update(self, _: &Msg) -> (Self, Option<Msg>) { (self, None) }
+    /// #     fn view(&self) -> String { "Hello   ".to_string() }
+    /// # }
+    /// let app = App::new(Greeting);
+    /// assert_eq!(app.capture_frame()?, "Hello");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn capture_frame(&self) -> io::Result<String> {
+        let _stage = StageGuard::new(self.max_width);
+        self.frame()
+    }
+
+    /// The next frame to draw, with the app's options applied to the model's view.
+    fn frame(&self) -> io::Result<String> {
+        Ok(self.frame_and_overlay()?.0)
+    }
+
+    /// The next frame to draw along with where the model's [overlay](Model::overlay) is in it,
+    /// if one is shown.
+    fn frame_and_overlay(&self) -> io::Result<(String, Option<Rect>)> {
+        let too_small = match self.min_size {
+            Some(min_size) => render::too_small(terminal_size()?, min_size),
+            None => None,
+        };
+        let mut shown_overlay = None;
+        let mut view = match too_small {
+            Some(too_small) => too_small,
+            None => {
+                // Anything below the bottom of the terminal can't be seen.
+                let rows = crossterm::terminal::size().map_or(usize::MAX, |(_, rows)| rows.into());
+                let lines: Vec<_> = self.model.view_lines().take(rows).collect();
+                let view = lines.join("\n");
+                match self.model.overlay() {
+                    Some((block, area)) => {
+                        shown_overlay = Some(area);
+                        overlay(&view, &block, area)
+                    }
+                    None => view,
+                }
+            }
+        };
+        view = render::sanitize(view, self.control_chars);
+        if self.trim_trailing_whitespace {
+            view = render::trim_trailing_whitespace(&view);
+        }
+        if self.trim_trailing_lines {
+            view = render::trim_trailing_lines(&view);
+        }
+        if self.max_width.is_some() {
+            let offset = terminal::stage_offset(crossterm::terminal::size()?.0);
+            view = indent(&view, offset);
+        }
+        Ok((view, shown_overlay))
+    }
+
+    /// Take the regions of the frame which changed from the model, moved to where they are drawn.
+    ///
+    /// This is `None` while the terminal is too small, since the model's view isn't shown.
+    fn dirty_regions(&mut self) -> io::Result<Option<Vec<Rect>>> {
+        let Some(mut regions) = self.model.take_dirty_regions() else {
+            return Ok(None);
+        };
+        if let Some(min_size) = self.min_size {
+            if render::too_small(terminal_size()?, min_size).is_some() {
+                return Ok(None);
+            }
+        }
+        if self.max_width.is_some() {
+            let offset = terminal::stage_offset(crossterm::terminal::size()?.0) as u16;
+            for region in &mut regions {
+                region.x = region.x.saturating_add(offset);
+            }
+        }
+        Ok(Some(regions))
+    }
+
+    /// Run this [`App`] only returning once the [`Quit`] message has been sent, giving back the
+    /// model as it was at the end.
+    ///
+    /// Apps can be run one after another or one inside another's update, in which case the
+    /// terminal is only restored once the outermost app returns.
+    ///
+    /// If the output isn't a terminal, such as when it is piped in to another program, the final
+    /// view is printed once instead, see [`App::with_piped_output`].
+    pub fn run(mut self) -> std::io::Result<M> {
+        if self.piped_output != PipedOutput::Interactive && !io::stdout().is_terminal() {
+            return self.run_piped(&mut io::stdout());
+        }
+
+        let _capabilities = CapabilitiesGuard::new(self.capabilities);
+        let _vim_keys = VimKeysGuard::new(self.vim_keys);
+
+        let mut guard = TerminalGuard::new(
+            self.panic_hook,
+            self.trailing_newline,
+            self.inline,
+            self.mouse_capture,
+        )?;
+        let stage = StageGuard::new(self.max_width);
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
+        // Read before the event thread starts so it can't take the terminal's reply.
+        let mut cursor_row = self.cursor_row();
+
+        let event_thread = event::spawn_event_thread(
+            self.message_sender.clone(),
+            event::TerminalEvents,
+            self.replies.clone(),
+        );
 
-        spawn_crossterm_event_thread(self.message_sender.clone());
+        if self.background_query {
+            self.replies.expect();
+            write!(stdout, "{}", osc::REQUEST_BACKGROUND)?;
+            stdout.flush()?;
+        }
 
         if let Some(msg) = self.model.startup() {
             self.message_sender.send(msg).unwrap();
         }
+        if let Some(delay) = self.auto_quit {
+            self.scheduler
+                .send_after_keyed(AUTO_QUIT_KEY, delay, Msg::new(Quit));
+        }
+
+        let mut renderer = match self.inline {
+            true => Renderer::inline(),
+            false => Renderer::new(self.clear_strategy),
+        };
+        if let (true, Some(header)) = (self.inline, &self.header) {
+            let header = render::sanitize(header.clone(), self.control_chars);
+            write!(stdout, "\r{}\r\n", header.replace('\n', "\r\n"))?;
+            let rows = crossterm::terminal::size().map_or(u16::MAX, |(_, rows)| rows);
+            let lines = header.split('\n').count() as u16;
+            cursor_row = cursor_row.saturating_add(lines).min(rows.saturating_sub(1));
+        }
+        renderer.detach(cursor_row);
 
+        let mut next_frame = Instant::now();
         'outer: loop {
-            let view = self.model.view().replace("\n", "\r\n");
-            // TODO: Diff this and last frame and only update what has changed.
-            execute!(stdout, Clear(ClearType::All), MoveTo(0, 0), Print(&view))?;
-            stdout.flush()?;
+            zone::next_frame();
+            let (view, overlay_area) = self.frame_and_overlay()?;
+            self.overlay_area = overlay_area;
+            let dirty = self.dirty_regions()?;
+            if let Ok(size) = crossterm::terminal::size() {
+                renderer.set_size(size);
+            }
+            renderer.write_frame(
+                &mut stdout,
+                &view,
+                dirty.as_deref(),
+                guard.take_alternate_screen(),
+            )?;
+            let column = crossterm::terminal::size()
+                .map_or(0, |(cols, _)| terminal::stage_offset(cols) as u16);
+            terminal::set_view_origin((column, renderer.top()));
 
-            let mut m = Some(self.message_receiver.recv().unwrap());
-            while let Some(msg) = m {
+            // Handle every message that is already waiting before drawing again, so updates sent
+            // together are drawn together rather than as a frame each.
+            let mut waiting = match self.loop_mode {
+                LoopMode::Blocking => Some(self.message_receiver.recv().unwrap()),
+                LoopMode::Polling(fps) => {
+                    // Skip frames which are already late rather than drawing them all at once.
+                    let interval = Duration::from_secs(1) / u32::from(fps.max(1));
+                    next_frame = (next_frame + interval).max(Instant::now());
+                    std::thread::sleep(next_frame.saturating_duration_since(Instant::now()));
+                    self.message_receiver.try_recv().ok()
+                }
+            };
+            let mut handled = 0;
+            while let Some(first) = waiting {
+                let limit = self.max_chained_messages;
+                let (app, end) = run_chain(self, first, limit, |mut app, msg| {
+                    let Some(msg) = app.hold_escape(msg) else {
+                        return Ok((app, ControlFlow::Continue(None)));
+                    };
+                    let Some(msg) = app.route_to_overlay(msg) else {
+                        return Ok((app, ControlFlow::Continue(None)));
+                    };
+                    if msg.is::<Quit>() {
+                        return Ok((app, ControlFlow::Break(())));
+                    }
+                    if let (Some(delay), true) = (app.auto_quit, app.input_resets_auto_quit) {
+                        if is_input(&msg) {
+                            app.scheduler
+                                .send_after_keyed(AUTO_QUIT_KEY, delay, Msg::new(Quit));
+                        }
+                    }
+                    if let Some(background) = msg.cast::<TerminalBackground>() {
+                        set_dark_background(background.is_dark());
+                    }
+                    if let Some(focus) = msg.cast::<Focus>() {
+                        app.focused = matches!(focus, Focus::Gained);
+                    }
+                    if app.run_command(&mut stdout, &msg, &mut renderer, &event_thread)? {
+                        return Ok((app, ControlFlow::Continue(None)));
+                    }
+                    if msg.is::<Resize>() {
+                        renderer.invalidate();
+                    }
+
+                    let (model, next) = app.model.update(&msg);
+                    app.model = model;
+                    Ok((app, ControlFlow::Continue(next)))
+                })?;
+                self = app;
+                match end {
+                    ChainEnd::Finished => {}
+                    ChainEnd::Quit => {
+                        self.model.on_quit();
+                        break 'outer;
+                    }
+                    ChainEnd::TooLong(msg) => {
+                        let limit = ChainLimitReached(msg.debug_name());
+                        let _ = self.message_sender.send(Msg::new(limit));
+                        // Draw before handling anything else so the app still shows progress.
+                        break;
+                    }
+                }
+
+                handled += 1;
+                waiting = match handled < MAX_BATCHED_MESSAGES {
+                    true => self.message_receiver.try_recv().ok(),
+                    false => None,
+                };
+            }
+        }
+
+        self.scheduler.cancel(AUTO_QUIT_KEY);
+        self.scheduler.cancel(DOUBLE_ESCAPE_KEY);
+        drop(event_thread);
+        drop(stage);
+        drop(guard);
+
+        Ok(self.model)
+    }
+
+    /// Handle every waiting message without a terminal, then write the final view to `out`.
+    fn run_piped(mut self, out: &mut impl Write) -> io::Result<M> {
+        if let Some(msg) = self.model.startup() {
+            self.message_sender.send(msg).unwrap();
+        }
+
+        let mut quit = false;
+        while let (false, Ok(first)) = (quit, self.message_receiver.try_recv()) {
+            let limit = self.max_chained_messages;
+            let (model, end) = run_chain(self.model, first, limit, |model, msg| {
                 if msg.is::<Quit>() {
-                    break 'outer;
+                    return Ok((model, ControlFlow::Break(())));
+                }
+                // There is nothing to draw while waiting, so tasks run in place.
+                if let Some(task) = msg.cast::<Task>() {
+                    return Ok((model, ControlFlow::Continue(task.take().map(|task| task()))));
                 }
+                if let Some(batch) = msg.cast::<Batch>() {
+                    for msg in batch.take() {
+                        self.message_sender.send(msg).unwrap();
+                    }
+                    return Ok((model, ControlFlow::Continue(None)));
+                }
+                let (model, next) = model.update(&msg);
+                Ok((model, ControlFlow::Continue(next)))
+            })?;
+            self.model = model;
+            quit = matches!(end, ChainEnd::Quit);
+        }
+
+        let mut view = render::sanitize(self.model.view(), self.control_chars);
+        if self.piped_output == PipedOutput::Plain {
+            view = text::strip_escapes(&view);
+        }
+        if self.trim_trailing_whitespace {
+            view = render::trim_trailing_whitespace(&view);
+        }
+        writeln!(out, "{view}")?;
+        out.flush()?;
+
+        self.model.on_quit();
+        Ok(self.model)
+    }
+
+    /// Turn messages which dismiss the model's [overlay](Model::overlay) in to
+    /// [`DismissOverlay`] while one is shown, and drop mouse input outside of it. Returns the
+    /// message to handle instead, if any.
+    fn route_to_overlay(&mut self, msg: Msg) -> Option<Msg> {
+        let Some(area) = self.overlay_area else {
+            return Some(msg);
+        };
+
+        let is_escape = msg
+            .cast::<Key>()
+            .is_some_and(|key| key.is_escape() && key.is_press());
+        let dismiss = match self.overlay_dismiss {
+            OverlayDismiss::Never => false,
+            OverlayDismiss::Escape => is_escape,
+            OverlayDismiss::EscapeAndQuit => is_escape || msg.is::<Quit>(),
+        };
+        if dismiss {
+            // Anything else in this batch goes to the view under the overlay.
+            self.overlay_area = None;
+            return Some(Msg::new(DismissOverlay));
+        }
+
+        if let Some(mouse) = msg.cast::<Mouse>() {
+            let (column, row) = terminal::view_origin();
+            let column = mouse.column.checked_sub(column)?;
+            let row = mouse.row.checked_sub(row)?;
+            if !area.contains(column, row) {
+                return None;
+            }
+        }
+        Some(msg)
+    }
+
+    /// The row of the terminal the cursor is on, which an inline app is drawn from. This is only
+    /// read if the mouse is, to find where it is over the view, otherwise it is `0`.
+    fn cursor_row(&self) -> u16 {
+        match self.inline && self.mouse_capture {
+            true => crossterm::cursor::position().map_or(0, |(_, row)| row),
+            false => 0,
+        }
+    }
+
+    /// Hold back an escape until the window from [`App::with_double_escape_quit`] has passed,
+    /// returning the message to handle instead, if any.
+    fn hold_escape(&self, msg: Msg) -> Option<Msg> {
+        let Some(window) = self.double_escape_quit else {
+            return Some(msg);
+        };
+        if msg.is::<HeldEscape>() {
+            use crossterm::event::{KeyEvent, KeyModifiers};
+            let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+            return Some(Msg::new(Key::from(key)));
+        }
+
+        let is_escape = msg.cast::<Key>().is_some_and(|key| {
+            key.is_escape()
+                && key.is_press()
+                && !(key.with_control() || key.with_shift() || key.with_alt())
+        });
+        if !is_escape {
+            return Some(msg);
+        }
+        if self.scheduler.cancel(DOUBLE_ESCAPE_KEY) {
+            return Some(Msg::new(Quit));
+        }
+        self.scheduler
+            .send_after_keyed(DOUBLE_ESCAPE_KEY, window, Msg::new(HeldEscape));
+        None
+    }
+
+    /// Run `msg` if it is a message handled by the app itself, returning whether it was.
+    fn run_command(
+        &self,
+        stdout: &mut impl Write,
+        msg: &Msg,
+        renderer: &mut Renderer,
+        event_thread: &EventThread,
+    ) -> io::Result<bool> {
+        #[cfg(feature = "paste")]
+        if msg.is::<RequestClipboard>() {
+            self.replies.expect();
+            write!(stdout, "{}", osc::REQUEST_CLIPBOARD)?;
+            stdout.flush()?;
+            return Ok(true);
+        }
 
-                let out = self.model.update(&msg);
-                self.model = out.0;
-                m = out.1;
+        if msg.is::<Redraw>() {
+            renderer.invalidate();
+        } else if msg.is::<Bell>() {
+            ring_bell(stdout, self.visual_bell)?;
+        } else if let Some(PrintAbove(text)) = msg.cast() {
+            renderer.print_above(&render::sanitize(text.clone(), self.control_chars));
+        } else if let Some(Notify { title, body }) = msg.cast() {
+            if self.focused_notifications || !self.focused {
+                let program = terminal_program();
+                write!(stdout, "{}", osc::notify(title, body, program.as_deref()))?;
+                stdout.flush()?;
             }
+        } else if let Some(RawSequence(sequence)) = msg.cast() {
+            write!(stdout, "{sequence}")?;
+        } else if let Some(SetCursorShape(shape)) = msg.cast() {
+            write!(stdout, "{}", shape.sequence())?;
+        } else if let Some(SetClipboard(text)) = msg.cast() {
+            write!(stdout, "{}", osc::set_clipboard(text))?;
+            stdout.flush()?;
+        } else if let Some(batch) = msg.cast::<Batch>() {
+            for msg in batch.take() {
+                let _ = self.message_sender.send(msg);
+            }
+        } else if let Some(task) = msg.cast::<Task>() {
+            if let Some(task) = task.take() {
+                let sender = self.message_sender.clone();
+                std::thread::spawn(move || {
+                    let _ = sender.send(task());
+                });
+            }
+        } else if let Some(exec) = msg.cast::<Exec>() {
+            if let Some(mut command) = exec.take() {
+                event_thread.pause();
+                terminal::suspend()?;
+                let status = command.status();
+                terminal::unsuspend()?;
+                let cursor_row = self.cursor_row();
+                event_thread.resume();
+
+                renderer.invalidate();
+                renderer.detach(cursor_row);
+                let _ = self.message_sender.send(Msg::new(ExecFinished(status)));
+            }
+        } else {
+            return Ok(false);
         }
 
-        disable_raw_mode()?;
-        execute!(stdout, LeaveAlternateScreen)?;
+        Ok(true)
+    }
+}
 
-        Ok(())
+/// How a chain of messages handled by [`run_chain`] ended.
+enum ChainEnd {
+    /// There were no more messages.
+    Finished,
+    /// A [`Quit`] message was received.
+    Quit,
+    /// The limit was reached, giving the next message which wasn't handled.
+    TooLong(Msg),
+}
+
+/// Handle `first` and every message returned after it with `step`, which gives the next message
+/// or breaks to quit, stopping once `limit` messages have been handled.
+///
+/// The `state` is passed through each step, since models are updated by value.
+fn run_chain<S>(
+    mut state: S,
+    first: Msg,
+    limit: usize,
+    mut step: impl FnMut(S, Msg) -> io::Result<(S, ControlFlow<(), Option<Msg>>)>,
+) -> io::Result<(S, ChainEnd)> {
+    let mut next = Some(first);
+    let mut handled = 0;
+    while let Some(msg) = next {
+        if handled == limit {
+            return Ok((state, ChainEnd::TooLong(msg)));
+        }
+        handled += 1;
+        let (new_state, flow) = step(state, msg)?;
+        state = new_state;
+        next = match flow {
+            ControlFlow::Continue(next) => next,
+            ControlFlow::Break(()) => return Ok((state, ChainEnd::Quit)),
+        };
     }
+    Ok((state, ChainEnd::Finished))
+}
+
+/// Run a model with the default options, returning the model as it was once the app quit.
+///
+/// This is the same as `App::new(model).run()`, use [`App`] to change any of the options.
+///
+/// ```no_run
+/// # use sketch::*;
+/// # #[derive(Default)]
+/// # struct Counter { count: usize }
+/// # impl Model for Counter {
+/// #     fn update(self, _: &Msg) -> (Self, Option<Msg>) { (self, None) }
+/// #     fn view(&self) -> String { String::new() }
+/// # }
+/// fn count() -> std::io::Result<usize> {
+///     let counter = sketch::run(Counter::default())?;
+///     Ok(counter.count)
+/// }
+/// ```
+pub fn run<M: Model>(model: M) -> io::Result<M> {
+    App::new(model).run()
 }
 
 /// A trait to turn your data in to something [`App`] can run.
 pub trait Model: Sized {
     /// Where any initial startup commands are sent.
+    ///
+    /// This runs before the first frame is drawn, so return a [`Task`] for anything slow rather
+    /// than doing it here.
     fn startup(&self) -> Option<Msg> {
         None
     }
@@ -188,33 +1130,231 @@ pub trait Model: Sized {
     fn update(self, msg: &Msg) -> (Self, Option<Msg>);
 
     /// Where the model is used to render a frame.
+    ///
+    /// This should only build the frame from the model without any side effects, since the app
+    /// decides when to call it. Frames are drawn once every waiting message has been given to
+    /// [`Model::update`], and each is written to the terminal in one go, so a partly updated
+    /// frame is never shown.
     fn view(&self) -> String;
+
+    /// Where the model is used to render a frame one line at a time, for views which are much
+    /// taller than the terminal.
+    ///
+    /// Lines are only taken until the terminal is full, so those below it never need to be
+    /// built. By default this splits up [`Model::view`], only override it if building the whole
+    /// view each frame is too slow.
+    ///
+    /// ```
+    /// # use sketch::*;
+    /// struct Log(Vec<String>);
+    ///
+    /// impl Model for Log {
+    ///     fn update(self, _: &Msg) -> (Self, Option<Msg>) { (self, None) }
+    ///
+    ///     fn view(&self) -> String {
+    ///         self.0.join("\n")
+    ///     }
+    ///
+    ///     fn view_lines(&self) -> Box<dyn Iterator<Item = String> + '_> {
+    ///         Box::new(self.0.iter().cloned())
+    ///     }
+    /// }
+    /// ```
+    fn view_lines(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        let view = self.view();
+        let lines: Vec<_> = view.split('\n').map(str::to_string).collect();
+        Box::new(lines.into_iter())
+    }
+
+    /// Where the model gives a block to draw on top of its view, such as a dialog, along with
+    /// the area to draw it in. Defaults to `None`, showing only the view.
+    ///
+    /// The block is cut or padded to the size of the area, see [`overlay`]. While it is shown,
+    /// mouse input outside of it is dropped and pressing escape sends [`DismissOverlay`]
+    /// instead, which can be changed with [`App::with_overlay_dismiss`]. The model still gets
+    /// every other message, it decides whether they are for the overlay or the view.
+    ///
+    /// ```
+    /// # use sketch::*;
+    /// struct Editor {
+    ///     confirm_quit: bool,
+    /// }
+    ///
+    /// impl Model for Editor {
+    ///     fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+    ///         if msg.is::<DismissOverlay>() {
+    ///             self.confirm_quit = false;
+    ///         } else if let Some(key) = msg.cast::<Key>() {
+    ///             if key.code == KeyCode::Char('y') && self.confirm_quit {
+    ///                 return (self, Some(Msg::new(Quit)));
+    ///             }
+    ///             self.confirm_quit = key.code == KeyCode::Char('q');
+    ///         }
+    ///         (self, None)
+    ///     }
+    ///
+    ///     fn view(&self) -> String {
+    ///         String::from("Some text being edited")
+    ///     }
+    ///
+    ///     fn overlay(&self) -> Option<(String, Rect)> {
+    ///         let dialog = String::from("Quit? (y/esc)");
+    ///         self.confirm_quit.then(|| (dialog, Rect::new(2, 1, 13, 1)))
+    ///     }
+    /// }
+    /// ```
+    fn overlay(&self) -> Option<(String, Rect)> {
+        None
+    }
+
+    /// Where the model says which regions of its view changed since the last frame, so only they
+    /// are redrawn, such as when only a clock has ticked.
+    ///
+    /// This is called once before each frame is drawn, after every waiting message has been
+    /// given to [`Model::update`], so the model should keep every region changed by those
+    /// updates and forget them once taken. The regions are drawn from the new view, anything
+    /// outside them is left as it was. Return `None`, the default, to redraw the whole frame.
+    ///
+    /// The whole frame is redrawn anyway for the first frame, after a resize or [`Redraw`], for
+    /// [inline](App::with_inline) apps and with [`ClearStrategy::Diff`], which finds what changed
+    /// itself.
+    ///
+    /// ```
+    /// # use sketch::*;
+    /// struct Clock {
+    ///     time: String,
+    ///     ticked: bool,
+    /// }
+    ///
+    /// impl Model for Clock {
+    ///     fn update(mut self, _: &Msg) -> (Self, Option<Msg>) {
+    ///         self.ticked = true;
+    ///         (self, None)
+    ///     }
+    ///
+    ///     fn view(&self) -> String {
+    ///         format!("The time is {}", self.time)
+    ///     }
+    ///
+    ///     fn take_dirty_regions(&mut self) -> Option<Vec<Rect>> {
+    ///         std::mem::take(&mut self.ticked).then(|| vec![Rect::new(12, 0, 8, 1)])
+    ///     }
+    /// }
+    /// ```
+    fn take_dirty_regions(&mut self) -> Option<Vec<Rect>> {
+        None
+    }
+
+    /// Where any cleanup is done when the app quits, such as saving state.
+    ///
+    /// This runs once a [`Quit`] message is received and before the terminal is restored, so
+    /// anything printed here is lost, return the data from [`App::run`] instead. It is not run
+    /// if the app panics or [`App::run`] returns an error.
+    fn on_quit(&self) {}
 }
 
-fn spawn_crossterm_event_thread(tx: Sender<Msg>) {
-    std::thread::spawn(move || loop {
-        let msg = match event::read().expect("Failed to read crossterm event") {
-            Event::FocusGained => Msg::new(Focus::Gained),
-            Event::FocusLost => Msg::new(Focus::Lost),
-            Event::Key(event) => Msg::new(Key::from(event)),
-            Event::Mouse(event) => Msg::new(Mouse::from(event)),
-            Event::Resize(width, height) => Msg::new(Resize { width, height }),
-
-            #[cfg(feature = "paste")]
-            Event::Paste(value) => Msg::new(msg::Paste(value)),
-            #[cfg(not(feature = "paste"))]
-            Event::Paste(_) => continue,
-        };
+fn ring_bell(stdout: &mut impl Write, visual: bool) -> io::Result<()> {
+    if visual {
+        // Reverse the screen's colors for a moment.
+        write!(stdout, "\x1b[?5h")?;
+        stdout.flush()?;
+        std::thread::sleep(Duration::from_millis(100));
+        write!(stdout, "\x1b[?5l")?;
+    } else {
+        write!(stdout, "\x07")?;
+    }
+    stdout.flush()
+}
 
-        tx.send(msg).expect("Failed to send on message channel");
-    });
+/// Is this message input from the user.
+fn is_input(msg: &Msg) -> bool {
+    #[cfg(feature = "paste")]
+    if msg.is::<Paste>() {
+        return true;
+    }
+    matches_msg!(msg, Key | Mouse)
 }
 
-fn set_panic_hook() {
-    let hook = std::panic::take_hook();
-    std::panic::set_hook(Box::new(move |info| {
-        let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
-        hook(info);
-    }));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A model which returns another message every time, as if by mistake.
+    struct Echo(usize);
+
+    impl Model for Echo {
+        fn update(self, _: &Msg) -> (Self, Option<Msg>) {
+            (Self(self.0 + 1), Some(Msg::new(Redraw)))
+        }
+
+        fn view(&self) -> String {
+            String::new()
+        }
+    }
+
+    /// A model which counts the messages it is given.
+    struct Counter(usize);
+
+    impl Model for Counter {
+        fn startup(&self) -> Option<Msg> {
+            Some(Msg::new(Redraw))
+        }
+
+        fn update(self, _: &Msg) -> (Self, Option<Msg>) {
+            (Self(self.0 + 1), None)
+        }
+
+        fn view(&self) -> String {
+            Style::new().bold().render(format!("Count: {}", self.0))
+        }
+    }
+
+    #[test]
+    fn test_piped_prints_final_view() {
+        let app = App::new(Counter(0));
+        app.sender().send(Msg::new(Redraw)).unwrap();
+        let mut out = Vec::new();
+        let model = app.run_piped(&mut out).unwrap();
+        assert_eq!(model.0, 2);
+        assert_eq!(String::from_utf8(out).unwrap(), "Count: 2\n");
+
+        let app = App::new(Counter(0)).with_piped_output(PipedOutput::Styled);
+        app.sender().send(Msg::new(Quit)).unwrap();
+        app.sender().send(Msg::new(Redraw)).unwrap();
+        let mut out = Vec::new();
+        let model = app.run_piped(&mut out).unwrap();
+        assert_eq!(model.0, 0);
+        assert_eq!(String::from_utf8(out).unwrap(), "\x1b[1mCount: 0\x1b[0m\n");
+    }
+
+    #[test]
+    fn test_piped_runs_tasks() {
+        let app = App::new(Counter(0));
+        let task = Task::new(|| Msg::new(Redraw));
+        app.sender().send(Msg::new(task)).unwrap();
+        let model = app.run_piped(&mut Vec::new()).unwrap();
+        // The startup message and the task's message.
+        assert_eq!(model.0, 2);
+    }
+
+    #[test]
+    fn test_piped_sends_batches() {
+        let app = App::new(Counter(0));
+        let batch = Batch::new([Msg::new(Redraw), Msg::new(Redraw)]);
+        app.sender().send(Msg::new(batch)).unwrap();
+        let model = app.run_piped(&mut Vec::new()).unwrap();
+        // The startup message and both messages from the batch.
+        assert_eq!(model.0, 3);
+    }
+
+    #[test]
+    fn test_endless_chain_is_cut_off() {
+        let (model, end) = run_chain(Echo(0), Msg::new(Redraw), 100, |model, msg| {
+            let (model, next) = model.update(&msg);
+            Ok((model, ControlFlow::Continue(next)))
+        })
+        .unwrap();
+        assert_eq!(model.0, 100);
+        assert!(matches!(end, ChainEnd::TooLong(msg) if msg.is::<Redraw>()));
+    }
 }
@@ -0,0 +1,123 @@
+use crate::{text, Align, Style};
+
+/// A single line built from pieces of text which each have their own [`Style`], such as a
+/// breadcrumb or status bar.
+///
+/// Next to each other pieces with the same style are rendered together, so there are only as
+/// many escape codes as there are changes in style.
+///
+/// ```
+/// # use sketch::{Align, Line, Style};
+/// const DIR: Style = Style::new().bold();
+/// const SLASH: Style = Style::new().dim();
+///
+/// let line = Line::new().push("home", &DIR).push("/", &SLASH).push("docs", &DIR);
+/// assert_eq!(line.width(), 9);
+/// let text = line.render(20, Align::Right);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Line {
+    segments: Vec<(String, Style)>,
+    spacing: usize,
+}
+
+impl Line {
+    /// Create a new empty [`Line`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of spaces put between each piece. Defaults to `0`.
+    pub fn with_spacing(mut self, spacing: usize) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Add a piece of text with a style to the end of the line.
+    pub fn push(mut self, text: impl Into<String>, style: &Style) -> Self {
+        self.segments.push((text.into(), style.clone()));
+        self
+    }
+
+    /// The number of columns the line takes up, not including alignment.
+    pub fn width(&self) -> usize {
+        let spacing = self.spacing * self.segments.len().saturating_sub(1);
+        let text: usize = self.segments.iter().map(|(t, _)| text::width(t)).sum();
+        text + spacing
+    }
+
+    /// Render the line aligned within `cols` columns.
+    ///
+    /// Lines wider than `cols` are left as they are. The alignment of each piece's style is
+    /// ignored.
+    pub fn render(&self, cols: usize, align: Align) -> String {
+        let free = cols.saturating_sub(self.width());
+        let padding = match align {
+            Align::Left => 0,
+            Align::Center => free / 2,
+            Align::Right => free,
+        };
+
+        let mut line = " ".repeat(padding);
+        let mut run = String::new();
+        let mut run_style: Option<&Style> = None;
+        let spacing = " ".repeat(self.spacing);
+
+        for (text, style) in &self.segments {
+            match run_style {
+                Some(run_style) if run_style == style => run.push_str(&spacing),
+                Some(run_style) => {
+                    // Keep the spacing between differently styled pieces unstyled.
+                    push_run(&mut line, &run, run_style);
+                    line.push_str(&spacing);
+                    run.clear();
+                }
+                None => {}
+            }
+            run_style = Some(style);
+            run.push_str(text);
+        }
+        if let Some(run_style) = run_style {
+            push_run(&mut line, &run, run_style);
+        }
+
+        line
+    }
+}
+
+/// Append text to a line with a style, leaving unstyled text without escape codes.
+fn push_run(line: &mut String, text: &str, style: &Style) {
+    let style = style.clone().align(Align::Left);
+    if style == Style::new() {
+        line.push_str(text);
+    } else {
+        line.push_str(&style.render_in(text, 0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_styles_are_joined() {
+        let bold = Style::new().bold();
+        let line = Line::new()
+            .push("a", &bold)
+            .push("b", &bold)
+            .push("c", &Style::new());
+        assert_eq!(line.render(0, Align::Left), "\x1b[1mab\x1b[0mc");
+    }
+
+    #[test]
+    fn test_spacing_and_alignment() {
+        let line = Line::new()
+            .with_spacing(1)
+            .push("a", &Style::new())
+            .push("b", &Style::new().dim());
+        assert_eq!(line.width(), 3);
+        assert_eq!(line.render(7, Align::Center), "  a \x1b[2mb\x1b[0m");
+        assert_eq!(line.render(5, Align::Right), "  a \x1b[2mb\x1b[0m");
+        assert_eq!(line.render(1, Align::Right), "a \x1b[2mb\x1b[0m");
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{
+    capabilities,
+    text::{is_reset, tokens, Token, RESET},
+    Style,
+};
+
+/// The styles used by [`render_inline`].
+#[derive(Debug, Clone)]
+pub struct InlineTheme {
+    /// The style for `*bold*` text.
+    pub bold: Style,
+    /// The style for `_italic_` text.
+    pub italic: Style,
+    /// The style for `` `code` `` text.
+    pub code: Style,
+}
+
+impl Default for InlineTheme {
+    fn default() -> Self {
+        Self {
+            bold: Style::new().bold(),
+            italic: Style::new().italic(),
+            code: Style::new().cyan(),
+        }
+    }
+}
+
+/// Render text with a small inline markup syntax.
+///
+/// * `*text*` is rendered with [`InlineTheme::bold`].
+/// * `_text_` is rendered with [`InlineTheme::italic`].
+/// * `` `text` `` is rendered with [`InlineTheme::code`]. Everything up to the closing backtick
+///   is kept as it is, including other markers and backslashes.
+///
+/// Bold and italic can be nested in each other, such as `*bold _and italic_*`. A marker closes
+/// the innermost open span it matches, and any spans opened inside that one are left as text. A
+/// marker which is never closed is kept as it is and a backslash makes the character after it
+/// be kept as it is, such as `\*` for a literal `*`. Any ANSI escape codes already in the text
+/// are kept as they are and never treated as markup, so the rendered text has the same visible
+/// width as the markup without its markers.
+///
+/// ```
+/// # use sketch::{render_inline, InlineTheme};
+/// let text = render_inline("Press `q` to *quit*", &InlineTheme::default());
+/// ```
+pub fn render_inline(markup: &str, theme: &InlineTheme) -> String {
+    let tokens: Vec<Token> = tokens(markup).collect();
+    render(&parse(&tokens), theme)
+}
+
+enum Event<'a> {
+    Text(String),
+    Escape(&'a str),
+    /// The start of a span, closed by the next [`Event::Close`] at the same depth.
+    Open(char),
+    Close,
+}
+
+/// Parse the markup in to a flat list of events, without recursing so deeply nested input can't
+/// overflow the stack.
+fn parse<'a>(tokens: &[Token<'a>]) -> Vec<Event<'a>> {
+    let mut events = Vec::new();
+    let mut text = String::new();
+    // The spans which haven't been closed yet, with where each one's `Open` is in `events`.
+    let mut open: Vec<(char, usize)> = Vec::new();
+    let mut pos = 0;
+
+    while let Some(&token) = tokens.get(pos) {
+        pos += 1;
+        let c = match token {
+            Token::Escape(code) => {
+                flush(&mut events, &mut text);
+                events.push(Event::Escape(code));
+                continue;
+            }
+            Token::Char(c) => c,
+        };
+
+        match c {
+            '\\' => match tokens.get(pos) {
+                Some(Token::Char(next)) => {
+                    text.push(*next);
+                    pos += 1;
+                }
+                _ => text.push('\\'),
+            },
+            '`' => {
+                let end = tokens[pos..]
+                    .iter()
+                    .position(|&t| t == Token::Char('`'))
+                    .map(|i| pos + i);
+                match end {
+                    Some(end) => {
+                        flush(&mut events, &mut text);
+                        let code = tokens[pos..end]
+                            .iter()
+                            .map(|t| match t {
+                                Token::Char(c) => c.to_string(),
+                                Token::Escape(code) => code.to_string(),
+                            })
+                            .collect();
+                        events.extend([Event::Open('`'), Event::Text(code), Event::Close]);
+                        pos = end + 1;
+                    }
+                    None => text.push('`'),
+                }
+            }
+            '*' | '_' => {
+                flush(&mut events, &mut text);
+                match open.iter().rposition(|&(marker, _)| marker == c) {
+                    // An empty span is just text, this marker may start another.
+                    Some(n) if open[n].1 + 1 == events.len() => {
+                        unclose(&mut events, open.drain(n..));
+                        open.push((c, events.len()));
+                        events.push(Event::Open(c));
+                    }
+                    // Spans opened inside this one which are still open are never closed.
+                    Some(n) => {
+                        unclose(&mut events, open.drain(n + 1..));
+                        open.pop();
+                        events.push(Event::Close);
+                    }
+                    None => {
+                        open.push((c, events.len()));
+                        events.push(Event::Open(c));
+                    }
+                }
+            }
+            c => text.push(c),
+        }
+    }
+
+    flush(&mut events, &mut text);
+    unclose(&mut events, open.drain(..));
+    events
+}
+
+fn flush(events: &mut Vec<Event>, text: &mut String) {
+    if !text.is_empty() {
+        events.push(Event::Text(std::mem::take(text)));
+    }
+}
+
+/// Turn the markers of spans which are never closed back in to text.
+fn unclose(events: &mut [Event], spans: impl Iterator<Item = (char, usize)>) {
+    for (marker, start) in spans {
+        events[start] = Event::Text(marker.to_string());
+    }
+}
+
+/// Render the events, only writing each style's codes once per change in the styles so deeply
+/// nested spans don't repeat the codes of every span around them.
+fn render(events: &[Event], theme: &InlineTheme) -> String {
+    let capabilities = capabilities();
+    let mut result = String::new();
+    // The codes of each open span, the first `applied` of which are in effect.
+    let mut active: Vec<String> = Vec::new();
+    let mut applied = 0;
+    // Whether codes of spans which have since closed are still in effect.
+    let mut stale = false;
+    // Whether any codes have been written since the last reset.
+    let mut styled = false;
+
+    for event in events {
+        match event {
+            Event::Open(marker) => {
+                let style = match marker {
+                    '*' => &theme.bold,
+                    '_' => &theme.italic,
+                    _ => &theme.code,
+                };
+                active.push(style.codes(capabilities));
+            }
+            Event::Close => {
+                active.pop();
+                if applied > active.len() {
+                    applied = active.len();
+                    stale = true;
+                }
+            }
+            Event::Escape(code) => {
+                result.push_str(code);
+                if is_reset(code) {
+                    (applied, stale, styled) = (0, false, false);
+                }
+            }
+            Event::Text(text) => {
+                if stale {
+                    if styled {
+                        result.push_str(RESET);
+                        styled = false;
+                    }
+                    (applied, stale) = (0, false);
+                }
+                for codes in &active[applied..] {
+                    result.push_str(codes);
+                    styled |= !codes.is_empty();
+                }
+                applied = active.len();
+                result.push_str(text);
+            }
+        }
+    }
+
+    if styled {
+        result.push_str(RESET);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(markup: &str) -> String {
+        render_inline(markup, &InlineTheme::default())
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(render("just text"), "just text");
+    }
+
+    #[test]
+    fn test_bold_and_italic() {
+        assert_eq!(render("a *b* _c_"), "a \x1b[1mb\x1b[0m \x1b[3mc\x1b[0m");
+    }
+
+    #[test]
+    fn test_code_is_literal() {
+        assert_eq!(render("`*a*\\`"), "\x1b[96m*a*\\\x1b[0m");
+    }
+
+    #[test]
+    fn test_nested() {
+        assert_eq!(
+            render("*a _b_ c*"),
+            "\x1b[1ma \x1b[3mb\x1b[0m\x1b[1m c\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_escaped_markers() {
+        assert_eq!(render("\\*a\\* \\_b\\_ \\`c\\` \\\\"), "*a* _b_ `c` \\");
+    }
+
+    #[test]
+    fn test_unclosed_markers() {
+        assert_eq!(render("2*3 and a_b"), "2*3 and a_b");
+        assert_eq!(render("*bold* *open"), "\x1b[1mbold\x1b[0m *open");
+        assert_eq!(render("`open"), "`open");
+        assert_eq!(render("*a\\*"), "*a*");
+        assert_eq!(render("** __"), "** __");
+    }
+
+    #[test]
+    fn test_alternating_markers() {
+        assert_eq!(render("*_*_"), "\x1b[1m_\x1b[0m_");
+        assert_eq!(render("*_x_*"), "\x1b[1m\x1b[3mx\x1b[0m");
+
+        // Each span's codes are only written once, however many markers there are.
+        let markup = "*_".repeat(100_000);
+        assert!(render(&markup).len() < markup.len() * 8);
+        let markup = format!("{}x{}", "*_".repeat(100_000), "_*".repeat(100_000));
+        assert!(render(&markup).len() < markup.len() * 8);
+    }
+
+    #[test]
+    fn test_keeps_escape_codes() {
+        assert_eq!(render("\x1b[38;5;9m*a*"), "\x1b[38;5;9m\x1b[1ma\x1b[0m");
+    }
+}
@@ -1,4 +1,12 @@
-use std::any::Any;
+use std::{
+    any::Any,
+    io,
+    process::{Command, ExitStatus},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
 use crossterm::event::{
     KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
@@ -6,15 +14,33 @@ use crossterm::event::{
 
 pub use crossterm::event::KeyCode;
 
+use crate::Color;
+
+static VIM_KEYS: AtomicBool = AtomicBool::new(false);
+
 /// A type containing a [`Message`] implementing type.
 pub struct Msg {
     msg: Box<dyn Any + Send>,
+    name: &'static str,
 }
 
 impl Msg {
     /// Create a new [`Msg`] from a type implementing [`Message`].
     pub fn new<M: Message + 'static>(msg: M) -> Self {
-        Self { msg: Box::new(msg) }
+        Self {
+            name: msg.debug_name(),
+            msg: Box::new(msg),
+        }
+    }
+
+    /// The name of the contained [`Message`], see [`Message::debug_name`].
+    ///
+    /// ```
+    /// # use sketch::*;
+    /// assert_eq!(Msg::new(Quit).debug_name(), "sketch::msg::Quit");
+    /// ```
+    pub fn debug_name(&self) -> &'static str {
+        self.name
     }
 
     /// Try convert this [`Msg`] to a explicit [`Message`] implementing type.
@@ -28,8 +54,42 @@ impl Msg {
     }
 }
 
+impl std::fmt::Debug for Msg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Msg").field(&self.name).finish()
+    }
+}
+
 /// A trait to allow a type to be used as a [`Msg`].
-pub trait Message: Send {}
+pub trait Message: Send {
+    /// A name for this message to show when debugging or logging.
+    ///
+    /// Defaults to the name of the type.
+    fn debug_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Check whether a [`Msg`] holds any of several [`Message`] types.
+///
+/// This is shorthand for chaining [`Msg::is`] with `||`.
+///
+/// ```
+/// # use sketch::*;
+/// fn is_input(msg: &Msg) -> bool {
+///     matches_msg!(msg, Key | Mouse | Focus)
+/// }
+///
+/// assert!(!is_input(&Msg::new(Quit)));
+/// assert!(matches_msg!(Msg::new(Quit), Redraw | Quit));
+/// ```
+#[macro_export]
+macro_rules! matches_msg {
+    ($msg:expr, $($ty:ty)|+ $(,)?) => {{
+        let msg: &$crate::Msg = &$msg;
+        false $(|| msg.is::<$ty>())+
+    }};
+}
 
 macro_rules! matches_method {
     ($method:ident, $field:ident, $value:pat, $doc:literal) => {
@@ -54,6 +114,310 @@ macro_rules! bitflags_method {
 pub struct Quit;
 impl Message for Quit {}
 
+/// A message to redraw the app.
+///
+/// The model's view is rendered again and the whole frame is redrawn. This is useful for a
+/// background thread that changed something the view reads, such as shared state behind a mutex,
+/// and has no message of its own to send.
+///
+/// ```no_run
+/// # use sketch::*;
+/// # let app = App::new(Counter);
+/// let sender = app.sender();
+/// std::thread::spawn(move || {
+///     // ...
+///     let _ = sender.send(Msg::new(Redraw));
+/// });
+/// # struct Counter;
+/// # impl Model for Counter {
+/// #     fn update(self, _: &Msg) -> (Self, Option<Msg>) { (self, None) }
+/// #     fn view(&self) -> String { String::new() }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Redraw;
+impl Message for Redraw {}
+
+/// A message to move animations, such as [`Skeleton`](crate::widgets::Skeleton), on to their
+/// next frame.
+///
+/// This is not sent by the app, send it at whatever rate animations should run at from a thread
+/// using [`App::sender`](crate::App::sender).
+#[derive(Debug)]
+pub struct Tick;
+impl Message for Tick {}
+
+/// A message to write an escape sequence straight to the terminal, such as one the crate has no
+/// message for.
+///
+/// The sequence is written as it is before the next frame is drawn. It is not checked in any way
+/// and the renderer doesn't know about it, so a sequence which moves the cursor, changes the
+/// screen or leaves styling enabled can corrupt what is drawn. Send [`Redraw`] afterwards to
+/// redraw the whole frame if needed.
+///
+/// ```
+/// # use sketch::*;
+/// // Change the cursor to a bar.
+/// let msg = Msg::new(RawSequence(String::from("\x1b[6 q")));
+/// ```
+#[derive(Debug)]
+pub struct RawSequence(pub String);
+impl Message for RawSequence {}
+
+/// The shape of the terminal's cursor, set with [`SetCursorShape`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CursorShape {
+    /// The shape the user has configured their terminal to use.
+    #[default]
+    Default,
+    /// A block covering the whole cell.
+    Block,
+    /// A blinking block covering the whole cell.
+    BlinkingBlock,
+    /// A line under the cell.
+    Underline,
+    /// A blinking line under the cell.
+    BlinkingUnderline,
+    /// A line before the cell.
+    Bar,
+    /// A blinking line before the cell.
+    BlinkingBar,
+}
+
+impl CursorShape {
+    /// The DECSCUSR escape sequence which sets this shape.
+    pub(crate) fn sequence(self) -> &'static str {
+        match self {
+            CursorShape::Default => "\x1b[0 q",
+            CursorShape::BlinkingBlock => "\x1b[1 q",
+            CursorShape::Block => "\x1b[2 q",
+            CursorShape::BlinkingUnderline => "\x1b[3 q",
+            CursorShape::Underline => "\x1b[4 q",
+            CursorShape::BlinkingBar => "\x1b[5 q",
+            CursorShape::Bar => "\x1b[6 q",
+        }
+    }
+}
+
+/// A message to change the shape of the cursor, such as to a bar while typing.
+///
+/// The shape is put back to [`CursorShape::Default`] when the app exits.
+#[derive(Debug)]
+pub struct SetCursorShape(pub CursorShape);
+impl Message for SetCursorShape {}
+
+/// A message with the terminal's background color, sent on startup if
+/// [`App::with_background_query`](crate::App::with_background_query) is enabled and the terminal
+/// answers.
+#[derive(Debug)]
+pub struct TerminalBackground(pub Color);
+impl Message for TerminalBackground {}
+
+impl TerminalBackground {
+    /// Is the background dark, so light text should be used on it.
+    ///
+    /// Colors other than [`Color::Rgb`] are assumed to be dark.
+    pub fn is_dark(&self) -> bool {
+        match self.0 {
+            // Perceived brightness, from ITU-R BT.601.
+            Color::Rgb { r, g, b } => {
+                (299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000 < 128
+            }
+            _ => true,
+        }
+    }
+}
+
+/// A message to print a line above an [inline](crate::App::with_inline) app, such as for a log
+/// of what it has done.
+///
+/// The text is printed where the app was and the app is drawn again below it, so the text
+/// scrolls in to the terminal's history like any other output while the app stays at the
+/// bottom. Control characters are handled the same as in the view, see
+/// [`App::with_control_chars`](crate::App::with_control_chars).
+///
+/// Apps drawn to the alternate screen have no history to print in to, so ignore this.
+///
+/// ```
+/// # use sketch::*;
+/// let msg = Msg::new(PrintAbove(String::from("Downloaded archive.tar.gz")));
+/// ```
+#[derive(Debug)]
+pub struct PrintAbove(pub String);
+impl Message for PrintAbove {}
+
+/// A message to ring the terminal bell.
+///
+/// If [`App::with_visual_bell`](crate::App::with_visual_bell) is enabled the screen is flashed
+/// instead of making a sound.
+#[derive(Debug)]
+pub struct Bell;
+impl Message for Bell {}
+
+/// A message to show a desktop notification, such as when a long running task has finished.
+///
+/// This uses the OSC 777 escape sequence in foot and terminals based on VTE such as GNOME
+/// Terminal, and OSC 9 everywhere else, which is supported by kitty, WezTerm, ghostty, iTerm2 and
+/// Windows Terminal. OSC 9 has no title, so the title and body are shown together. Unsupported
+/// terminals silently ignore it.
+///
+/// By default notifications are only shown while the terminal is out of focus (see [`Focus`]),
+/// change this with [`App::with_focused_notifications`](crate::App::with_focused_notifications).
+/// Terminals which don't report focus changes are assumed to be in focus.
+///
+/// ```
+/// # use sketch::*;
+/// let msg = Msg::new(Notify::new("Download finished", "archive.tar.gz"));
+/// ```
+#[derive(Debug)]
+pub struct Notify {
+    /// The title of the notification.
+    pub title: String,
+    /// The text of the notification.
+    pub body: String,
+}
+impl Message for Notify {}
+
+impl Notify {
+    /// Create a new [`Notify`] message.
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// A message to run a command which takes over the terminal, such as opening a text editor.
+///
+/// While the command runs the app stops reading input and puts the terminal back in to its
+/// normal state so the command gets all the input. Once it exits the app is redrawn and an
+/// [`ExecFinished`] message is sent.
+///
+/// ```no_run
+/// # use sketch::*;
+/// let msg = Msg::new(Exec::new(std::process::Command::new("vim")));
+/// ```
+#[derive(Debug)]
+pub struct Exec(Mutex<Option<Command>>);
+impl Message for Exec {}
+
+impl Exec {
+    /// Create a new [`Exec`] to run `command`.
+    pub fn new(command: Command) -> Self {
+        Self(Mutex::new(Some(command)))
+    }
+
+    /// Take the command out to run it, this is `None` if it has already been taken.
+    pub(crate) fn take(&self) -> Option<Command> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+/// A message to run slow work, such as reading a file or making a network request, on another
+/// thread so the app keeps drawing and handling input while it runs.
+///
+/// The closure is run on a new thread and the message it returns is sent to the app once it is
+/// done. Returning a task from [`Model::startup`](crate::Model::startup) lets the first frame be
+/// drawn straight away, showing a loading state until the result arrives.
+///
+/// ```
+/// # use sketch::*;
+/// struct Config(String);
+/// impl Message for Config {}
+///
+/// let msg = Msg::new(Task::new(|| {
+///     let config = std::fs::read_to_string("config.toml").unwrap_or_default();
+///     Msg::new(Config(config))
+/// }));
+/// ```
+pub struct Task(Mutex<Option<Box<dyn FnOnce() -> Msg + Send>>>);
+impl Message for Task {}
+
+impl Task {
+    /// Create a new [`Task`] to run `task`, sending the message it returns.
+    pub fn new(task: impl FnOnce() -> Msg + Send + 'static) -> Self {
+        Self(Mutex::new(Some(Box::new(task))))
+    }
+
+    /// Take the closure out to run it, this is `None` if it has already been taken.
+    pub(crate) fn take(&self) -> Option<Box<dyn FnOnce() -> Msg + Send>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+impl std::fmt::Debug for Task {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Task").finish_non_exhaustive()
+    }
+}
+
+/// A message to send several messages at once, such as from [`Model::startup`](crate::Model::startup)
+/// when more than one thing needs starting.
+///
+/// Each message is handled in order as if it had been sent on its own.
+///
+/// ```
+/// # use sketch::*;
+/// let msg = Msg::new(Batch::new([Msg::new(Bell), Msg::new(Redraw)]));
+/// ```
+pub struct Batch(Mutex<Vec<Msg>>);
+impl Message for Batch {}
+
+impl Batch {
+    /// Create a new [`Batch`] of `msgs`.
+    pub fn new(msgs: impl IntoIterator<Item = Msg>) -> Self {
+        Self(Mutex::new(msgs.into_iter().collect()))
+    }
+
+    /// Take the messages out to send them, this is empty if they have already been taken.
+    pub(crate) fn take(&self) -> Vec<Msg> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl std::fmt::Debug for Batch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Batch").finish_non_exhaustive()
+    }
+}
+
+/// A message sent once the command from an [`Exec`] has exited.
+#[derive(Debug)]
+pub struct ExecFinished(pub io::Result<ExitStatus>);
+impl Message for ExecFinished {}
+
+/// A message sent in place of an escape key press while the model shows an
+/// [overlay](crate::Model::overlay), for the model to hide it.
+///
+/// Which messages are replaced is set with
+/// [`App::with_overlay_dismiss`](crate::App::with_overlay_dismiss).
+#[derive(Debug)]
+pub struct DismissOverlay;
+impl Message for DismissOverlay {}
+
+/// A message sent when [`Model::update`](crate::Model::update) returned more messages one after
+/// another than [`App::with_max_chained_messages`](crate::App::with_max_chained_messages)
+/// allows, usually because of a bug which returns a message every time.
+///
+/// This holds the [`Msg::debug_name`] of the message which was dropped.
+#[derive(Debug)]
+pub struct ChainLimitReached(pub &'static str);
+impl Message for ChainLimitReached {}
+
+/// A message to copy text to the system clipboard.
+///
+/// This uses the OSC 52 escape sequence so it works over SSH without any native clipboard
+/// support, but only in terminals that implement it such as kitty, WezTerm, Alacritty, iTerm2,
+/// foot and xterm (when `allowWindowOps` is enabled). tmux only forwards it with
+/// `set-clipboard on`. Unsupported terminals silently ignore it.
+///
+/// Many terminals limit how much text can be copied this way, often to around 100 KB or less
+/// once base64 encoded, so prefer it for short values.
+#[derive(Debug)]
+pub struct SetClipboard(pub String);
+impl Message for SetClipboard {}
+
 /// A message keyboard input.
 #[derive(Debug)]
 pub struct Key {
@@ -62,6 +426,8 @@ pub struct Key {
     modifiers: KeyModifiers,
     kind: KeyEventKind,
     state: KeyEventState,
+    burst: bool,
+    raw: KeyEvent,
 }
 impl Message for Key {}
 
@@ -78,6 +444,119 @@ impl Key {
     bitflags_method! { from_keypad, state, KeyEventState, KEYPAD, "Is the key in the keypad part of the keyboard" }
     bitflags_method! { with_capslock, state, KeyEventState, CAPS_LOCK, "Was caps-lock active" }
     bitflags_method! { with_numlock, state, KeyEventState, NUM_LOCK, "Was num-lock active" }
+
+    /// Did this key arrive in a quick burst of input, such as from text being pasted.
+    ///
+    /// Pasted text is given to `Paste` messages when the `paste` feature is enabled and the
+    /// terminal supports bracketed paste, otherwise it arrives as a key for each character. This
+    /// guesses which keys were pasted from how close together they are, so an app can avoid
+    /// treating pasted text as commands. Very fast typing or key repeat may also be reported as
+    /// a burst.
+    pub fn is_burst(&self) -> bool {
+        self.burst
+    }
+
+    /// The crossterm event this key was read from, for passing on to other crossterm based
+    /// libraries or reading anything not exposed here.
+    ///
+    /// This is the event type from the version of crossterm this crate depends on, so it can
+    /// change in any release which updates crossterm.
+    pub fn raw(&self) -> &KeyEvent {
+        &self.raw
+    }
+
+    pub(crate) fn with_burst(mut self, burst: bool) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    matches_method! { is_enter, code, KeyCode::Enter, "Is this the enter key" }
+    /// Is this the escape key.
+    ///
+    /// Terminals send keys pressed with alt as an escape followed by the key, which is read as a
+    /// single key with [`Key::with_alt`] when they arrive together. An escape with nothing
+    /// straight after it is sent as soon as it is read, so it can be used to cancel without a
+    /// delay.
+    pub fn is_escape(&self) -> bool {
+        matches!(self.code, KeyCode::Esc)
+    }
+    matches_method! { is_tab, code, KeyCode::Tab, "Is this the tab key, not including shift+tab" }
+
+    /// Is this the up arrow, or `k` if [vim keys](set_vim_keys) are enabled.
+    pub fn is_up(&self) -> bool {
+        self.is_arrow(KeyCode::Up, 'k', vim_keys())
+    }
+
+    /// Is this the down arrow, or `j` if [vim keys](set_vim_keys) are enabled.
+    pub fn is_down(&self) -> bool {
+        self.is_arrow(KeyCode::Down, 'j', vim_keys())
+    }
+
+    /// Is this the left arrow, or `h` if [vim keys](set_vim_keys) are enabled.
+    pub fn is_left(&self) -> bool {
+        self.is_arrow(KeyCode::Left, 'h', vim_keys())
+    }
+
+    /// Is this the right arrow, or `l` if [vim keys](set_vim_keys) are enabled.
+    pub fn is_right(&self) -> bool {
+        self.is_arrow(KeyCode::Right, 'l', vim_keys())
+    }
+
+    /// Is this the `arrow` key, or `vim_key` if `vim_keys` is set.
+    fn is_arrow(&self, arrow: KeyCode, vim_key: char, vim_keys: bool) -> bool {
+        self.code == arrow
+            || (vim_keys
+                && self.code == KeyCode::Char(vim_key)
+                && !self.with_control()
+                && !self.with_alt())
+    }
+
+    /// What the key means taking num-lock in to account.
+    ///
+    /// Keypad keys are digits while num-lock is active and navigation keys otherwise, but not all
+    /// terminals report them that way. This gives the navigation key for a keypad digit without
+    /// num-lock and the digit for a keypad navigation key with num-lock. Other keys are returned
+    /// as they are.
+    pub fn effective_code(&self) -> KeyCode {
+        if !self.from_keypad() {
+            return self.code;
+        }
+
+        const KEYPAD: [(char, KeyCode); 11] = [
+            ('0', KeyCode::Insert),
+            ('1', KeyCode::End),
+            ('2', KeyCode::Down),
+            ('3', KeyCode::PageDown),
+            ('4', KeyCode::Left),
+            ('5', KeyCode::KeypadBegin),
+            ('6', KeyCode::Right),
+            ('7', KeyCode::Home),
+            ('8', KeyCode::Up),
+            ('9', KeyCode::PageUp),
+            ('.', KeyCode::Delete),
+        ];
+
+        let mapped = if self.with_numlock() {
+            KEYPAD
+                .iter()
+                .find(|(_, nav)| *nav == self.code)
+                .map(|(c, _)| KeyCode::Char(*c))
+        } else {
+            KEYPAD
+                .iter()
+                .find(|(c, _)| KeyCode::Char(*c) == self.code)
+                .map(|(_, nav)| *nav)
+        };
+        mapped.unwrap_or(self.code)
+    }
+
+    /// The digit this key types, if any, taking num-lock in to account for keypad keys.
+    pub fn as_digit(&self) -> Option<u8> {
+        match self.effective_code() {
+            KeyCode::Char(c) => c.to_digit(10).map(|d| d as u8),
+            _ => None,
+        }
+    }
 }
 
 impl From<KeyEvent> for Key {
@@ -87,6 +566,39 @@ impl From<KeyEvent> for Key {
             modifiers: value.modifiers,
             kind: value.kind,
             state: value.state,
+            burst: false,
+            raw: value,
+        }
+    }
+}
+
+/// Are `h`, `j`, `k` and `l` treated as arrow keys by [`Key::is_left`] and friends.
+pub fn vim_keys() -> bool {
+    VIM_KEYS.load(Ordering::Relaxed)
+}
+
+/// Set whether `h`, `j`, `k` and `l` are treated as arrow keys by [`Key::is_left`] and friends
+/// for the whole program, see also [`App::with_vim_keys`](crate::App::with_vim_keys).
+///
+/// Leave this disabled if the keys are used for typing, such as in a text input.
+pub fn set_vim_keys(enabled: bool) {
+    VIM_KEYS.store(enabled, Ordering::Relaxed);
+}
+
+/// Sets whether vim keys are enabled while an [`App`](crate::App) is running, putting back the
+/// previous setting when dropped.
+pub(crate) struct VimKeysGuard(Option<bool>);
+
+impl VimKeysGuard {
+    pub(crate) fn new(enabled: Option<bool>) -> Self {
+        Self(enabled.map(|enabled| VIM_KEYS.swap(enabled, Ordering::Relaxed)))
+    }
+}
+
+impl Drop for VimKeysGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0 {
+            VIM_KEYS.store(previous, Ordering::Relaxed);
         }
     }
 }
@@ -100,10 +612,20 @@ pub struct Mouse {
     pub column: u16,
     /// The row the pointer was over.
     pub row: u16,
+    raw: MouseEvent,
 }
 impl Message for Mouse {}
 
 impl Mouse {
+    /// The crossterm event this mouse input was read from, for passing on to other crossterm
+    /// based libraries or reading anything not exposed here.
+    ///
+    /// This is the event type from the version of crossterm this crate depends on, so it can
+    /// change in any release which updates crossterm.
+    pub fn raw(&self) -> &MouseEvent {
+        &self.raw
+    }
+
     /// Was this message emitted by the left mouse button.
     pub fn is_left(&self) -> bool {
         matches!(
@@ -163,6 +685,7 @@ impl From<MouseEvent> for Mouse {
             column: value.column,
             row: value.row,
             modifiers: value.modifiers,
+            raw: value,
         }
     }
 }
@@ -178,11 +701,32 @@ pub enum Focus {
 impl Message for Focus {}
 
 /// A message for user pasting from clipboard.
+///
+/// Only sent if the terminal supports bracketed paste, otherwise see [`Key::is_burst`].
 #[cfg(feature = "paste")]
 pub struct Paste(pub String);
 #[cfg(feature = "paste")]
 impl Message for Paste {}
 
+/// A message to ask the terminal for the contents of the system clipboard.
+///
+/// If the terminal answers, the contents are sent as a [`Clipboard`] message. This uses the OSC
+/// 52 escape sequence which fewer terminals allow reading than writing (see [`SetClipboard`]), and
+/// some will ask the user for permission first. If there is no answer within a second then no
+/// message is sent.
+#[cfg(feature = "paste")]
+#[derive(Debug)]
+pub struct RequestClipboard;
+#[cfg(feature = "paste")]
+impl Message for RequestClipboard {}
+
+/// A message with the contents of the system clipboard after a [`RequestClipboard`].
+#[cfg(feature = "paste")]
+#[derive(Debug)]
+pub struct Clipboard(pub String);
+#[cfg(feature = "paste")]
+impl Message for Clipboard {}
+
 /// A message for terminal window resizing.
 pub struct Resize {
     /// The number of columns available.
@@ -191,3 +735,112 @@ pub struct Resize {
     pub height: u16,
 }
 impl Message for Resize {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keypad(code: KeyCode, numlock: bool) -> Key {
+        let mut state = KeyEventState::KEYPAD;
+        if numlock {
+            state |= KeyEventState::NUM_LOCK;
+        }
+        Key::from(KeyEvent::new_with_kind_and_state(
+            code,
+            KeyModifiers::NONE,
+            KeyEventKind::Press,
+            state,
+        ))
+    }
+
+    #[test]
+    fn test_background_is_dark() {
+        let black = TerminalBackground(Color::Rgb { r: 0, g: 0, b: 0 });
+        let white = TerminalBackground(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+        let yellow = TerminalBackground(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 0,
+        });
+        assert!(black.is_dark());
+        assert!(!white.is_dark());
+        assert!(!yellow.is_dark());
+    }
+
+    #[test]
+    fn test_navigation_keys() {
+        let key = |code| Key::from(KeyEvent::new(code, KeyModifiers::NONE));
+        assert!(key(KeyCode::Up).is_up());
+        assert!(key(KeyCode::Down).is_down());
+        assert!(!key(KeyCode::Up).is_down());
+        assert!(key(KeyCode::Enter).is_enter());
+        assert!(key(KeyCode::Esc).is_escape());
+        assert!(key(KeyCode::Tab).is_tab());
+        assert!(!key(KeyCode::BackTab).is_tab());
+    }
+
+    #[test]
+    fn test_vim_keys() {
+        let k = Key::from(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE));
+        let ctrl_k = Key::from(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
+
+        assert!(k.is_arrow(KeyCode::Up, 'k', true));
+        assert!(!ctrl_k.is_arrow(KeyCode::Up, 'k', true));
+        assert!(!k.is_arrow(KeyCode::Up, 'k', false));
+        assert!(!k.is_arrow(KeyCode::Down, 'j', true));
+    }
+
+    #[test]
+    fn test_keypad_with_numlock() {
+        assert_eq!(keypad(KeyCode::Char('8'), true).as_digit(), Some(8));
+        assert_eq!(keypad(KeyCode::Up, true).as_digit(), Some(8));
+        assert_eq!(
+            keypad(KeyCode::Delete, true).effective_code(),
+            KeyCode::Char('.')
+        );
+    }
+
+    #[test]
+    fn test_keypad_without_numlock() {
+        assert_eq!(keypad(KeyCode::Char('8'), false).as_digit(), None);
+        assert_eq!(
+            keypad(KeyCode::Char('8'), false).effective_code(),
+            KeyCode::Up
+        );
+        assert_eq!(keypad(KeyCode::Home, false).effective_code(), KeyCode::Home);
+    }
+
+    #[test]
+    fn test_non_keypad_digits() {
+        let key = Key::from(KeyEvent::new(KeyCode::Char('3'), KeyModifiers::NONE));
+        assert_eq!(key.as_digit(), Some(3));
+        let key = Key::from(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+        assert_eq!(key.effective_code(), KeyCode::Up);
+        assert_eq!(key.as_digit(), None);
+    }
+
+    #[test]
+    fn test_raw_events_are_kept() {
+        let event = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert_eq!(Key::from(event).raw(), &event);
+        let event = MouseEvent {
+            kind: MouseEventKind::Moved,
+            column: 3,
+            row: 4,
+            modifiers: KeyModifiers::SHIFT,
+        };
+        assert_eq!(Mouse::from(event).raw(), &event);
+    }
+
+    #[test]
+    fn test_matches_msg() {
+        let msg = Msg::new(Bell);
+        assert!(matches_msg!(msg, Bell));
+        assert!(matches_msg!(&msg, Quit | Bell));
+        assert!(!matches_msg!(msg, Quit | Redraw,));
+    }
+}
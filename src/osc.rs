@@ -0,0 +1,194 @@
+use crate::Color;
+
+/// The sequence to set the system clipboard to `text`.
+pub(crate) fn set_clipboard(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+/// The sequence to ask the terminal for the contents of the system clipboard.
+#[cfg(feature = "paste")]
+pub(crate) const REQUEST_CLIPBOARD: &str = "\x1b]52;c;?\x07";
+
+/// Get the clipboard text from the content of a reply to [`REQUEST_CLIPBOARD`].
+#[cfg(feature = "paste")]
+pub(crate) fn parse_clipboard(content: &str) -> Option<String> {
+    let mut parts = content.splitn(3, ';');
+    if parts.next()? != "52" {
+        return None;
+    }
+    let _selection = parts.next()?;
+    let bytes = decode_base64(parts.next()?)?;
+    String::from_utf8(bytes).ok()
+}
+
+/// The sequence to show a desktop notification in the terminal emulator `program`, as named by
+/// [`terminal_program`](crate::terminal_program).
+pub(crate) fn notify(title: &str, body: &str, program: Option<&str>) -> String {
+    // Control characters would end the sequence early.
+    let clean = |text: &str| text.replace(|c: char| c.is_control(), " ");
+    match program {
+        Some("foot" | "VTE") => {
+            // Semicolons separate the title from the body.
+            let title = clean(title).replace(';', ",");
+            format!("\x1b]777;notify;{title};{}\x07", clean(body))
+        }
+        _ => {
+            let text = match (title.is_empty(), body.is_empty()) {
+                (_, true) => clean(title),
+                (true, false) => clean(body),
+                (false, false) => format!("{}: {}", clean(title), clean(body)),
+            };
+            format!("\x1b]9;{text}\x07")
+        }
+    }
+}
+
+/// The sequence to ask the terminal for its background color.
+pub(crate) const REQUEST_BACKGROUND: &str = "\x1b]11;?\x07";
+
+/// Get the color from the content of a reply to [`REQUEST_BACKGROUND`], such as
+/// `11;rgb:ffff/ffff/ffff`.
+pub(crate) fn parse_background(content: &str) -> Option<Color> {
+    let rgb = content.strip_prefix("11;rgb:")?;
+    let mut channels = rgb.split('/').map(|channel| {
+        // Each channel has 1 to 4 hex digits, scale it to 8 bits.
+        if !(1..=4).contains(&channel.len()) {
+            return None;
+        }
+        let value = u32::from_str_radix(channel, 16).ok()?;
+        let max = (1u32 << (channel.len() * 4)) - 1;
+        Some((value * 255 / max) as u8)
+    });
+
+    let color = Color::Rgb {
+        r: channels.next()??,
+        g: channels.next()??,
+        b: channels.next()??,
+    };
+    channels.next().is_none().then_some(color)
+}
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as standard padded base64.
+fn base64(input: &[u8]) -> String {
+    let mut result = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        result.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        result.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        match chunk.len() {
+            1 => result.push_str("=="),
+            2 => {
+                result.push(ALPHABET[(n >> 6) as usize & 63] as char);
+                result.push('=');
+            }
+            _ => {
+                result.push(ALPHABET[(n >> 6) as usize & 63] as char);
+                result.push(ALPHABET[n as usize & 63] as char);
+            }
+        }
+    }
+
+    result
+}
+
+/// Decode standard base64, padding is optional.
+#[cfg(feature = "paste")]
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let mut result = Vec::with_capacity(input.len() / 4 * 3);
+    let mut n = 0u32;
+    let mut bits = 0;
+
+    for c in input.bytes().filter(|&c| c != b'=') {
+        let value = ALPHABET.iter().position(|&a| a == c)? as u32;
+        n = n << 6 | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((n >> bits) as u8);
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_background() {
+        assert_eq!(
+            parse_background("11;rgb:ffff/8080/0000"),
+            Some(Color::Rgb {
+                r: 255,
+                g: 128,
+                b: 0
+            })
+        );
+        assert_eq!(
+            parse_background("11;rgb:f/80/000"),
+            Some(Color::Rgb {
+                r: 255,
+                g: 128,
+                b: 0
+            })
+        );
+        assert_eq!(parse_background("11;rgb:ffff/ffff"), None);
+        assert_eq!(parse_background("52;c;aGk="), None);
+        assert_eq!(parse_background("11;rgb:ffffffff/0/0"), None);
+        assert_eq!(parse_background("11;rgb:/0/0"), None);
+    }
+
+    #[test]
+    fn test_notify() {
+        assert_eq!(
+            notify("Build", "done\x07", Some("kitty")),
+            "\x1b]9;Build: done \x07"
+        );
+        assert_eq!(notify("", "done", None), "\x1b]9;done\x07");
+        assert_eq!(
+            notify("a;b", "c;d", Some("foot")),
+            "\x1b]777;notify;a,b;c;d\x07"
+        );
+    }
+
+    #[test]
+    fn test_base64_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[cfg(feature = "paste")]
+    #[test]
+    fn test_decode_base64() {
+        for input in ["", "f", "fo", "foo", "foobar", "héllo 👍"] {
+            let encoded = base64(input.as_bytes());
+            assert_eq!(decode_base64(&encoded).unwrap(), input.as_bytes());
+        }
+        assert_eq!(decode_base64("not base64!"), None);
+    }
+
+    #[cfg(feature = "paste")]
+    #[test]
+    fn test_parse_clipboard() {
+        assert_eq!(parse_clipboard("52;c;aGk="), Some(String::from("hi")));
+        assert_eq!(parse_clipboard("52;c;"), Some(String::new()));
+        assert_eq!(parse_clipboard("11;rgb:0000/0000/0000"), None);
+    }
+
+    #[test]
+    fn test_set_clipboard() {
+        assert_eq!(set_clipboard("hi"), "\x1b]52;c;aGk=\x07");
+    }
+}
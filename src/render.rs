@@ -0,0 +1,858 @@
+use crate::{
+    text::{self, sgr_params, tokens, Token},
+    Rect,
+};
+use crossterm::{
+    cursor::{MoveTo, MoveToColumn, MoveToPreviousLine},
+    queue,
+    style::Print,
+    terminal::{
+        BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate, EnterAlternateScreen,
+    },
+};
+use std::io::{self, Write};
+
+/// How the previous frame is cleared before drawing the next one.
+///
+/// Set with [`App::with_clear_strategy`](crate::App::with_clear_strategy).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ClearStrategy {
+    /// Clear the whole screen and then draw the frame.
+    ///
+    /// This is always correct but the screen is blank for a moment each frame which can be seen as
+    /// flicker on some terminals.
+    All,
+    /// Clear and redraw each line in place, then clear everything below the last line.
+    ///
+    /// This avoids the blank screen of [`ClearStrategy::All`] while still handling frames that
+    /// shrink.
+    #[default]
+    PerLine,
+    /// Only redraw the characters that changed since the last frame.
+    ///
+    /// This writes the least to the terminal, which matters for apps where only a few characters
+    /// change each frame such as a clock, but takes more work to find what changed. Frames
+    /// taller than the terminal are not supported.
+    Diff,
+}
+
+/// What to do with control characters in a frame, other than newlines, tabs and the escapes
+/// used for escape codes.
+///
+/// Control characters such as NUL, backspace or carriage return move the cursor or are drawn
+/// differently by each terminal, which would corrupt the display. They mostly come from showing
+/// untrusted text such as log lines.
+///
+/// Set with [`App::with_control_chars`](crate::App::with_control_chars).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ControlChars {
+    /// Remove control characters.
+    Strip,
+    /// Show control characters as a visible placeholder, such as `␀` for NUL.
+    #[default]
+    Replace,
+}
+
+/// Draws frames to the terminal using a [`ClearStrategy`].
+///
+/// Every line is drawn by moving the cursor to its start rather than writing newlines, so the
+/// output is the same whichever newline conversion the terminal or console does.
+///
+/// Inline renderers instead draw below wherever the cursor was when they started, always
+/// redrawing the whole frame and writing `\r\n` between lines so the terminal scrolls as the
+/// frame grows.
+pub(crate) struct Renderer {
+    strategy: ClearStrategy,
+    last_frame: Option<Vec<Vec<FrameCell>>>,
+    /// The number of lines in the last frame for inline renderers, or `None` if the renderer
+    /// draws to the whole screen.
+    inline_height: Option<usize>,
+    /// Whether the screen shows a whole frame, so only parts of the next one can be drawn.
+    drawn: bool,
+    /// Lines to print above the next frame of an inline renderer.
+    above: Vec<String>,
+    /// The size of the terminal as `(columns, rows)`, if it is known.
+    size: Option<(u16, u16)>,
+    /// The row of the terminal the last frame of an inline renderer starts on, or the cursor's
+    /// row before the first frame.
+    top: u16,
+}
+
+impl Renderer {
+    pub(crate) fn new(strategy: ClearStrategy) -> Self {
+        Self {
+            strategy,
+            last_frame: None,
+            inline_height: None,
+            drawn: false,
+            above: Vec::new(),
+            size: None,
+            top: 0,
+        }
+    }
+
+    /// Create a renderer which draws below the cursor rather than to the whole screen.
+    pub(crate) fn inline() -> Self {
+        Self {
+            strategy: ClearStrategy::PerLine,
+            last_frame: None,
+            inline_height: Some(0),
+            drawn: false,
+            above: Vec::new(),
+            size: None,
+            top: 0,
+        }
+    }
+
+    /// Forget the last frame so the next one is fully redrawn.
+    pub(crate) fn invalidate(&mut self) {
+        self.last_frame = None;
+        self.drawn = false;
+    }
+
+    /// Draw the next frame of an inline renderer from the cursor's line, rather than over the
+    /// last frame, such as once another program has written below it. `row` is the row of the
+    /// terminal the cursor is on.
+    pub(crate) fn detach(&mut self, row: u16) {
+        if let Some(height) = &mut self.inline_height {
+            *height = 0;
+            self.top = row;
+        }
+    }
+
+    /// The row of the terminal the last frame starts on.
+    pub(crate) fn top(&self) -> u16 {
+        self.top
+    }
+
+    /// Set the size of the terminal as `(columns, rows)`, which inline frames are cut down to.
+    pub(crate) fn set_size(&mut self, size: (u16, u16)) {
+        self.size = Some(size);
+    }
+
+    /// Print `text` above the next frame of an inline renderer, where it scrolls in to the
+    /// terminal's history rather than being drawn over. Renderers for the whole screen ignore
+    /// it since the alternate screen has no history.
+    pub(crate) fn print_above(&mut self, text: &str) {
+        if self.inline_height.is_some() {
+            self.above.extend(text.split('\n').map(str::to_string));
+        }
+    }
+
+    /// Draw a frame to `out` with a single write and flush it, entering the alternate screen
+    /// first if `enter_alternate_screen` is set.
+    ///
+    /// If `dirty` is given only those regions of the frame are drawn, see [`Renderer::draw`].
+    ///
+    /// The frame is wrapped in a synchronized update so terminals which support it show the
+    /// whole frame at once, and entering the alternate screen is part of the same write so the
+    /// empty screen is never shown before the first frame.
+    pub(crate) fn write_frame(
+        &mut self,
+        out: &mut impl Write,
+        view: &str,
+        dirty: Option<&[Rect]>,
+        enter_alternate_screen: bool,
+    ) -> io::Result<()> {
+        let mut frame = Vec::new();
+        queue!(frame, BeginSynchronizedUpdate)?;
+        if enter_alternate_screen {
+            queue!(frame, EnterAlternateScreen)?;
+        }
+        self.draw(&mut frame, view, dirty)?;
+        queue!(frame, EndSynchronizedUpdate)?;
+        out.write_all(&frame)?;
+        out.flush()
+    }
+
+    /// Draw a frame to `out`, this does not flush.
+    ///
+    /// If `dirty` is given only those regions of the frame are drawn, clipped to the frame's
+    /// height. The whole frame is drawn anyway if the screen doesn't show a whole frame yet, for
+    /// inline renderers and for [`ClearStrategy::Diff`] which finds what changed itself.
+    pub(crate) fn draw(
+        &mut self,
+        out: &mut impl Write,
+        view: &str,
+        dirty: Option<&[Rect]>,
+    ) -> io::Result<()> {
+        if let Some(height) = &mut self.inline_height {
+            // The cursor is left on the last line of the last frame. Only lines which are still
+            // on screen can be moved back to, so frames are cut down to fit the terminal with
+            // each line taking exactly one row.
+            match *height {
+                0 | 1 => queue!(out, MoveToColumn(0))?,
+                height => queue!(out, MoveToPreviousLine(height as u16 - 1))?,
+            }
+            // Clear the whole of the last frame first, so nothing is left behind when the new
+            // one is shorter.
+            queue!(out, Clear(ClearType::FromCursorDown))?;
+            // Printing over the last frame leaves the new one to be drawn below.
+            let above = self.above.len();
+            for line in self.above.drain(..) {
+                queue!(out, Print(line), Print("\r\n"))?;
+            }
+
+            let (cols, rows) = self.size.map_or((usize::MAX, usize::MAX), |(cols, rows)| {
+                (cols as usize, rows.max(1) as usize)
+            });
+            let lines: Vec<&str> = view.split('\n').take(rows).collect();
+            for (y, line) in lines.iter().enumerate() {
+                if y > 0 {
+                    queue!(out, Print("\r\n"))?;
+                }
+                match text::width(line) > cols {
+                    true => queue!(out, Print(text::slice_columns(line, 0, cols)))?,
+                    false => queue!(out, Print(line))?,
+                }
+            }
+            *height = lines.len();
+            // Anything past the bottom of the terminal scrolls the frame up.
+            let top = self.top as usize + above;
+            self.top = match self.size {
+                Some((_, rows)) => top.min((rows as usize).saturating_sub(lines.len())),
+                None => top,
+            } as u16;
+            return Ok(());
+        }
+
+        if let (Some(dirty), true, false) =
+            (dirty, self.drawn, self.strategy == ClearStrategy::Diff)
+        {
+            return draw_regions(out, view, dirty);
+        }
+        self.drawn = true;
+
+        match self.strategy {
+            ClearStrategy::All => {
+                queue!(out, Clear(ClearType::All))?;
+                for (y, line) in view.split('\n').enumerate() {
+                    queue!(out, MoveTo(0, y as u16), Print(line))?;
+                }
+            }
+            ClearStrategy::PerLine => {
+                // Styling left open at the end of a line would color the cleared cells on
+                // terminals which erase with the current background, so it is reset before each
+                // clear and opened again after.
+                let mut active = String::new();
+                for (y, line) in view.split('\n').enumerate() {
+                    queue!(out, MoveTo(0, y as u16))?;
+                    match active.is_empty() {
+                        true => queue!(out, Clear(ClearType::UntilNewLine))?,
+                        false => queue!(
+                            out,
+                            Print(text::RESET),
+                            Clear(ClearType::UntilNewLine),
+                            Print(&active)
+                        )?,
+                    }
+                    queue!(out, Print(line))?;
+                    for token in tokens(line) {
+                        if let Token::Escape(code) = token {
+                            text::track_style(&mut active, code);
+                        }
+                    }
+                }
+                if !active.is_empty() {
+                    queue!(out, Print(text::RESET))?;
+                }
+                queue!(out, Clear(ClearType::FromCursorDown))?;
+            }
+            ClearStrategy::Diff => {
+                let rows = parse_frame(view);
+                let last = self.last_frame.take();
+
+                if last.is_none() {
+                    queue!(out, Clear(ClearType::All))?;
+                }
+
+                for (y, row) in rows.iter().enumerate() {
+                    let last_row = last.as_ref().and_then(|last| last.get(y));
+                    draw_row_changes(out, y as u16, row, last_row.map_or(&[], Vec::as_slice))?;
+                }
+
+                if last.as_ref().is_some_and(|last| last.len() > rows.len()) {
+                    queue!(
+                        out,
+                        MoveTo(0, rows.len() as u16),
+                        Clear(ClearType::FromCursorDown)
+                    )?;
+                }
+
+                self.last_frame = Some(rows);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Draw only the `regions` of a frame, padding rows which are shorter than a region with spaces.
+fn draw_regions(out: &mut impl Write, view: &str, regions: &[Rect]) -> io::Result<()> {
+    let lines: Vec<&str> = view.split('\n').collect();
+    for region in regions {
+        let rows = lines
+            .iter()
+            .enumerate()
+            .skip(region.y.into())
+            .take(region.height.into());
+        for (y, line) in rows {
+            let part = text::slice_columns(line, region.x.into(), region.width.into());
+            let padding = (region.width as usize).saturating_sub(text::width(&part));
+            queue!(
+                out,
+                MoveTo(region.x, y as u16),
+                Print(part),
+                Print(text::RESET),
+                Print(" ".repeat(padding))
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// A single cell of a frame used to find what changed between frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FrameCell {
+    /// The character in the cell along with any zero width characters and escape codes other than
+    /// styling before it, or empty if the cell is covered by the wide character to its left.
+    symbol: String,
+    /// The styling escape codes active for the cell.
+    style: String,
+}
+
+/// Split a frame in to rows of cells, tracking the styling of each cell.
+fn parse_frame(view: &str) -> Vec<Vec<FrameCell>> {
+    let mut style = String::new();
+    let mut rows = Vec::new();
+
+    for line in view.split('\n') {
+        let mut row: Vec<FrameCell> = Vec::new();
+        let mut pending = String::new();
+
+        for token in tokens(line) {
+            match token {
+                Token::Escape(code) if text::is_reset(code) => style.clear(),
+                Token::Escape(code) if sgr_params(code).is_some() => {
+                    if sgr_params(code).unwrap().first() == Some(&0) {
+                        style.clear();
+                    }
+                    style.push_str(code);
+                }
+                Token::Escape(code) => pending.push_str(code),
+                Token::Char(c) => match text::char_width(c) {
+                    0 => match row.iter_mut().rev().find(|cell| !cell.symbol.is_empty()) {
+                        Some(cell) => cell.symbol.push(c),
+                        None => pending.push(c),
+                    },
+                    width => {
+                        pending.push(c);
+                        let cell = FrameCell {
+                            symbol: std::mem::take(&mut pending),
+                            style: style.clone(),
+                        };
+                        if width == 2 {
+                            let covered = FrameCell {
+                                symbol: String::new(),
+                                style: style.clone(),
+                            };
+                            row.extend([cell, covered]);
+                        } else {
+                            row.push(cell);
+                        }
+                    }
+                },
+            }
+        }
+
+        // Keep escape codes after the last character, such as the end of a hyperlink.
+        if let Some(cell) = row.iter_mut().rev().find(|cell| !cell.symbol.is_empty()) {
+            cell.symbol.push_str(&pending);
+        }
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// Draw the cells of a row which are different from the last frame.
+fn draw_row_changes(
+    out: &mut impl Write,
+    y: u16,
+    row: &[FrameCell],
+    last_row: &[FrameCell],
+) -> io::Result<()> {
+    let mut x = 0;
+    while x < row.len() {
+        if last_row.get(x) == Some(&row[x]) {
+            x += 1;
+            continue;
+        }
+
+        // Start at the wide character covering this cell, if any.
+        let mut start = x;
+        while start > 0 && row[start].symbol.is_empty() {
+            start -= 1;
+        }
+        let mut end = x;
+        while end < row.len() && last_row.get(end) != Some(&row[end]) {
+            end += 1;
+        }
+
+        queue!(out, MoveTo(start as u16, y))?;
+        let mut active = "";
+        for cell in &row[start..end] {
+            if cell.symbol.is_empty() {
+                continue;
+            }
+            if cell.style != active {
+                if !active.is_empty() {
+                    queue!(out, Print(text::RESET))?;
+                }
+                queue!(out, Print(&cell.style))?;
+                active = &cell.style;
+            }
+            queue!(out, Print(&cell.symbol))?;
+        }
+        if !active.is_empty() {
+            queue!(out, Print(text::RESET))?;
+        }
+
+        x = end;
+    }
+
+    if last_row.len() > row.len() {
+        queue!(
+            out,
+            MoveTo(row.len() as u16, y),
+            Clear(ClearType::UntilNewLine)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The view to show instead of the model's if the terminal is smaller than `min_size`.
+pub(crate) fn too_small(size: (u16, u16), min_size: (u16, u16)) -> Option<String> {
+    let (cols, rows) = size;
+    let (min_cols, min_rows) = min_size;
+    if cols >= min_cols && rows >= min_rows {
+        return None;
+    }
+    Some(format!(
+        "Terminal too small\nNeeds {min_cols}x{min_rows}, is {cols}x{rows}"
+    ))
+}
+
+/// Strip or replace the control characters in a frame which would corrupt the display.
+pub(crate) fn sanitize(frame: String, policy: ControlChars) -> String {
+    let is_unsafe = |c: char| c.is_control() && c != '\n' && c != '\t' && c != '\x1b';
+    if !frame.contains(is_unsafe) {
+        return frame;
+    }
+
+    let mut result = String::with_capacity(frame.len());
+    for token in tokens(&frame) {
+        match token {
+            Token::Char(c) if is_unsafe(c) => {
+                if policy == ControlChars::Replace {
+                    result.push(control_picture(c));
+                }
+            }
+            Token::Char(c) => result.push(c),
+            Token::Escape(code) => result.push_str(code),
+        }
+    }
+    result
+}
+
+/// The visible symbol to show in place of a control character.
+fn control_picture(c: char) -> char {
+    match c as u32 {
+        // The Control Pictures block has a symbol for each C0 control character.
+        n @ 0..=0x1f => char::from_u32(0x2400 + n).unwrap_or('\u{fffd}'),
+        0x7f => '␡',
+        _ => '\u{fffd}',
+    }
+}
+
+/// Remove whitespace from the end of each line of a frame.
+///
+/// Spaces which are visible because of their styling, such as having a background color, are
+/// kept. Escape codes are always kept.
+pub(crate) fn trim_trailing_whitespace(frame: &str) -> String {
+    let mut result = String::with_capacity(frame.len());
+    let mut visible = VisibleSpaces::default();
+
+    for (n, line) in frame.split('\n').enumerate() {
+        if n > 0 {
+            result.push('\n');
+        }
+
+        let mut pending = String::new();
+        for token in tokens(line) {
+            match token {
+                Token::Char(c) if c.is_whitespace() && !visible.any() => pending.push(c),
+                Token::Char(c) => {
+                    result.push_str(&pending);
+                    pending.clear();
+                    result.push(c);
+                }
+                Token::Escape(code) => {
+                    visible.apply(code);
+                    if pending.is_empty() {
+                        result.push_str(code);
+                    } else {
+                        pending.push_str(code);
+                    }
+                }
+            }
+        }
+
+        // Keep the escape codes from amongst the trimmed whitespace.
+        result.extend(tokens(&pending).filter_map(|token| match token {
+            Token::Escape(code) => Some(code),
+            Token::Char(_) => None,
+        }));
+    }
+
+    result
+}
+
+/// Remove the empty lines from the end of a frame, always leaving at least one line.
+///
+/// A line is empty if it has no characters, escape codes from the removed lines are kept at the
+/// end of the last line.
+pub(crate) fn trim_trailing_lines(frame: &str) -> String {
+    let is_empty = |line: &str| tokens(line).all(|token| matches!(token, Token::Escape(_)));
+
+    let mut kept = frame;
+    let mut codes = Vec::new();
+    while let Some((rest, last)) = kept.rsplit_once('\n') {
+        if !is_empty(last) {
+            break;
+        }
+        codes.push(last);
+        kept = rest;
+    }
+
+    let mut result = kept.to_string();
+    result.extend(codes.into_iter().rev());
+    result
+}
+
+/// Tracks the styling which makes spaces visible.
+#[derive(Default)]
+struct VisibleSpaces {
+    background: bool,
+    reverse: bool,
+    underline: bool,
+    crossed_out: bool,
+    overline: bool,
+}
+
+impl VisibleSpaces {
+    fn any(&self) -> bool {
+        self.background || self.reverse || self.underline || self.crossed_out || self.overline
+    }
+
+    fn apply(&mut self, code: &str) {
+        if text::is_reset(code) {
+            *self = Self::default();
+            return;
+        }
+        let Some(params) = sgr_params(code) else {
+            return;
+        };
+
+        let mut params = params.into_iter();
+        while let Some(param) = params.next() {
+            match param {
+                0 => *self = Self::default(),
+                4 => self.underline = true,
+                7 => self.reverse = true,
+                9 => self.crossed_out = true,
+                24 => self.underline = false,
+                27 => self.reverse = false,
+                29 => self.crossed_out = false,
+                53 => self.overline = true,
+                55 => self.overline = false,
+                40..=47 | 100..=107 => self.background = true,
+                49 => self.background = false,
+                // Extended colors, skip their arguments.
+                38 | 48 | 58 => {
+                    let args = match params.next() {
+                        Some(5) => 1,
+                        Some(2) => 3,
+                        _ => 0,
+                    };
+                    for _ in 0..args {
+                        params.next();
+                    }
+                    if param == 48 {
+                        self.background = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(renderer: &mut Renderer, view: &str) -> String {
+        let mut out = Vec::new();
+        renderer.draw(&mut out, view, None).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_per_line_clears_below_frame() {
+        let mut renderer = Renderer::new(ClearStrategy::PerLine);
+        let out = draw(&mut renderer, "a\nb");
+        assert_eq!(out, "\x1b[1;1H\x1b[Ka\x1b[2;1H\x1b[Kb\x1b[J");
+    }
+
+    #[test]
+    fn test_per_line_resets_style_before_clearing() {
+        let mut renderer = Renderer::new(ClearStrategy::PerLine);
+        let out = draw(&mut renderer, "\x1b[104mab  \nc   \x1b[0m\nd");
+        assert_eq!(
+            out,
+            "\x1b[1;1H\x1b[K\x1b[104mab  \
+             \x1b[2;1H\x1b[0m\x1b[K\x1b[104mc   \x1b[0m\
+             \x1b[3;1H\x1b[Kd\x1b[J"
+        );
+        let out = draw(&mut renderer, "\x1b[1ma\nb");
+        assert!(out.ends_with("\x1b[0m\x1b[K\x1b[1mb\x1b[0m\x1b[J"));
+    }
+
+    #[test]
+    fn test_only_dirty_regions_are_drawn() {
+        let mut renderer = Renderer::new(ClearStrategy::PerLine);
+        let dirty = [Rect::new(2, 0, 3, 1), Rect::new(0, 1, 2, 5)];
+        let mut out = Vec::new();
+        renderer.draw(&mut out, "ab", Some(&dirty)).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .starts_with("\x1b[1;1H\x1b[Kab"));
+
+        let mut out = Vec::new();
+        let view = "ab\x1b[1mcdef\x1b[0m\nx";
+        renderer.draw(&mut out, view, Some(&dirty)).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[1;3H\x1b[1mcde\x1b[0m\x1b[0m\x1b[2;1Hx\x1b[0m "
+        );
+    }
+
+    /// Records each write so tests can check how the output was split up.
+    #[derive(Default)]
+    struct Writes(Vec<Vec<u8>>);
+
+    impl Write for Writes {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.push(buf.to_vec());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_first_frame_is_a_single_write() {
+        let mut renderer = Renderer::new(ClearStrategy::All);
+        let mut out = Writes::default();
+        let view = "a long line\n".repeat(500);
+        renderer.write_frame(&mut out, &view, None, true).unwrap();
+        assert_eq!(out.0.len(), 1);
+        let frame = String::from_utf8(out.0.remove(0)).unwrap();
+        assert!(frame.starts_with("\x1b[?2026h\x1b[?1049h\x1b[2J"));
+        assert!(frame.ends_with("\x1b[?2026l"));
+    }
+
+    #[test]
+    fn test_inline_redraws_over_last_frame() {
+        let mut renderer = Renderer::inline();
+        assert_eq!(draw(&mut renderer, "a\nb"), "\x1b[1G\x1b[Ja\r\nb");
+        assert_eq!(draw(&mut renderer, "c"), "\x1b[1F\x1b[Jc");
+        renderer.detach(0);
+        assert!(draw(&mut renderer, "d").starts_with("\x1b[1G"));
+    }
+
+    #[test]
+    fn test_inline_frame_growing_past_bottom() {
+        let mut renderer = Renderer::inline();
+        renderer.set_size((3, 4));
+        draw(&mut renderer, "a\nb");
+        // Only the lines which fit are drawn, so the next frame can move back to the first.
+        assert_eq!(
+            draw(&mut renderer, "1\n2\n3\n4\n5\n6"),
+            "\x1b[1F\x1b[J1\r\n2\r\n3\r\n4"
+        );
+        assert_eq!(draw(&mut renderer, "x"), "\x1b[3F\x1b[Jx");
+    }
+
+    #[test]
+    fn test_inline_frame_top_row() {
+        let mut renderer = Renderer::inline();
+        renderer.set_size((3, 8));
+        renderer.detach(4);
+        draw(&mut renderer, "a\nb");
+        assert_eq!(renderer.top(), 4);
+        renderer.print_above("one");
+        draw(&mut renderer, "a\nb");
+        assert_eq!(renderer.top(), 5);
+        // Growing past the bottom scrolls the frame up.
+        draw(&mut renderer, "1\n2\n3\n4\n5");
+        assert_eq!(renderer.top(), 3);
+    }
+
+    #[test]
+    fn test_inline_lines_are_cut_to_width() {
+        let mut renderer = Renderer::inline();
+        renderer.set_size((3, 4));
+        assert_eq!(
+            draw(&mut renderer, "\x1b[1mabcdef\x1b[0m\nab"),
+            "\x1b[1G\x1b[J\x1b[1mabc\x1b[0m\r\nab"
+        );
+    }
+
+    #[test]
+    fn test_print_above_inline_frame() {
+        let mut renderer = Renderer::inline();
+        draw(&mut renderer, "a\nb");
+        renderer.print_above("one\ntwo");
+        assert_eq!(
+            draw(&mut renderer, "a\nb"),
+            "\x1b[1F\x1b[Jone\r\ntwo\r\na\r\nb"
+        );
+        assert_eq!(draw(&mut renderer, "c"), "\x1b[1F\x1b[Jc");
+
+        let mut renderer = Renderer::new(ClearStrategy::PerLine);
+        renderer.print_above("one");
+        assert!(!draw(&mut renderer, "a").contains("one"));
+    }
+
+    #[test]
+    fn test_lines_are_drawn_without_newlines() {
+        for strategy in [
+            ClearStrategy::All,
+            ClearStrategy::PerLine,
+            ClearStrategy::Diff,
+        ] {
+            let mut renderer = Renderer::new(strategy);
+            let out = draw(&mut renderer, "a\n\nb");
+            assert!(!out.contains(['\r', '\n']), "{strategy:?}: {out:?}");
+            assert!(out.contains("\x1b[3;1H"), "{strategy:?}: {out:?}");
+        }
+    }
+
+    #[test]
+    fn test_too_small() {
+        assert_eq!(
+            too_small((40, 10), (80, 24)).as_deref(),
+            Some("Terminal too small\nNeeds 80x24, is 40x10")
+        );
+        assert!(too_small((80, 10), (80, 24)).is_some());
+        assert_eq!(too_small((80, 24), (80, 24)), None);
+        assert_eq!(too_small((120, 40), (80, 24)), None);
+    }
+
+    #[test]
+    fn test_trim_trailing_whitespace() {
+        let frame = "a   \n\x1b[1mb  \x1b[0m  \n   \nc \x1b[41md";
+        assert_eq!(
+            trim_trailing_whitespace(frame),
+            "a\n\x1b[1mb\x1b[0m\n\nc \x1b[41md"
+        );
+    }
+
+    #[test]
+    fn test_trim_keeps_background_spaces() {
+        let frame = "\x1b[41mab  \x1b[0m  \nc\x1b[48;5;1m  \x1b[49m ";
+        assert_eq!(
+            trim_trailing_whitespace(frame),
+            "\x1b[41mab  \x1b[0m\nc\x1b[48;5;1m  \x1b[49m"
+        );
+    }
+
+    #[test]
+    fn test_trim_trailing_lines() {
+        let frame = "a\n\nb\n\n\x1b[0m\n\n";
+        let trimmed = trim_trailing_lines(frame);
+        assert_eq!(trimmed, "a\n\nb\x1b[0m");
+        assert_eq!(trimmed.lines().count(), 3);
+        assert_eq!(trim_trailing_lines("\n\n"), "");
+    }
+
+    #[test]
+    fn test_diff_only_draws_changed_lines() {
+        let mut renderer = Renderer::new(ClearStrategy::Diff);
+        draw(&mut renderer, "a\nb\nc");
+        let out = draw(&mut renderer, "a\nB");
+        assert_eq!(out, "\x1b[2;1HB\x1b[3;1H\x1b[J");
+    }
+
+    #[test]
+    fn test_diff_only_draws_changed_characters() {
+        let mut renderer = Renderer::new(ClearStrategy::Diff);
+        draw(&mut renderer, "Time: \x1b[1m12:00:05\x1b[0m");
+        let out = draw(&mut renderer, "Time: \x1b[1m12:00:06\x1b[0m");
+        // A ticking clock only needs to move to and redraw the last digit.
+        assert_eq!(out, "\x1b[1;14H\x1b[1m6\x1b[0m");
+
+        let mut per_line = Renderer::new(ClearStrategy::PerLine);
+        let full = draw(&mut per_line, "Time: \x1b[1m12:00:06\x1b[0m");
+        assert_eq!((out.len(), full.len()), (16, 34));
+    }
+
+    #[test]
+    fn test_diff_keeps_style_across_lines() {
+        let mut renderer = Renderer::new(ClearStrategy::Diff);
+        draw(&mut renderer, "\x1b[31ma\nb\x1b[0m");
+        let out = draw(&mut renderer, "\x1b[31ma\nc\x1b[0m");
+        assert_eq!(out, "\x1b[2;1H\x1b[31mc\x1b[0m");
+    }
+
+    #[test]
+    fn test_diff_shorter_line() {
+        let mut renderer = Renderer::new(ClearStrategy::Diff);
+        draw(&mut renderer, "abcd");
+        let out = draw(&mut renderer, "ab");
+        assert_eq!(out, "\x1b[1;3H\x1b[K");
+    }
+
+    #[test]
+    fn test_diff_wide_characters() {
+        let mut renderer = Renderer::new(ClearStrategy::Diff);
+        draw(&mut renderer, "a你b");
+        let out = draw(&mut renderer, "a好b");
+        assert_eq!(out, "\x1b[1;2H好");
+        let out = draw(&mut renderer, "axyb");
+        assert_eq!(out, "\x1b[1;2Hxy");
+    }
+
+    #[test]
+    fn test_sanitize_replaces_control_characters() {
+        let frame = String::from("a\0b\x08c\n\x1b[1md\te\x1b[0m");
+        assert_eq!(
+            sanitize(frame, ControlChars::Replace),
+            "a␀b␈c\n\x1b[1md\te\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_strips_control_characters() {
+        let frame = String::from("a\0b\x08c\u{200b}\x7f\r");
+        let frame = sanitize(frame, ControlChars::Strip);
+        assert_eq!(frame, "abc\u{200b}");
+        // Zero width characters are kept but take up no columns.
+        assert_eq!(text::width(&frame), 3);
+    }
+}
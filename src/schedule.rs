@@ -0,0 +1,224 @@
+use crate::Msg;
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    sync::{mpsc::Sender, Arc, Condvar, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+/// Sends messages to an [`App`](crate::App) at a later time, get one with
+/// [`App::scheduler`](crate::App::scheduler).
+///
+/// Messages are delivered in time order by a single background thread, so messages scheduled for
+/// the same or nearby instants stay in step, such as the frames of an animation. The thread is
+/// only running while there are messages waiting to be sent.
+///
+/// Schedulers can be cloned and moved to other threads, every clone shares the same messages.
+///
+/// ```no_run
+/// # use sketch::*;
+/// # use std::time::{Duration, Instant};
+/// # let app = App::new(Counter);
+/// let scheduler = app.scheduler();
+/// let start = Instant::now();
+/// for frame in 1..=10 {
+///     scheduler.send_at(start + Duration::from_millis(frame * 16), Msg::new(Tick));
+/// }
+/// // Only the latest timeout with a key is kept.
+/// scheduler.send_after_keyed("timeout", Duration::from_secs(5), Msg::new(Quit));
+/// # struct Counter;
+/// # impl Model for Counter {
+/// #     fn update(self, _: &Msg) -> (Self, Option<Msg>) { (self, None) }
+/// #     fn view(&self) -> String { String::new() }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Scheduler {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    tx: Sender<Msg>,
+    state: Mutex<State>,
+    /// Notified when a message is scheduled so the thread can recheck when to wake up.
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct State {
+    queue: BinaryHeap<Scheduled>,
+    /// Used to keep messages for the same instant in the order they were scheduled.
+    next_seq: u64,
+    running: bool,
+}
+
+struct Scheduled {
+    at: Instant,
+    seq: u64,
+    key: Option<String>,
+    msg: Msg,
+}
+
+// Ordered so the earliest message is at the top of the max heap.
+impl Ord for Scheduled {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.at, other.seq).cmp(&(self.at, self.seq))
+    }
+}
+
+impl PartialOrd for Scheduled {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Scheduled {
+    fn eq(&self, other: &Self) -> bool {
+        (self.at, self.seq) == (other.at, other.seq)
+    }
+}
+
+impl Eq for Scheduled {}
+
+impl Scheduler {
+    pub(crate) fn new(tx: Sender<Msg>) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                tx,
+                state: Mutex::new(State::default()),
+                changed: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Send `msg` at `at`, or as soon as possible if it has already passed.
+    pub fn send_at(&self, at: Instant, msg: Msg) {
+        self.schedule(at, None, msg);
+    }
+
+    /// Send `msg` once `delay` has passed.
+    pub fn send_after(&self, delay: Duration, msg: Msg) {
+        self.send_at(Instant::now() + delay, msg);
+    }
+
+    /// Send `msg` at `at`, replacing any message waiting to be sent with the same key.
+    pub fn send_at_keyed(&self, key: impl Into<String>, at: Instant, msg: Msg) {
+        self.schedule(at, Some(key.into()), msg);
+    }
+
+    /// Send `msg` once `delay` has passed, replacing any message waiting to be sent with the same
+    /// key.
+    pub fn send_after_keyed(&self, key: impl Into<String>, delay: Duration, msg: Msg) {
+        self.send_at_keyed(key, Instant::now() + delay, msg);
+    }
+
+    /// Stop the message with this key from being sent, returning whether there was one.
+    pub fn cancel(&self, key: &str) -> bool {
+        let mut state = self.lock();
+        let len = state.queue.len();
+        state.queue.retain(|s| s.key.as_deref() != Some(key));
+        state.queue.len() != len
+    }
+
+    /// The number of messages waiting to be sent.
+    pub fn pending(&self) -> usize {
+        self.lock().queue.len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.shared.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Queue `msg` to be sent at `at`, replacing any message waiting with the same key.
+    fn schedule(&self, at: Instant, key: Option<String>, msg: Msg) {
+        let mut state = self.lock();
+        if let Some(key) = &key {
+            state.queue.retain(|s| s.key.as_ref() != Some(key));
+        }
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.queue.push(Scheduled { at, seq, key, msg });
+
+        if state.running {
+            self.shared.changed.notify_one();
+        } else {
+            state.running = true;
+            let shared = self.shared.clone();
+            std::thread::spawn(move || run(&shared));
+        }
+    }
+}
+
+/// Send each message once it is due, stopping once there are none left.
+fn run(shared: &Shared) {
+    let mut state = shared.state.lock().unwrap_or_else(|e| e.into_inner());
+    loop {
+        let Some(next) = state.queue.peek() else {
+            state.running = false;
+            return;
+        };
+
+        let now = Instant::now();
+        if next.at > now {
+            let timeout = next.at - now;
+            state = shared
+                .changed
+                .wait_timeout(state, timeout)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+            continue;
+        }
+
+        let msg = state.queue.pop().unwrap().msg;
+        // The app has stopped if the receiver is gone.
+        if shared.tx.send(msg).is_err() {
+            state.queue.clear();
+            state.running = false;
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Message;
+    use std::sync::mpsc::channel;
+
+    struct N(u32);
+    impl Message for N {}
+
+    fn recv(rx: &std::sync::mpsc::Receiver<Msg>) -> u32 {
+        let msg = rx.recv_timeout(Duration::from_secs(1)).unwrap();
+        msg.cast::<N>().unwrap().0
+    }
+
+    #[test]
+    fn test_sends_in_time_order() {
+        let (tx, rx) = channel();
+        let scheduler = Scheduler::new(tx);
+        let start = Instant::now();
+        scheduler.send_at(start + Duration::from_millis(30), Msg::new(N(3)));
+        scheduler.send_at(start + Duration::from_millis(10), Msg::new(N(1)));
+        scheduler.send_at(start + Duration::from_millis(20), Msg::new(N(2)));
+        scheduler.send_at(start + Duration::from_millis(20), Msg::new(N(4)));
+
+        assert_eq!([recv(&rx), recv(&rx), recv(&rx), recv(&rx)], [1, 2, 4, 3]);
+        assert!(Instant::now() >= start + Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_keyed_replaces_and_cancels() {
+        let (tx, rx) = channel();
+        let scheduler = Scheduler::new(tx);
+        scheduler.send_after_keyed("a", Duration::from_millis(10), Msg::new(N(1)));
+        scheduler.send_after_keyed("a", Duration::from_millis(10), Msg::new(N(2)));
+        scheduler.send_after_keyed("b", Duration::from_millis(5), Msg::new(N(3)));
+        assert!(scheduler.cancel("b"));
+        assert!(!scheduler.cancel("c"));
+
+        assert_eq!(recv(&rx), 2);
+        assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+        assert_eq!(scheduler.pending(), 0);
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{Message, Model, Msg, Rect};
+use std::sync::Mutex;
+
+/// A stack of screens where only the top one is shown and given messages, for apps that move
+/// between several views such as a menu, then settings, then a confirmation.
+///
+/// Screens are pushed by returning a [`PushScreen`] message and removed by returning a
+/// [`PopScreen`] message, which can carry a result for the screen underneath. The first screen
+/// can't be popped so there is always one to show.
+///
+/// All the screens are the same type, usually an enum with a variant for each kind of screen.
+/// [`Model::take_dirty_regions`] is passed on to the top screen, except for the frame after a
+/// screen is pushed or popped which is always drawn in full.
+///
+/// ```
+/// # use sketch::*;
+/// # struct Screen(&'static str);
+/// # impl Model for Screen {
+/// #     fn update(self, _: &Msg) -> (Self, Option<Msg>) { (self, None) }
+/// #     fn view(&self) -> String { self.0.to_string() }
+/// # }
+/// let stack = ScreenStack::new(Screen("menu"));
+/// let (stack, _) = stack.update(&Msg::new(PushScreen::new(Screen("settings"))));
+/// assert_eq!(stack.view(), "settings");
+/// let (stack, _) = stack.update(&Msg::new(PopScreen::new()));
+/// assert_eq!(stack.view(), "menu");
+/// ```
+pub struct ScreenStack<S> {
+    screens: Vec<S>,
+    /// Whether a screen has been pushed or popped since the last frame.
+    switched: bool,
+}
+
+impl<S: Model> ScreenStack<S> {
+    /// Create a new [`ScreenStack`] showing `root`.
+    pub fn new(root: S) -> Self {
+        Self {
+            screens: vec![root],
+            switched: false,
+        }
+    }
+
+    /// Put a screen on top of the stack, returning the message from its [`Model::startup`].
+    pub fn push(&mut self, screen: S) -> Option<Msg> {
+        let msg = screen.startup();
+        self.screens.push(screen);
+        self.switched = true;
+        msg
+    }
+
+    /// Remove the top screen, this is `None` if only the first screen is left.
+    pub fn pop(&mut self) -> Option<S> {
+        if self.screens.len() > 1 {
+            self.switched = true;
+            self.screens.pop()
+        } else {
+            None
+        }
+    }
+
+    /// The screen being shown.
+    pub fn top(&self) -> &S {
+        self.screens
+            .last()
+            .expect("the root screen is never popped")
+    }
+
+    /// The number of screens on the stack, including the first.
+    pub fn depth(&self) -> usize {
+        self.screens.len()
+    }
+
+    /// Give a message to the top screen.
+    fn update_top(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        let top = self.screens.pop().expect("the root screen is never popped");
+        let (top, msg) = top.update(msg);
+        self.screens.push(top);
+        (self, msg)
+    }
+}
+
+impl<S: Model + Send + 'static> Model for ScreenStack<S> {
+    fn startup(&self) -> Option<Msg> {
+        self.top().startup()
+    }
+
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if let Some(push) = msg.cast::<PushScreen<S>>() {
+            let msg = push.take().and_then(|screen| self.push(screen));
+            return (self, msg);
+        }
+
+        if let Some(pop) = msg.cast::<PopScreen>() {
+            if self.pop().is_none() {
+                return (self, None);
+            }
+            return match pop.take() {
+                Some(result) => self.update_top(&result),
+                None => (self, None),
+            };
+        }
+
+        self.update_top(msg)
+    }
+
+    fn view(&self) -> String {
+        self.top().view()
+    }
+
+    fn view_lines(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        self.top().view_lines()
+    }
+
+    fn overlay(&self) -> Option<(String, Rect)> {
+        self.top().overlay()
+    }
+
+    fn take_dirty_regions(&mut self) -> Option<Vec<Rect>> {
+        let top = self
+            .screens
+            .last_mut()
+            .expect("the root screen is never popped");
+        // The regions are still taken after a switch so they don't carry over to the next frame.
+        let regions = top.take_dirty_regions();
+        match std::mem::take(&mut self.switched) {
+            true => None,
+            false => regions,
+        }
+    }
+
+    fn on_quit(&self) {
+        for screen in self.screens.iter().rev() {
+            screen.on_quit();
+        }
+    }
+}
+
+/// A message to put a screen on top of a [`ScreenStack`].
+///
+/// The screen must be the same type as the stack's screens, otherwise it is passed on to the top
+/// screen like any other message.
+pub struct PushScreen<S>(Mutex<Option<S>>);
+impl<S: Send> Message for PushScreen<S> {}
+
+impl<S> PushScreen<S> {
+    /// Create a new [`PushScreen`] to show `screen`.
+    pub fn new(screen: S) -> Self {
+        Self(Mutex::new(Some(screen)))
+    }
+
+    /// Take the screen out to push it, this is `None` if it has already been taken.
+    fn take(&self) -> Option<S> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+/// A message to remove the top screen of a [`ScreenStack`], optionally giving a result to the
+/// screen underneath.
+///
+/// ```
+/// # use sketch::*;
+/// struct Confirmed(bool);
+/// impl Message for Confirmed {}
+///
+/// let msg = Msg::new(PopScreen::with_result(Msg::new(Confirmed(true))));
+/// ```
+pub struct PopScreen(Mutex<Option<Msg>>);
+impl Message for PopScreen {}
+
+impl PopScreen {
+    /// Create a new [`PopScreen`] without a result.
+    pub fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    /// Create a new [`PopScreen`] which gives `result` to the screen underneath once the top
+    /// screen is removed.
+    pub fn with_result(result: Msg) -> Self {
+        Self(Mutex::new(Some(result)))
+    }
+
+    /// Take the result out to give it to the next screen.
+    fn take(&self) -> Option<Msg> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+impl Default for PopScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tick;
+
+    /// A screen which records the messages it was given, marking its first line as changed by
+    /// each one.
+    struct Screen {
+        name: &'static str,
+        seen: Vec<&'static str>,
+        dirty: bool,
+    }
+
+    impl Screen {
+        fn new(name: &'static str) -> Self {
+            Self {
+                name,
+                seen: vec![],
+                dirty: false,
+            }
+        }
+    }
+
+    impl Model for Screen {
+        fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+            self.seen.push(msg.debug_name());
+            self.dirty = true;
+            (self, None)
+        }
+
+        fn view(&self) -> String {
+            self.name.to_string()
+        }
+
+        fn take_dirty_regions(&mut self) -> Option<Vec<Rect>> {
+            std::mem::take(&mut self.dirty).then(|| vec![Rect::new(0, 0, 4, 1)])
+        }
+    }
+
+    #[test]
+    fn test_messages_go_to_top() {
+        let stack = ScreenStack::new(Screen::new("root"));
+        let (stack, _) = stack.update(&Msg::new(PushScreen::new(Screen::new("top"))));
+        let (stack, _) = stack.update(&Msg::new(Tick));
+        assert_eq!(stack.depth(), 2);
+        assert_eq!(stack.top().seen, ["sketch::msg::Tick"]);
+        assert!(stack.screens[0].seen.is_empty());
+    }
+
+    #[test]
+    fn test_pop_gives_result_to_next_screen() {
+        let mut stack = ScreenStack::new(Screen::new("root"));
+        stack.push(Screen::new("top"));
+        let pop = PopScreen::with_result(Msg::new(Tick));
+        let (stack, _) = stack.update(&Msg::new(pop));
+        assert_eq!(stack.view(), "root");
+        assert_eq!(stack.top().seen, ["sketch::msg::Tick"]);
+    }
+
+    #[test]
+    fn test_root_is_never_popped() {
+        let mut stack = ScreenStack::new(Screen::new("root"));
+        assert!(stack.pop().is_none());
+        let (stack, _) = stack.update(&Msg::new(PopScreen::new()));
+        assert_eq!(stack.depth(), 1);
+        assert_eq!(stack.view(), "root");
+    }
+
+    #[test]
+    fn test_dirty_regions_come_from_top() {
+        let stack = ScreenStack::new(Screen::new("root"));
+        let (mut stack, _) = stack.update(&Msg::new(Tick));
+        assert_eq!(
+            stack.take_dirty_regions(),
+            Some(vec![Rect::new(0, 0, 4, 1)])
+        );
+        assert_eq!(stack.take_dirty_regions(), None);
+
+        // The frame after a push or pop is drawn in full, even if the new top has changed.
+        let (stack, _) = stack.update(&Msg::new(PushScreen::new(Screen::new("top"))));
+        let (mut stack, _) = stack.update(&Msg::new(Tick));
+        assert_eq!(stack.take_dirty_regions(), None);
+        let (mut stack, _) = stack.update(&Msg::new(Tick));
+        assert_eq!(
+            stack.take_dirty_regions(),
+            Some(vec![Rect::new(0, 0, 4, 1)])
+        );
+
+        let pop = PopScreen::with_result(Msg::new(Tick));
+        let (mut stack, _) = stack.update(&Msg::new(pop));
+        assert_eq!(stack.take_dirty_regions(), None);
+    }
+}
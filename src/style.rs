@@ -1,8 +1,75 @@
-use crate::terminal_size;
+use crate::{capabilities, terminal_size, text, Capabilities};
+use std::borrow::Cow;
 use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub use crossterm::style::Color;
 
+/// The width of the terminal, or `0` if it is unknown.
+fn terminal_width() -> usize {
+    terminal_size().map_or(0, |(cols, _)| cols as usize)
+}
+
+/// The ANSI code to reset all styling.
+const RESET: &str = "\x1b[0m";
+
+/// Whether the terminal's background is dark, used to pick [`AdaptiveColor`]s.
+static DARK_BACKGROUND: AtomicBool = AtomicBool::new(true);
+
+/// Is the terminal's background dark, which decides the color [`AdaptiveColor`]s are rendered
+/// in.
+///
+/// This is assumed until the app receives a [`TerminalBackground`](crate::TerminalBackground)
+/// message, see [`App::with_background_query`](crate::App::with_background_query).
+pub fn is_dark_background() -> bool {
+    DARK_BACKGROUND.load(Ordering::Relaxed)
+}
+
+/// Set whether the terminal's background is dark for the whole program, such as if it is known
+/// without asking the terminal.
+pub fn set_dark_background(dark: bool) {
+    DARK_BACKGROUND.store(dark, Ordering::Relaxed);
+}
+
+/// A color which depends on whether the terminal's background is light or dark, so a style can
+/// stay readable on both.
+///
+/// The color is picked each time a [`Style`] is rendered, see [`is_dark_background`].
+///
+/// ```
+/// # use sketch::{AdaptiveColor, Color, Style};
+/// const SUBTLE: AdaptiveColor = AdaptiveColor::new(Color::DarkGrey, Color::Grey);
+/// const HINT_STYLE: Style = Style::new().adaptive_fg(SUBTLE.light, SUBTLE.dark);
+/// assert_eq!(SUBTLE.resolve(true), Color::Grey);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveColor {
+    /// The color used on light backgrounds.
+    pub light: Color,
+    /// The color used on dark backgrounds.
+    pub dark: Color,
+}
+
+impl AdaptiveColor {
+    /// Create a new [`AdaptiveColor`].
+    pub const fn new(light: Color, dark: Color) -> Self {
+        Self { light, dark }
+    }
+
+    /// The same color on every background.
+    const fn fixed(color: Color) -> Self {
+        Self::new(color, color)
+    }
+
+    /// The color to use on a dark background if `dark` is set, otherwise a light one.
+    pub const fn resolve(&self, dark: bool) -> Color {
+        match dark {
+            true => self.dark,
+            false => self.light,
+        }
+    }
+}
+
 /// Change how the text is displayed to the user.
 ///
 /// ```no_run
@@ -12,21 +79,107 @@ pub use crossterm::style::Color;
 /// ```
 #[derive(Debug, Default, Clone)]
 pub struct Style {
-    fg: Option<Color>,
-    bg: Option<Color>,
+    fg: Option<AdaptiveColor>,
+    bg: Option<AdaptiveColor>,
     bold: bool,
     dim: bool,
     italic: bool,
     underline: bool,
+    overline: bool,
     underline_color: Option<Color>,
     blink: Option<Blink>,
     reverse: bool,
     crossed_out: bool,
     align: Align,
+    fade_end: Option<usize>,
+    fill_width: bool,
+    bg_gradient: Option<BgGradient>,
+    padding: Padding,
+    border: Option<BorderStyle>,
+    width: Option<usize>,
+    ellipsis: Option<String>,
+    wrap: Option<usize>,
+    /// Which of the fields without an `Option` have been set, so a field set to its default value
+    /// still wins when [merged](Style::merge).
+    set: u16,
+}
+
+const SET_BOLD: u16 = 1;
+const SET_DIM: u16 = 1 << 1;
+const SET_ITALIC: u16 = 1 << 2;
+const SET_UNDERLINE: u16 = 1 << 3;
+const SET_OVERLINE: u16 = 1 << 4;
+const SET_REVERSE: u16 = 1 << 5;
+const SET_CROSSED_OUT: u16 = 1 << 6;
+const SET_ALIGN: u16 = 1 << 7;
+const SET_FILL_WIDTH: u16 = 1 << 8;
+const SET_PADDING_TOP: u16 = 1 << 9;
+const SET_PADDING_RIGHT: u16 = 1 << 10;
+const SET_PADDING_BOTTOM: u16 = 1 << 11;
+const SET_PADDING_LEFT: u16 = 1 << 12;
+
+// Styles are equal if they render the same, whichever fields were set to get there.
+impl PartialEq for Style {
+    fn eq(&self, other: &Self) -> bool {
+        let Style {
+            fg,
+            bg,
+            bold,
+            dim,
+            italic,
+            underline,
+            overline,
+            underline_color,
+            blink,
+            reverse,
+            crossed_out,
+            align,
+            fade_end,
+            fill_width,
+            bg_gradient,
+            padding,
+            border,
+            width,
+            ellipsis,
+            wrap,
+            set: _,
+        } = self;
+        *fg == other.fg
+            && *bg == other.bg
+            && *bold == other.bold
+            && *dim == other.dim
+            && *italic == other.italic
+            && *underline == other.underline
+            && *overline == other.overline
+            && *underline_color == other.underline_color
+            && *blink == other.blink
+            && *reverse == other.reverse
+            && *crossed_out == other.crossed_out
+            && *align == other.align
+            && *fade_end == other.fade_end
+            && *fill_width == other.fill_width
+            && *bg_gradient == other.bg_gradient
+            && *padding == other.padding
+            && *border == other.border
+            && *width == other.width
+            && *ellipsis == other.ellipsis
+            && *wrap == other.wrap
+    }
+}
+
+impl Eq for Style {}
+
+/// The spacing inside a rendered block of text, see [`Style::padding`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Padding {
+    top: u16,
+    right: u16,
+    bottom: u16,
+    left: u16,
 }
 
 /// The speed of text blinking for [`Style::blink`].
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Blink {
     /// Less than 150 times per minute.
     Slow,
@@ -34,8 +187,66 @@ pub enum Blink {
     Rapid,
 }
 
+/// The characters a [`Style::border`] is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderStyle {
+    /// Thin lines with square corners, `┌─┐`.
+    Plain,
+    /// Thin lines with rounded corners, `╭─╮`.
+    Rounded,
+    /// Two thin lines, `╔═╗`.
+    Double,
+    /// Thick lines, `┏━┓`.
+    Thick,
+}
+
+impl BorderStyle {
+    /// The top left, top right, bottom left and bottom right corners, then the horizontal and
+    /// vertical lines.
+    const fn chars(self) -> [char; 6] {
+        match self {
+            BorderStyle::Plain => ['┌', '┐', '└', '┘', '─', '│'],
+            BorderStyle::Rounded => ['╭', '╮', '╰', '╯', '─', '│'],
+            BorderStyle::Double => ['╔', '╗', '╚', '╝', '═', '║'],
+            BorderStyle::Thick => ['┏', '┓', '┗', '┛', '━', '┃'],
+        }
+    }
+
+    /// Draw a box around `text`, as wide as its widest line.
+    fn apply(self, text: &str) -> String {
+        let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] = self.chars();
+        let width = text.split('\n').map(text::width).max().unwrap_or(0);
+        let rule = horizontal.to_string().repeat(width);
+
+        let mut lines = vec![format!("{top_left}{rule}{top_right}")];
+        lines.extend(text.split('\n').map(|line| {
+            let fill = " ".repeat(width - text::width(line));
+            format!("{vertical}{line}{fill}{vertical}")
+        }));
+        lines.push(format!("{bottom_left}{rule}{bottom_right}"));
+        lines.join("\n")
+    }
+}
+
+/// Which way a [`Style::bg_gradient`] goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// From the top line to the bottom line.
+    Vertical,
+    /// From the left column to the right column.
+    Horizontal,
+}
+
+/// A background which blends between two colors, see [`Style::bg_gradient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BgGradient {
+    from: Color,
+    to: Color,
+    direction: GradientDirection,
+}
+
 /// Alignment options for text.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub enum Align {
     /// Align text left.
     #[default]
@@ -50,14 +261,14 @@ macro_rules! style_method {
     ($method:ident, fg, $value:expr) => {
         #[doc = concat!("Set the text color to [`", stringify!($value), "`].")]
         pub const fn $method(mut self) -> Self {
-            self.fg = Some($value);
+            self.fg = Some(AdaptiveColor::fixed($value));
             self
         }
     };
     ($method:ident, bg, $value:expr) => {
         #[doc = concat!("Set the background color to [`", stringify!($value), "`].")]
         pub const fn $method(mut self) -> Self {
-            self.fg = Some($value);
+            self.fg = Some(AdaptiveColor::fixed($value));
             self
         }
     };
@@ -75,6 +286,25 @@ macro_rules! style_method {
             self
         }
     };
+    ($method:ident, $no_method:ident, $field:ident, $set:ident, $doc:literal) => {
+        #[doc = $doc]
+        pub const fn $method(mut self) -> Self {
+            self.$field = true;
+            self.set |= $set;
+            self
+        }
+
+        #[doc = concat!(
+                    "Turn off [`Style::",
+                    stringify!($method),
+                    "`], such as in a style [merged](Style::merge) over one which turns it on."
+                )]
+        pub const fn $no_method(mut self) -> Self {
+            self.$field = false;
+            self.set |= $set;
+            self
+        }
+    };
 }
 
 impl Style {
@@ -87,29 +317,68 @@ impl Style {
             dim: false,
             italic: false,
             underline: false,
+            overline: false,
             underline_color: None,
             blink: None,
             reverse: false,
             crossed_out: false,
             align: Align::Left,
+            fade_end: None,
+            fill_width: false,
+            bg_gradient: None,
+            padding: Padding {
+                top: 0,
+                right: 0,
+                bottom: 0,
+                left: 0,
+            },
+            border: None,
+            width: None,
+            ellipsis: None,
+            wrap: None,
+            set: 0,
         }
     }
 
     /// Set the color of the text.
+    ///
+    /// [`Color::Reset`] uses the terminal's default text color, leaving the rest of the style as
+    /// it is. Use [`Style::reset_code`] to reset all styling.
     pub const fn fg(mut self, color: Color) -> Self {
-        self.fg = Some(color);
+        self.fg = Some(AdaptiveColor::fixed(color));
+        self
+    }
+
+    /// Set the color of the text to `light` on light backgrounds and `dark` on dark ones, see
+    /// [`AdaptiveColor`].
+    pub const fn adaptive_fg(mut self, light: Color, dark: Color) -> Self {
+        self.fg = Some(AdaptiveColor::new(light, dark));
         self
     }
 
     /// Set the color of the background.
+    ///
+    /// [`Color::Reset`] uses the terminal's default background color, leaving the rest of the
+    /// style as it is.
     pub const fn bg(mut self, color: Color) -> Self {
-        self.bg = Some(color);
+        self.bg = Some(AdaptiveColor::fixed(color));
+        self
+    }
+
+    /// Set the color of the background to `light` on light terminal backgrounds and `dark` on
+    /// dark ones, see [`AdaptiveColor`].
+    pub const fn adaptive_bg(mut self, light: Color, dark: Color) -> Self {
+        self.bg = Some(AdaptiveColor::new(light, dark));
         self
     }
 
     /// Set the color of the underline.
+    ///
+    /// [`Color::Reset`] uses the same color as the text for the underline, leaving the rest of
+    /// the style as it is.
     pub const fn underline_color(mut self, color: Color) -> Self {
         self.underline = true;
+        self.set |= SET_UNDERLINE;
         self.underline_color = Some(color);
         self
     }
@@ -127,22 +396,197 @@ impl Style {
     /// See [`Style::left`], [`Style::center`] and [`Style::right`] for shorthands.
     pub const fn align(mut self, align: Align) -> Self {
         self.align = align;
+        self.set |= SET_ALIGN;
+        self
+    }
+
+    /// Cut each line of the text to at most `width` columns, dimming the last few columns of
+    /// lines that were cut to suggest there is more.
+    ///
+    /// This is a softer alternative to ending an overflowing line with an ellipsis. On terminals
+    /// without dim text the lines are only cut.
+    ///
+    /// ```
+    /// # use sketch::Style;
+    /// let status = Style::new().fade_end(6).render("0123456789");
+    /// assert!(status.starts_with("012\x1b[2m345"));
+    /// ```
+    pub const fn fade_end(mut self, width: usize) -> Self {
+        self.fade_end = Some(width);
         self
     }
 
-    style_method! { left, align, Align::Left, "Align the text to the left." }
-    style_method! { center, align, Align::Center, "Align the text in the center." }
-    style_method! { right, align, Align::Right, "Align the text to the right." }
+    /// Pad each line with spaces to the width it is rendered in, so a background color or
+    /// reverse covers the whole row rather than just the text, such as for the selected row of
+    /// a list.
+    ///
+    /// Use [`Style::render_in`] to fill a part of the screen such as a panel rather than the
+    /// whole width of the terminal. Lines wider than the width are left as they are.
+    ///
+    /// ```
+    /// # use sketch::Style;
+    /// let row = Style::new().reverse().fill_width().render_in("Item", 8);
+    /// assert_eq!(row, "\x1b[7mItem    \x1b[0m");
+    /// ```
+    pub const fn fill_width(mut self) -> Self {
+        self.fill_width = true;
+        self.set |= SET_FILL_WIDTH;
+        self
+    }
+
+    /// Fill the background of every cell the text covers with a gradient from `from` to `to`,
+    /// such as for a header or splash screen.
+    ///
+    /// Each line is padded to the width it is rendered in, or to the widest line if the width is
+    /// unknown, so the gradient covers a full block. This replaces any [`Style::bg`] color.
+    ///
+    /// The gradient needs both colors to be [`Color::Rgb`] and a terminal with truecolor, see
+    /// [`Capabilities::truecolor`](crate::Capabilities::truecolor), otherwise the whole
+    /// background is `from`.
+    ///
+    /// ```
+    /// # use sketch::{Color, GradientDirection, Style};
+    /// const NAVY: Color = Color::Rgb { r: 0, g: 0, b: 64 };
+    /// const TEAL: Color = Color::Rgb { r: 0, g: 128, b: 128 };
+    /// let splash = Style::new()
+    ///     .white()
+    ///     .bg_gradient(NAVY, TEAL, GradientDirection::Vertical)
+    ///     .render_in("\n  Welcome\n", 20);
+    /// ```
+    pub const fn bg_gradient(
+        mut self,
+        from: Color,
+        to: Color,
+        direction: GradientDirection,
+    ) -> Self {
+        self.bg_gradient = Some(BgGradient {
+            from,
+            to,
+            direction,
+        });
+        self
+    }
+
+    /// Add blank space inside the text, with the style's background covering it.
+    ///
+    /// Every line is padded to the width of the widest one so the text is a solid block, then
+    /// `left` and `right` columns of spaces are added to each side and `top` and `bottom` blank
+    /// lines above and below. The padding counts towards the width of the text when aligning it.
+    ///
+    /// ```
+    /// # use sketch::Style;
+    /// let button = Style::new().reverse().padding(0, 2, 0, 2).render("OK");
+    /// assert_eq!(button, "\x1b[7m  OK  \x1b[0m");
+    /// ```
+    pub const fn padding(mut self, top: u16, right: u16, bottom: u16, left: u16) -> Self {
+        self.padding = Padding {
+            top,
+            right,
+            bottom,
+            left,
+        };
+        self.set |= SET_PADDING_TOP | SET_PADDING_RIGHT | SET_PADDING_BOTTOM | SET_PADDING_LEFT;
+        self
+    }
+
+    /// Add the same padding on every side, see [`Style::padding`].
+    pub const fn padding_all(self, padding: u16) -> Self {
+        self.padding(padding, padding, padding, padding)
+    }
+
+    /// Add padding to the left and right, see [`Style::padding`].
+    pub const fn padding_x(mut self, padding: u16) -> Self {
+        self.padding.left = padding;
+        self.padding.right = padding;
+        self.set |= SET_PADDING_LEFT | SET_PADDING_RIGHT;
+        self
+    }
+
+    /// Add padding above and below, see [`Style::padding`].
+    pub const fn padding_y(mut self, padding: u16) -> Self {
+        self.padding.top = padding;
+        self.padding.bottom = padding;
+        self.set |= SET_PADDING_TOP | SET_PADDING_BOTTOM;
+        self
+    }
+
+    /// Word wrap the text to fit within `cols` columns with [`wrap_text`](crate::wrap_text)
+    /// before anything else is done to it.
+    ///
+    /// ```
+    /// # use sketch::Style;
+    /// let text = Style::new().wrap(10).render("the quick brown fox");
+    /// assert_eq!(text, "the quick\nbrown fox\x1b[0m");
+    /// ```
+    pub const fn wrap(mut self, cols: usize) -> Self {
+        self.wrap = Some(cols);
+        self
+    }
+
+    /// Make each line exactly `cols` columns wide, such as for the cells of a table.
+    ///
+    /// Shorter lines are padded with spaces and longer lines are cut, ending with an ellipsis
+    /// which is `…` unless set with [`Style::truncate_with`]. The width doesn't include any
+    /// [padding](Style::padding) or [border](Style::border).
+    ///
+    /// ```
+    /// # use sketch::Style;
+    /// assert_eq!(Style::new().width(6).render("ab"), "ab    \x1b[0m");
+    /// assert_eq!(Style::new().width(6).render("abcdefgh"), "abcde…\x1b[0m");
+    /// ```
+    pub const fn width(mut self, cols: usize) -> Self {
+        self.width = Some(cols);
+        self
+    }
+
+    /// Set the text which ends lines cut to fit a [`Style::width`]. Defaults to `"…"`.
+    pub fn truncate_with(mut self, ellipsis: &str) -> Self {
+        self.ellipsis = Some(ellipsis.to_string());
+        self
+    }
+
+    /// Draw a box around the text, sized to its widest line and outside any
+    /// [padding](Style::padding).
+    ///
+    /// The border is drawn in the same style as the text, so it has the same color.
+    ///
+    /// ```
+    /// # use sketch::{BorderStyle, Style};
+    /// let boxed = Style::new().border(BorderStyle::Plain).render("hi");
+    /// assert_eq!(boxed, "┌──┐\n│hi│\n└──┘\x1b[0m");
+    /// ```
+    pub const fn border(mut self, border: BorderStyle) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// Align the text to the left.
+    pub const fn left(self) -> Self {
+        self.align(Align::Left)
+    }
+
+    /// Align the text in the center.
+    pub const fn center(self) -> Self {
+        self.align(Align::Center)
+    }
+
+    /// Align the text to the right.
+    pub const fn right(self) -> Self {
+        self.align(Align::Right)
+    }
 
     // Modifiers
-    style_method! { bold, bold, true, "Make the text bold." }
-    style_method! { dim, dim, true, "Make the text dim." }
-    style_method! { italic, italic, true, "Make the text italic." }
-    style_method! { underline, underline, true, "Underline the text." }
+    style_method! { bold, no_bold, bold, SET_BOLD, "Make the text bold." }
+    style_method! { dim, no_dim, dim, SET_DIM, "Make the text dim." }
+    style_method! { italic, no_italic, italic, SET_ITALIC, "Make the text italic." }
+    style_method! { underline, no_underline, underline, SET_UNDERLINE, "Underline the text." }
+    style_method! { overline, no_overline, overline, SET_OVERLINE, "Draw a line above the text." }
     style_method! { slow_blink, blink, Some(Blink::Slow), "Blink the text slowly." }
     style_method! { rapid_blink, blink, Some(Blink::Rapid), "Blick the text rapidly." }
-    style_method! { reverse, reverse, true, "Spawn the text and background colors." }
-    style_method! { crossed_out, crossed_out, true, "Cross the text." }
+    style_method! {
+        reverse, no_reverse, reverse, SET_REVERSE, "Spawn the text and background colors."
+    }
+    style_method! { crossed_out, no_crossed_out, crossed_out, SET_CROSSED_OUT, "Cross the text." }
 
     // Forground/Text Colors
     style_method! { black, fg, Color::Black }
@@ -198,18 +642,298 @@ impl Style {
     style_method! { underline_white, underline_color, Color::White }
     style_method! { underline_grey, underline_color, Color::Grey }
 
+    /// Layer `other` on top of this style, such as to override part of a base style from a
+    /// [`Theme`](crate::Theme).
+    ///
+    /// Anything set on `other` wins, everything it leaves unset is kept from this style. Setting a
+    /// field to its default value still counts, so a modifier such as bold can be turned off with
+    /// [`Style::no_bold`] and [`Style::left`] overrides a centered style.
+    ///
+    /// ```
+    /// # use sketch::Style;
+    /// let base = Style::new().red().bold();
+    /// let selected = base.merge(&Style::new().reverse());
+    /// assert_eq!(selected, Style::new().red().bold().reverse());
+    /// ```
+    pub fn merge(self, other: &Style) -> Style {
+        Style {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            bold: other.pick(SET_BOLD, self.bold, other.bold),
+            dim: other.pick(SET_DIM, self.dim, other.dim),
+            italic: other.pick(SET_ITALIC, self.italic, other.italic),
+            underline: other.pick(SET_UNDERLINE, self.underline, other.underline),
+            overline: other.pick(SET_OVERLINE, self.overline, other.overline),
+            underline_color: other.underline_color.or(self.underline_color),
+            blink: other.blink.clone().or(self.blink),
+            reverse: other.pick(SET_REVERSE, self.reverse, other.reverse),
+            crossed_out: other.pick(SET_CROSSED_OUT, self.crossed_out, other.crossed_out),
+            align: other.pick(SET_ALIGN, self.align, other.align.clone()),
+            fade_end: other.fade_end.or(self.fade_end),
+            fill_width: other.pick(SET_FILL_WIDTH, self.fill_width, other.fill_width),
+            bg_gradient: other.bg_gradient.or(self.bg_gradient),
+            padding: Padding {
+                top: other.pick(SET_PADDING_TOP, self.padding.top, other.padding.top),
+                right: other.pick(SET_PADDING_RIGHT, self.padding.right, other.padding.right),
+                bottom: other.pick(
+                    SET_PADDING_BOTTOM,
+                    self.padding.bottom,
+                    other.padding.bottom,
+                ),
+                left: other.pick(SET_PADDING_LEFT, self.padding.left, other.padding.left),
+            },
+            border: other.border.or(self.border),
+            width: other.width.or(self.width),
+            ellipsis: other.ellipsis.clone().or(self.ellipsis),
+            wrap: other.wrap.or(self.wrap),
+            set: self.set | other.set,
+        }
+    }
+
+    /// `over` if this style set the field with the `set` bit, otherwise `this`.
+    fn pick<T>(&self, set: u16, this: T, over: T) -> T {
+        match self.set & set != 0 {
+            true => over,
+            false => this,
+        }
+    }
+
     /// Render text with this style
+    ///
+    /// If the width of the terminal can't be found, or is reported as zero as it is in some
+    /// detached sessions, centered and right aligned text is left aligned instead.
+    ///
+    /// Any style resets (`\x1b[0m`) inside `text`, such as from an already styled substring, are
+    /// followed by this style's codes so the text after them keeps this style.
     pub fn render(&self, text: impl AsRef<str>) -> String {
+        self.render_with(text.as_ref(), terminal_width, capabilities())
+    }
+
+    /// Render text with this style, aligning it within `cols` columns instead of the width of the
+    /// terminal.
+    ///
+    /// This is useful for aligning text within part of the screen such as a panel. A width of `0`
+    /// is treated as unknown and the text is left aligned.
+    ///
+    /// ```
+    /// # use sketch::Style;
+    /// let label = Style::new().center().render_in("0123456789", 20);
+    /// assert!(label.starts_with(&" ".repeat(5)));
+    /// ```
+    pub fn render_in(&self, text: impl AsRef<str>, cols: usize) -> String {
+        self.render_with(text.as_ref(), || cols, capabilities())
+    }
+
+    /// Render text with this style without resetting the style at the end.
+    ///
+    /// This avoids a reset after every piece of text when building up a string from many styled
+    /// pieces, but the caller is then responsible for resetting the style with
+    /// [`Style::reset_code`] before any text which shouldn't have it.
+    ///
+    /// ```
+    /// # use sketch::Style;
+    /// let mut text = Style::new().bold().render_open("bold ");
+    /// text.push_str(&Style::new().italic().render_open("and italic"));
+    /// text.push_str(Style::reset_code());
+    /// assert_eq!(text, "\x1b[1mbold \x1b[3mand italic\x1b[0m");
+    /// ```
+    pub fn render_open(&self, text: impl AsRef<str>) -> String {
+        self.render_open_with(text.as_ref(), terminal_width, capabilities())
+    }
+
+    /// The ANSI code which resets all styling, for ending text from [`Style::render_open`].
+    ///
+    /// Unlike [`Color::Reset`], which only resets a single color, this also resets every
+    /// modifier such as bold.
+    pub const fn reset_code() -> &'static str {
+        RESET
+    }
+
+    /// Render text with this style aligned within `cols` columns, like [`Style::render_in`], also
+    /// returning the `(width, height)` it takes up on screen.
+    ///
+    /// The width is that of the widest line including any padding from alignment, this saves
+    /// measuring the rendered text again when laying it out.
+    ///
+    /// ```
+    /// # use sketch::Style;
+    /// let (text, size) = Style::new().bold().right().render_measured("ab", 6);
+    /// assert_eq!(size, (6, 1));
+    /// ```
+    pub fn render_measured(&self, text: impl AsRef<str>, cols: usize) -> (String, (usize, usize)) {
         let mut result = String::new();
-        let cols = terminal_size().unwrap().0 as usize;
+        let size = self.write_open(&mut result, text.as_ref(), || cols, capabilities());
+        result.push_str(RESET);
+        (result, size)
+    }
 
-        if self.bold {
+    /// Render text with this style, appending it to `buf` instead of allocating a new string.
+    ///
+    /// This is the same as [`Style::render`] but avoids an allocation for each piece of text when
+    /// building up a large view.
+    ///
+    /// ```
+    /// # use sketch::Style;
+    /// let mut view = String::with_capacity(64);
+    /// for n in 0..3 {
+    ///     Style::new().bold().render_into(&mut view, &n.to_string());
+    /// }
+    /// assert_eq!(view, "\x1b[1m0\x1b[0m\x1b[1m1\x1b[0m\x1b[1m2\x1b[0m");
+    /// ```
+    pub fn render_into(&self, buf: &mut String, text: &str) {
+        self.write_open(buf, text, terminal_width, capabilities());
+        buf.push_str(RESET);
+    }
+
+    /// Render text with this style, only getting the width to align within if it is needed.
+    fn render_with(
+        &self,
+        text: &str,
+        cols: impl FnOnce() -> usize,
+        capabilities: Capabilities,
+    ) -> String {
+        let mut result = String::new();
+        self.write_open(&mut result, text, cols, capabilities);
+        result.push_str(RESET);
+        result
+    }
+
+    /// Render text with this style like [`Style::render_with`] but without the final reset.
+    fn render_open_with(
+        &self,
+        text: &str,
+        cols: impl FnOnce() -> usize,
+        capabilities: Capabilities,
+    ) -> String {
+        let mut result = String::new();
+        self.write_open(&mut result, text, cols, capabilities);
+        result
+    }
+
+    /// Append text with this style to `buf`, without the final reset, returning the visible
+    /// `(width, height)` of what was written.
+    fn write_open(
+        &self,
+        buf: &mut String,
+        text: &str,
+        cols: impl FnOnce() -> usize,
+        capabilities: Capabilities,
+    ) -> (usize, usize) {
+        let codes_start = buf.len();
+        self.write_codes(buf, capabilities, is_dark_background());
+        let codes_end = buf.len();
+
+        let text = match self.wrap {
+            Some(cols) => Cow::Owned(text::wrap_text(text, cols).join("\n")),
+            None => Cow::Borrowed(text),
+        };
+        let text = match self.fade_end {
+            Some(width) => Cow::Owned(
+                text.split('\n')
+                    .map(|line| text::fade_end(line, width, capabilities.dim))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            None => text,
+        };
+        let text = match self.width {
+            Some(cols) => {
+                let ellipsis = self.ellipsis.as_deref().unwrap_or("…");
+                Cow::Owned(
+                    text.split('\n')
+                        .map(|line| fit_width(line, cols, ellipsis))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                )
+            }
+            None => text,
+        };
+        let text = match self.padding == Padding::default() {
+            true => text,
+            false => Cow::Owned(self.padding.apply(&text)),
+        };
+        let text = match self.border {
+            Some(border) => Cow::Owned(border.apply(&text)),
+            None => text,
+        };
+        let text = text.as_ref();
+
+        let len = text.split('\n').map(visible_length).max().unwrap_or(0);
+
+        // A width of zero means it is unknown, so leave the text unaligned and unfilled.
+        let filled = self.fill_width || self.bg_gradient.is_some();
+        let align = &self.align;
+        let cols = match (filled, align) {
+            (false, Align::Left) => 0,
+            _ => cols(),
+        };
+        let mut padding = match align {
+            _ if cols == 0 => 0,
+            Align::Left => 0,
+            Align::Center => (cols / 2).saturating_sub(len / 2),
+            Align::Right => cols.saturating_sub(len),
+        };
+
+        let text = match &self.bg_gradient {
+            // The padding is part of the block so the gradient covers it too.
+            Some(gradient) => {
+                let text = format!("{}{text}", " ".repeat(padding));
+                padding = 0;
+                let width = match cols {
+                    0 => text.split('\n').map(text::width).max().unwrap_or(0),
+                    cols => cols,
+                };
+                let text = fill_lines(&text, width, 0);
+                Cow::Owned(match capabilities.truecolor && capabilities.color {
+                    true => gradient.paint(&text, width),
+                    false => text,
+                })
+            }
+            None if self.fill_width && cols > 0 => Cow::Owned(fill_lines(text, cols, padding)),
+            None => Cow::Borrowed(text),
+        };
+        let text = text.as_ref();
+        buf.extend(std::iter::repeat_n(' ', padding));
+
+        if codes_start == codes_end || !text.contains(RESET) {
+            buf.push_str(text);
+        } else {
+            let codes = buf[codes_start..codes_end].to_string();
+            for (n, part) in text.split(RESET).enumerate() {
+                if n > 0 {
+                    buf.push_str(RESET);
+                    buf.push_str(&codes);
+                }
+                buf.push_str(part);
+            }
+        }
+
+        // Only the first line is padded.
+        text.split('\n')
+            .enumerate()
+            .fold((0, 0), |(width, height), (n, line)| {
+                let line_width = text::width(line) + if n == 0 { padding } else { 0 };
+                (width.max(line_width), height + 1)
+            })
+    }
+
+    /// The ANSI codes to enable this style, leaving out anything the terminal doesn't support.
+    pub(crate) fn codes(&self, capabilities: Capabilities) -> String {
+        let mut result = String::new();
+        self.write_codes(&mut result, capabilities, is_dark_background());
+        result
+    }
+
+    /// Append the ANSI codes to enable this style to `result`, picking adaptive colors for a dark
+    /// background if `dark` is set.
+    fn write_codes(&self, result: &mut String, capabilities: Capabilities, dark: bool) {
+        if self.bold && capabilities.bold {
             result.push_str("\x1b[1m");
         }
-        if self.dim {
+        if self.dim && capabilities.dim {
             result.push_str("\x1b[2m");
         }
-        if self.italic {
+        if self.italic && capabilities.italic {
             result.push_str("\x1b[3m");
         }
         if self.underline {
@@ -224,38 +948,33 @@ impl Style {
         if self.reverse {
             result.push_str("\x1b[7m");
         }
-        if self.crossed_out {
+        if self.crossed_out && capabilities.crossed_out {
             result.push_str("\x1b[9m");
         }
+        if self.overline {
+            result.push_str("\x1b[53m");
+        }
 
+        if !capabilities.color {
+            return;
+        }
         if let Some(color) = &self.fg {
-            Self::write_fg_color(&mut result, color);
+            Self::write_fg_color(result, &color.resolve(dark));
         }
-        if let Some(color) = &self.bg {
-            Self::write_bg_color(&mut result, color);
+        match (&self.bg_gradient, &self.bg) {
+            (Some(gradient), _) => Self::write_bg_color(result, &gradient.from),
+            (None, Some(color)) => Self::write_bg_color(result, &color.resolve(dark)),
+            (None, None) => {}
         }
         if let Some(color) = &self.underline_color {
-            Self::write_underline_color(&mut result, color);
-        }
-
-        let text = text.as_ref();
-        let len = visible_length(text);
-
-        match self.align {
-            Align::Left => {}
-            Align::Center => result.push_str(&" ".repeat(cols / 2 - len / 2)),
-            Align::Right => result.push_str(&" ".repeat(cols - len)),
+            Self::write_underline_color(result, color);
         }
-
-        result.push_str(text);
-        result.push_str("\x1b[0m"); // Reset style
-        result
     }
 
     /// Write the ANSI code for text with the given color.
     fn write_fg_color(f: &mut String, color: &Color) {
         match color {
-            Color::Reset => write!(f, "\x1b[0m").unwrap(),
+            Color::Reset => write!(f, "\x1b[39m").unwrap(),
             Color::Black => write!(f, "\x1b[30m").unwrap(),
             Color::DarkGrey => write!(f, "\x1b[90m").unwrap(),
             Color::Red => write!(f, "\x1b[91m").unwrap(),
@@ -328,30 +1047,123 @@ impl Style {
     }
 }
 
-/// The length of a string excluding the ANSI codes.
-fn visible_length(input: &str) -> usize {
-    let mut in_escape_code = false;
-    let mut length = 0;
-
-    for c in input.chars() {
-        match c {
-            '\x1b' => {
-                // Start of escape sequence
-                in_escape_code = true;
+impl Padding {
+    /// Pad `text` out in to a block with this much space around it.
+    fn apply(&self, text: &str) -> String {
+        let width = text.split('\n').map(text::width).max().unwrap_or(0);
+        let full_width = self.left as usize + width + self.right as usize;
+        let blank = " ".repeat(full_width);
+
+        let mut lines = vec![blank.clone(); self.top as usize];
+        lines.extend(text.split('\n').map(|line| {
+            let right = full_width - self.left as usize - text::width(line);
+            format!(
+                "{}{line}{}",
+                " ".repeat(self.left as usize),
+                " ".repeat(right)
+            )
+        }));
+        lines.extend(std::iter::repeat_n(blank, self.bottom as usize));
+        lines.join("\n")
+    }
+}
+
+impl BgGradient {
+    /// Give each cell of `text`, a block `width` columns wide, its own background color.
+    ///
+    /// The text is expected to follow the style's codes, which already set the background to the
+    /// first color. Text is left as it is unless both colors are [`Color::Rgb`].
+    fn paint(&self, text: &str, width: usize) -> String {
+        let (
+            Color::Rgb { r, g, b },
+            Color::Rgb {
+                r: r2,
+                g: g2,
+                b: b2,
+            },
+        ) = (self.from, self.to)
+        else {
+            return text.to_string();
+        };
+        let height = text.split('\n').count();
+        let blend = |position: usize, len: usize| {
+            let t = position as f32 / len.saturating_sub(1).max(1) as f32;
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+            Color::Rgb {
+                r: mix(r, r2),
+                g: mix(g, g2),
+                b: mix(b, b2),
             }
-            'm' if in_escape_code => {
-                // End of escape sequence
-                in_escape_code = false;
+        };
+
+        let mut result = String::with_capacity(text.len() * 4);
+        for (row, line) in text.split('\n').enumerate() {
+            if row > 0 {
+                result.push('\n');
             }
-            _ if !in_escape_code => {
-                // Count character if it's not in an escape sequence
-                length += 1;
+            // The last color written, cleared by escape codes in the text which may change it.
+            let mut current = match row {
+                0 => Some(self.from),
+                _ => None,
+            };
+            let mut column = 0;
+            for token in text::tokens(line) {
+                match token {
+                    text::Token::Escape(code) => {
+                        result.push_str(code);
+                        current = None;
+                    }
+                    text::Token::Char(c) => {
+                        let color = match self.direction {
+                            GradientDirection::Vertical => blend(row, height),
+                            GradientDirection::Horizontal => blend(column, width),
+                        };
+                        if current != Some(color) {
+                            Style::write_bg_color(&mut result, &color);
+                            current = Some(color);
+                        }
+                        result.push(c);
+                        column += text::char_width(c);
+                    }
+                }
             }
-            _ => {}
         }
+        result
     }
+}
+
+/// Pad `line` with spaces to exactly `cols` columns, or cut it down to them ending with
+/// `ellipsis` if it is wider.
+fn fit_width(line: &str, cols: usize, ellipsis: &str) -> String {
+    let width = text::width(line);
+    if width <= cols {
+        return format!("{line}{}", " ".repeat(cols - width));
+    }
+    let ellipsis_width = text::width(ellipsis);
+    match cols.checked_sub(ellipsis_width) {
+        Some(kept) => text::slice_columns(line, 0, kept) + ellipsis,
+        None => text::slice_columns(ellipsis, 0, cols),
+    }
+}
 
-    length
+/// Pad each line of `text` with spaces to `cols` columns, allowing for the first line being
+/// indented by `padding` columns.
+fn fill_lines(text: &str, cols: usize, padding: usize) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(n, line)| {
+            let indent = if n == 0 { padding } else { 0 };
+            let fill = cols.saturating_sub(indent + text::width(line));
+            format!("{line}{}", " ".repeat(fill))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The number of columns a string takes up excluding the ANSI codes, with wide characters such
+/// as CJK and most emoji counting as two.
+fn visible_length(input: &str) -> usize {
+    text::width(input)
 }
 
 #[cfg(test)]
@@ -406,4 +1218,344 @@ mod tests {
         let result = visible_length(input);
         assert_eq!(result, 13);
     }
+
+    #[test]
+    fn test_wide_characters() {
+        assert_eq!(visible_length("你好世界"), 8);
+        assert_eq!(visible_length("\x1b[1m👍\x1b[0m"), 2);
+    }
+
+    #[test]
+    fn test_nested_style_reapplies_outer() {
+        let inner = Style::new().red().render("inner");
+        let result = Style::new().bold().render(format!("outer {inner} outer"));
+        assert_eq!(
+            result,
+            "\x1b[1mouter \x1b[91minner\x1b[0m\x1b[1m outer\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_render_in_center() {
+        let result = Style::new().center().render_in("0123456789", 20);
+        assert_eq!(result, "     0123456789\x1b[0m");
+    }
+
+    #[test]
+    fn test_render_in_right() {
+        let result = Style::new().right().render_in("0123456789", 15);
+        assert_eq!(result, "     0123456789\x1b[0m");
+    }
+
+    #[test]
+    fn test_render_without_italic_capability() {
+        let style = Style::new().bold().italic();
+        let capabilities = Capabilities {
+            italic: false,
+            ..Capabilities::all()
+        };
+        assert_eq!(
+            style.render_with("text", || 0, capabilities),
+            "\x1b[1mtext\x1b[0m"
+        );
+        assert_eq!(
+            style.render_with("text", || 0, Capabilities::all()),
+            "\x1b[1m\x1b[3mtext\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_unstyled_render_leaves_inner_resets() {
+        let inner = Style::new().red().render("inner");
+        let result = Style::new().render(format!("outer {inner} outer"));
+        assert_eq!(result, "outer \x1b[91minner\x1b[0m outer\x1b[0m");
+    }
+
+    #[test]
+    fn test_fg_reset_keeps_bold() {
+        let style = Style::new().bold().fg(Color::Reset);
+        assert_eq!(
+            style.render_with("text", || 0, Capabilities::all()),
+            "\x1b[1m\x1b[39mtext\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_render_open_has_no_reset() {
+        let style = Style::new().bold();
+        assert_eq!(
+            style.render_open_with("text", || 0, Capabilities::all()),
+            "\x1b[1mtext"
+        );
+    }
+
+    #[test]
+    fn test_color_resets_keep_bold() {
+        let style = Style::new().bold().bg(Color::Reset);
+        assert_eq!(
+            style.render_with("text", || 0, Capabilities::all()),
+            "\x1b[1m\x1b[49mtext\x1b[0m"
+        );
+        let style = Style::new().bold().underline_color(Color::Reset);
+        assert_eq!(
+            style.render_with("text", || 0, Capabilities::all()),
+            "\x1b[1m\x1b[4m\x1b[59mtext\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_adaptive_color_follows_background() {
+        let style = Style::new()
+            .adaptive_fg(Color::Black, Color::White)
+            .adaptive_bg(Color::Grey, Color::DarkGrey);
+        let mut light = String::new();
+        style.write_codes(&mut light, Capabilities::all(), false);
+        let mut dark = String::new();
+        style.write_codes(&mut dark, Capabilities::all(), true);
+        assert_eq!(light, "\x1b[30m\x1b[47m");
+        assert_eq!(dark, "\x1b[97m\x1b[100m");
+    }
+
+    #[test]
+    fn test_fill_width_within_region() {
+        let style = Style::new().bg(Color::Blue).fill_width();
+        let row = style.render_with("ab\nc", || 4, Capabilities::all());
+        assert_eq!(row, "\x1b[104mab  \nc   \x1b[0m");
+
+        let centered = style
+            .clone()
+            .center()
+            .render_with("ab", || 6, Capabilities::all());
+        assert_eq!(centered, "\x1b[104m  ab  \x1b[0m");
+        let unknown = style.render_with("ab", || 0, Capabilities::all());
+        assert_eq!(unknown, "\x1b[104mab\x1b[0m");
+    }
+
+    #[test]
+    fn test_vertical_bg_gradient() {
+        let from = Color::Rgb { r: 0, g: 0, b: 0 };
+        let to = Color::Rgb {
+            r: 200,
+            g: 100,
+            b: 0,
+        };
+        let style = Style::new().bg_gradient(from, to, GradientDirection::Vertical);
+        let block = style.render_with("ab\nc\nd", || 0, Capabilities::all());
+        let lines: Vec<_> = block.split('\n').collect();
+        assert_eq!(lines[0], "\x1b[48;2;0;0;0mab");
+        assert_eq!(lines[1], "\x1b[48;2;100;50;0mc ");
+        assert_eq!(lines[2], "\x1b[48;2;200;100;0md \x1b[0m");
+
+        let capabilities = Capabilities {
+            truecolor: false,
+            ..Capabilities::all()
+        };
+        let solid = style.render_with("ab\nc", || 0, capabilities);
+        assert_eq!(solid, "\x1b[48;2;0;0;0mab\nc \x1b[0m");
+    }
+
+    #[test]
+    fn test_horizontal_bg_gradient_fills_width() {
+        let from = Color::Rgb { r: 0, g: 0, b: 0 };
+        let to = Color::Rgb { r: 0, g: 0, b: 30 };
+        let style = Style::new().bg_gradient(from, to, GradientDirection::Horizontal);
+        let row = style.render_with("a", || 4, Capabilities::all());
+        assert_eq!(text::width(&row), 4);
+        assert!(row.ends_with("\x1b[48;2;0;0;30m \x1b[0m"));
+    }
+
+    #[test]
+    fn test_align_text_wider_than_cols() {
+        let text = "0123456789".repeat(4);
+        let centered = Style::new()
+            .center()
+            .render_with(&text, || 10, Capabilities::all());
+        assert_eq!(centered, format!("{text}\x1b[0m"));
+        let right = Style::new()
+            .right()
+            .render_with(&text, || 10, Capabilities::all());
+        assert_eq!(right, format!("{text}\x1b[0m"));
+    }
+
+    #[test]
+    fn test_padding_all() {
+        let style = Style::new().bg(Color::Blue).padding_all(1);
+        let block = style.render_with("ab", || 0, Capabilities::all());
+        assert_eq!(block, "\x1b[104m    \n ab \n    \x1b[0m");
+
+        let style = Style::new().padding_x(2).padding_y(1).right();
+        let block = style.render_with("ab", || 8, Capabilities::all());
+        assert_eq!(block, "        \n  ab  \n      \x1b[0m");
+    }
+
+    #[test]
+    fn test_rounded_border() {
+        let style = Style::new().red().border(BorderStyle::Rounded);
+        let boxed = style.render_with("hi\nthere", || 0, Capabilities::all());
+        assert!(boxed.starts_with("\x1b[91m╭"));
+        let lines: Vec<_> = boxed.split('\n').collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|line| text::width(line) == 7));
+        assert_eq!(text::strip_escapes(lines[1]), "│hi   │");
+    }
+
+    #[test]
+    fn test_width_exact_fit() {
+        let style = Style::new().width(5);
+        assert_eq!(
+            style.render_with("hello", || 0, Capabilities::all()),
+            "hello\x1b[0m"
+        );
+        assert_eq!(
+            style.render_with("世界a", || 0, Capabilities::all()),
+            "世界a\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_width_pads_short_text() {
+        let style = Style::new().width(5);
+        let cell = style.render_with("\x1b[1mhi\x1b[0m\na", || 0, Capabilities::all());
+        assert_eq!(cell, "\x1b[1mhi\x1b[0m   \na    \x1b[0m");
+    }
+
+    #[test]
+    fn test_width_truncates_long_text() {
+        let style = Style::new().width(5);
+        let cell = style.render_with("\x1b[1mhello world\x1b[0m", || 0, Capabilities::all());
+        assert_eq!(cell, "\x1b[1mhell\x1b[0m…\x1b[0m");
+        // The wide character cut in half becomes a space.
+        let cell = style.render_with("abc世界", || 0, Capabilities::all());
+        assert_eq!(cell, "abc …\x1b[0m");
+        let style = style.truncate_with("...");
+        let cell = style.render_with("hello world", || 0, Capabilities::all());
+        assert_eq!(cell, "he...\x1b[0m");
+    }
+
+    #[test]
+    fn test_wrap_breaks_long_words() {
+        let style = Style::new().wrap(4);
+        let text = style.render_with("ab abcdefgh", || 0, Capabilities::all());
+        assert_eq!(text, "ab\nabcd\nefgh\x1b[0m");
+    }
+
+    #[test]
+    fn test_wrap_drops_spaces_at_breaks() {
+        let style = Style::new().wrap(5);
+        let text = style.render_with("one two   ", || 0, Capabilities::all());
+        assert_eq!(text, "one\ntwo\x1b[0m");
+        let text = style.render_with("one ", || 0, Capabilities::all());
+        assert_eq!(text, "one \x1b[0m");
+        let text = style.render_with("one   two", || 0, Capabilities::all());
+        assert_eq!(text, "one\ntwo\x1b[0m");
+    }
+
+    #[test]
+    fn test_wrap_keeps_colors() {
+        let style = Style::new().bold().wrap(5);
+        let text = style.render_with("a \x1b[91mred word\x1b[0m b", || 0, Capabilities::all());
+        assert_eq!(
+            text,
+            "\x1b[1ma \x1b[91mred\x1b[0m\x1b[1m\n\x1b[91mword\x1b[0m\x1b[1m\nb\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_left_aligned_render_never_reads_width() {
+        let style = Style::new().bold().padding_x(1);
+        let no_width = || -> usize { panic!("the width was read") };
+        assert_eq!(
+            style.render_with("text", no_width, Capabilities::all()),
+            "\x1b[1m text \x1b[0m"
+        );
+        // Nothing here is a terminal while testing.
+        assert_eq!(Style::new().right().render_in("ab", 4), "  ab\x1b[0m");
+    }
+
+    #[test]
+    fn test_overline() {
+        let style = Style::new().overline();
+        assert_eq!(
+            style.render_with("text", || 0, Capabilities::all()),
+            "\x1b[53mtext\x1b[0m"
+        );
+        let plain = Style::new().render_with("text", || 0, Capabilities::all());
+        assert!(!plain.contains("\x1b[53m"));
+    }
+
+    #[test]
+    fn test_merge_keeps_unset_fields() {
+        let base = Style::new().red().bold().center();
+        let merged = base.clone().merge(&Style::new().italic());
+        assert_eq!(merged, Style::new().red().bold().center().italic());
+        assert_eq!(
+            merged.render_with("a", || 0, Capabilities::all()),
+            "\x1b[1m\x1b[3m\x1b[91ma\x1b[0m"
+        );
+
+        let merged = base.merge(&Style::new().blue().left());
+        assert_eq!(merged, Style::new().bold().blue().left());
+    }
+
+    #[test]
+    fn test_merge_explicit_defaults_win() {
+        let base = Style::new().bold().italic().center().padding_all(1);
+        let merged = base.merge(&Style::new().no_bold().padding_x(0));
+        assert_eq!(merged, Style::new().italic().center().padding(1, 0, 1, 0));
+        assert_eq!(Style::new().no_bold(), Style::new());
+        assert_eq!(Style::new().left(), Style::new());
+    }
+
+    #[test]
+    fn test_no_color_keeps_modifiers() {
+        let style = Style::new()
+            .bold()
+            .underline()
+            .red()
+            .bg(Color::Blue)
+            .underline_green();
+        let capabilities = Capabilities {
+            color: false,
+            ..Capabilities::all()
+        };
+        assert_eq!(
+            style.render_with("text", || 0, capabilities),
+            "\x1b[1m\x1b[4mtext\x1b[0m"
+        );
+        assert_eq!(
+            style.render_with("text", || 0, Capabilities::all()),
+            "\x1b[1m\x1b[4m\x1b[91m\x1b[104m\x1b[58;5;10mtext\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_render_in_zero_width_is_left_aligned() {
+        assert_eq!(Style::new().center().render_in("text", 0), "text\x1b[0m");
+        assert_eq!(Style::new().right().render_in("text", 0), "text\x1b[0m");
+    }
+
+    #[test]
+    fn test_fade_end_without_dim_capability() {
+        let style = Style::new().fade_end(4);
+        let capabilities = Capabilities {
+            dim: false,
+            ..Capabilities::all()
+        };
+        assert_eq!(
+            style.render_with("status\nok", || 0, capabilities),
+            "stat\nok\x1b[0m"
+        );
+        assert_eq!(
+            style.render_with("status\nok", || 0, Capabilities::all()),
+            "s\x1b[2mtat\x1b[0m\nok\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_render_measured_matches_text() {
+        let style = Style::new().bold().center();
+        let (text, size) = style.render_measured("\x1b[3m世界\x1b[0m\nlonger line", 20);
+        let width = text.split('\n').map(text::width).max().unwrap();
+        assert_eq!(size, (width, text.split('\n').count()));
+    }
 }
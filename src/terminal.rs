@@ -0,0 +1,458 @@
+use crate::CursorShape;
+use crossterm::{
+    cursor::{self, Show},
+    event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture},
+    execute,
+    style::Print,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{
+    io::{self, Write},
+    panic::PanicHookInfo,
+    sync::{
+        atomic::{AtomicBool, AtomicU16, Ordering},
+        Arc, Mutex,
+    },
+};
+
+type PanicHook = Arc<dyn Fn(&PanicHookInfo<'_>) + Send + Sync + 'static>;
+
+/// The terminal state shared by every [`App`](crate::App) in the process.
+struct State {
+    /// The number of apps currently running.
+    depth: usize,
+    /// The panic hook that was set before ours, to be put back once no apps are running.
+    previous_hook: Option<PanicHook>,
+    /// Whether to print a newline once the terminal is restored.
+    trailing_newline: bool,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    depth: 0,
+    previous_hook: None,
+    trailing_newline: false,
+});
+
+/// Whether the running app is drawn inline rather than on the alternate screen.
+static INLINE: AtomicBool = AtomicBool::new(false);
+
+/// Whether the running app reads mouse input.
+static MOUSE_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+/// The maximum width of the running app from [`App::with_max_width`](crate::App::with_max_width),
+/// or `0` for no maximum.
+static STAGE_WIDTH: AtomicU16 = AtomicU16::new(0);
+
+/// The column and row of the terminal the running app's view starts at, updated every frame.
+static VIEW_COLUMN: AtomicU16 = AtomicU16::new(0);
+static VIEW_ROW: AtomicU16 = AtomicU16::new(0);
+
+/// Get the size of the terminal as `(columns, rows)`.
+///
+/// While an app with a maximum width set by
+/// [`App::with_max_width`](crate::App::with_max_width) is running, the columns are at most that
+/// width since that is all the app is drawn in.
+pub fn terminal_size() -> io::Result<(u16, u16)> {
+    let (cols, rows) = crossterm::terminal::size()?;
+    Ok((stage_width(cols), rows))
+}
+
+/// The number of columns the running app can use on a terminal `cols` wide.
+pub(crate) fn stage_width(cols: u16) -> u16 {
+    match STAGE_WIDTH.load(Ordering::Relaxed) {
+        0 => cols,
+        max => cols.min(max),
+    }
+}
+
+/// The number of columns to indent the running app by to center it on a terminal `cols` wide.
+pub(crate) fn stage_offset(cols: u16) -> usize {
+    (cols - stage_width(cols)) as usize / 2
+}
+
+/// The column and row of the terminal the running app's view starts at, as of the last frame.
+pub(crate) fn view_origin() -> (u16, u16) {
+    (
+        VIEW_COLUMN.load(Ordering::Relaxed),
+        VIEW_ROW.load(Ordering::Relaxed),
+    )
+}
+
+/// Set where the running app's view starts once a frame has been drawn.
+pub(crate) fn set_view_origin((column, row): (u16, u16)) {
+    VIEW_COLUMN.store(column, Ordering::Relaxed);
+    VIEW_ROW.store(row, Ordering::Relaxed);
+}
+
+/// Sets the maximum width of the app while it is running, putting back the previous one along
+/// with where the previous app's view starts when dropped.
+pub(crate) struct StageGuard {
+    width: u16,
+    origin: (u16, u16),
+}
+
+impl StageGuard {
+    pub(crate) fn new(max_width: Option<u16>) -> Self {
+        let width = max_width.map_or(0, |width| width.max(1));
+        let origin = view_origin();
+        set_view_origin((0, 0));
+        Self {
+            width: STAGE_WIDTH.swap(width, Ordering::Relaxed),
+            origin,
+        }
+    }
+}
+
+impl Drop for StageGuard {
+    fn drop(&mut self) {
+        STAGE_WIDTH.store(self.width, Ordering::Relaxed);
+        set_view_origin(self.origin);
+    }
+}
+
+/// Puts the terminal in to the state needed by the [`App`](crate::App), restoring it when
+/// dropped.
+///
+/// Because this is restored on drop the terminal is also restored when a panic unwinds through
+/// [`App::run`](crate::App::run), even if the panic hook is not installed.
+///
+/// Guards can be nested, such as when an app is run from within another app's update, in which
+/// case only the outermost guard sets up and restores the terminal.
+///
+/// The alternate screen isn't entered by the guard, it is left to the first frame so the screen
+/// never shows as cleared before it is drawn, see [`TerminalGuard::take_alternate_screen`].
+pub(crate) struct TerminalGuard {
+    modes: Arc<dyn Modes>,
+    enter_alternate_screen: bool,
+}
+
+impl TerminalGuard {
+    pub(crate) fn new(
+        panic_hook: bool,
+        trailing_newline: bool,
+        inline: bool,
+        mouse_capture: bool,
+    ) -> io::Result<Self> {
+        Self::with_modes(
+            Arc::new(TerminalModes),
+            panic_hook,
+            trailing_newline,
+            inline,
+            mouse_capture,
+        )
+    }
+
+    fn with_modes(
+        modes: Arc<dyn Modes>,
+        panic_hook: bool,
+        trailing_newline: bool,
+        inline: bool,
+        mouse_capture: bool,
+    ) -> io::Result<Self> {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        let outermost = state.enter();
+        if outermost {
+            MOUSE_CAPTURE.store(mouse_capture, Ordering::Relaxed);
+            INLINE.store(inline, Ordering::Relaxed);
+            if let Err(e) = modes.set_up() {
+                state.leave();
+                return Err(e);
+            }
+            if panic_hook {
+                state.previous_hook = Some(set_panic_hook(modes.clone()));
+            }
+            state.trailing_newline = trailing_newline;
+        }
+        Ok(Self {
+            modes,
+            enter_alternate_screen: outermost && !inline,
+        })
+    }
+
+    /// Whether the alternate screen still needs to be entered, which the caller must then do.
+    pub(crate) fn take_alternate_screen(&mut self) -> bool {
+        std::mem::take(&mut self.enter_alternate_screen)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        if !state.leave() {
+            return;
+        }
+
+        // The hook can't be changed while panicking, this is only reached then if the app
+        // panicked and the process will likely exit anyway.
+        if std::thread::panicking() {
+            self.modes.restore();
+            return;
+        }
+
+        let _ = self.modes.restore_to_clean_line(state.trailing_newline);
+        if let Some(hook) = state.previous_hook.take() {
+            std::panic::set_hook(Box::new(move |info| hook(info)));
+        }
+    }
+}
+
+impl State {
+    /// Record an app starting, returning whether it is the first.
+    fn enter(&mut self) -> bool {
+        self.depth += 1;
+        self.depth == 1
+    }
+
+    /// Record an app stopping, returning whether it was the last.
+    fn leave(&mut self) -> bool {
+        self.depth -= 1;
+        self.depth == 0
+    }
+}
+
+/// Changes the modes of the terminal for a [`TerminalGuard`].
+trait Modes: Send + Sync + 'static {
+    /// Turn on raw mode along with the input the app reads.
+    fn set_up(&self) -> io::Result<()>;
+    /// Put the terminal back in to its normal state, as quickly as possible.
+    fn restore(&self);
+    /// Put the terminal back in to its normal state, see [`restore_to_clean_line`].
+    fn restore_to_clean_line(&self, trailing_newline: bool) -> io::Result<()>;
+}
+
+/// The modes of the terminal the app is drawn to.
+struct TerminalModes;
+
+impl Modes for TerminalModes {
+    fn set_up(&self) -> io::Result<()> {
+        enable_raw_mode()?;
+        set_bracketed_paste(true);
+        set_mouse_capture(true);
+        let _ = execute!(io::stdout(), EnableFocusChange);
+        Ok(())
+    }
+
+    fn restore(&self) {
+        restore();
+    }
+
+    fn restore_to_clean_line(&self, trailing_newline: bool) -> io::Result<()> {
+        restore_to_clean_line(trailing_newline)
+    }
+}
+
+/// Put the terminal back in to its normal state while another process uses it, call
+/// [`unsuspend`] to return to the app's state.
+pub(crate) fn suspend() -> io::Result<()> {
+    set_bracketed_paste(false);
+    set_mouse_capture(false);
+    leave_alternate_screen()?;
+    execute!(io::stdout(), Show, DisableFocusChange)?;
+    disable_raw_mode()
+}
+
+/// Return the terminal to the app's state after [`suspend`].
+pub(crate) fn unsuspend() -> io::Result<()> {
+    enable_raw_mode()?;
+    if !INLINE.load(Ordering::Relaxed) {
+        execute!(io::stdout(), EnterAlternateScreen)?;
+    }
+    set_bracketed_paste(true);
+    set_mouse_capture(true);
+    execute!(io::stdout(), EnableFocusChange)
+}
+
+/// Turn bracketed paste on or off if the `paste` feature is enabled, so pasted text is read as a
+/// single event instead of a key for each character.
+///
+/// Terminals that don't support it ignore this.
+fn set_bracketed_paste(enabled: bool) {
+    #[cfg(feature = "paste")]
+    {
+        use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
+        let _ = if enabled {
+            execute!(io::stdout(), EnableBracketedPaste)
+        } else {
+            execute!(io::stdout(), DisableBracketedPaste)
+        };
+    }
+    #[cfg(not(feature = "paste"))]
+    let _ = enabled;
+}
+
+/// Turn reporting mouse input on or off, if the running app reads it.
+fn set_mouse_capture(enabled: bool) {
+    if !MOUSE_CAPTURE.load(Ordering::Relaxed) {
+        return;
+    }
+    let _ = match enabled {
+        true => execute!(io::stdout(), EnableMouseCapture),
+        false => execute!(io::stdout(), DisableMouseCapture),
+    };
+}
+
+/// Leave the alternate screen unless the app is drawn inline.
+///
+/// Leaving when not on the alternate screen also restores a saved cursor position in many
+/// terminals, which would move the cursor away from an inline app.
+fn leave_alternate_screen() -> io::Result<()> {
+    if INLINE.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    execute!(io::stdout(), LeaveAlternateScreen)
+}
+
+/// Put the terminal back in to its normal state.
+fn restore() {
+    set_bracketed_paste(false);
+    set_mouse_capture(false);
+    let _ = disable_raw_mode();
+    let _ = leave_alternate_screen();
+    let _ = execute!(
+        io::stdout(),
+        Show,
+        DisableFocusChange,
+        Print(CursorShape::Default.sequence())
+    );
+}
+
+/// Put the terminal back in to its normal state, making sure the cursor is at the start of a line
+/// so the shell's prompt is printed cleanly.
+fn restore_to_clean_line(trailing_newline: bool) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    set_bracketed_paste(false);
+    set_mouse_capture(false);
+    let result = (|| {
+        leave_alternate_screen()?;
+        execute!(
+            stdout,
+            Show,
+            DisableFocusChange,
+            Print(CursorShape::Default.sequence())
+        )?;
+        // This must be done while still in raw mode to read the terminal's reply.
+        if cursor::position()?.0 != 0 {
+            write!(stdout, "\r\n")?;
+        }
+        if trailing_newline {
+            write!(stdout, "\r\n")?;
+        }
+        stdout.flush()
+    })();
+    disable_raw_mode()?;
+    result
+}
+
+/// Wrap the current panic hook so the terminal is restored before the panic message is printed,
+/// returning the hook that was wrapped.
+fn set_panic_hook(modes: Arc<dyn Modes>) -> PanicHook {
+    let hook: PanicHook = Arc::from(std::panic::take_hook());
+    let previous = hook.clone();
+    std::panic::set_hook(Box::new(move |info| {
+        modes.restore();
+        hook(info);
+    }));
+    previous
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_sequential_apps_each_set_up() {
+        let mut state = State {
+            depth: 0,
+            previous_hook: None,
+            trailing_newline: false,
+        };
+
+        assert!(state.enter());
+        assert!(state.leave());
+        assert!(state.enter());
+        assert!(state.leave());
+        assert_eq!(state.depth, 0);
+    }
+
+    #[test]
+    fn test_nested_apps_only_set_up_once() {
+        let mut state = State {
+            depth: 0,
+            previous_hook: None,
+            trailing_newline: false,
+        };
+
+        assert!(state.enter());
+        assert!(!state.enter());
+        assert!(!state.leave());
+        assert_eq!(state.depth, 1);
+        assert!(state.leave());
+    }
+
+    #[derive(Default)]
+    struct FakeModes {
+        raw: AtomicBool,
+        restores: AtomicUsize,
+    }
+
+    impl Modes for FakeModes {
+        fn set_up(&self) -> io::Result<()> {
+            assert!(!self.raw.swap(true, Ordering::Relaxed), "already set up");
+            Ok(())
+        }
+
+        fn restore(&self) {
+            self.raw.store(false, Ordering::Relaxed);
+            self.restores.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn restore_to_clean_line(&self, _: bool) -> io::Result<()> {
+            self.restore();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sequential_apps_restore_terminal() {
+        static HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+        std::panic::set_hook(Box::new(|_| {
+            HOOK_CALLS.fetch_add(1, Ordering::Relaxed);
+        }));
+
+        let modes = Arc::new(FakeModes::default());
+        for n in 1..=2 {
+            let guard = TerminalGuard::with_modes(modes.clone(), true, false, true, false).unwrap();
+            assert!(modes.raw.load(Ordering::Relaxed));
+
+            // The app's hook restores the terminal before calling the one it wrapped.
+            let _ = std::panic::catch_unwind(|| panic!("app {n}"));
+            assert_eq!(HOOK_CALLS.load(Ordering::Relaxed), n);
+            assert_eq!(modes.restores.load(Ordering::Relaxed), n * 2 - 1);
+            assert!(!modes.raw.load(Ordering::Relaxed));
+
+            drop(guard);
+            assert_eq!(modes.restores.load(Ordering::Relaxed), n * 2);
+            let state = STATE.lock().unwrap();
+            assert_eq!(state.depth, 0);
+            assert!(state.previous_hook.is_none());
+        }
+
+        // Only the hook from before the apps is left.
+        let _ = std::panic::catch_unwind(|| panic!("after the apps"));
+        assert_eq!(HOOK_CALLS.load(Ordering::Relaxed), 3);
+        assert_eq!(modes.restores.load(Ordering::Relaxed), 4);
+        drop(std::panic::take_hook());
+    }
+
+    #[test]
+    fn test_stage_width() {
+        let stage = StageGuard::new(Some(80));
+        assert_eq!(stage_width(120), 80);
+        assert_eq!(stage_offset(120), 20);
+        assert_eq!(stage_width(60), 60);
+        assert_eq!(stage_offset(60), 0);
+        drop(stage);
+        assert_eq!(stage_width(120), 120);
+    }
+}
@@ -0,0 +1,815 @@
+use unicode_width::UnicodeWidthChar;
+
+/// The ANSI code to reset all styling.
+pub(crate) const RESET: &str = "\x1b[0m";
+
+/// Word wrap text to fit within `width` columns.
+///
+/// Text is broken on spaces, and words longer than `width` are broken wherever they need to be.
+/// Explicit newlines are kept and spaces where a line is broken are removed. ANSI escape codes
+/// don't count towards the width and any styling active at the end of a line is reset and then
+/// reopened at the start of the next, so each line can be used on its own. Wide characters such
+/// as CJK and emoji count as two columns.
+///
+/// ```
+/// # use sketch::wrap_text;
+/// let lines = wrap_text("the quick brown fox", 10);
+/// assert_eq!(lines, ["the quick", "brown fox"]);
+/// ```
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Lines::default();
+
+    for (n, line) in text.split('\n').enumerate() {
+        if n > 0 {
+            lines.newline();
+        }
+
+        let mut word = Vec::new();
+        let mut word_width = 0;
+        let mut spaces = String::new();
+
+        for token in tokens(line) {
+            match token {
+                Token::Char(c) if c.is_whitespace() => {
+                    if word_width > 0 {
+                        lines.push_word(&spaces, &word, word_width, width);
+                        word.clear();
+                        word_width = 0;
+                        spaces.clear();
+                    }
+                    spaces.push(c);
+                }
+                Token::Char(c) => {
+                    word_width += char_width(c);
+                    word.push(token);
+                }
+                Token::Escape(_) => word.push(token),
+            }
+        }
+
+        lines.push_word(&spaces, &word, word_width, width);
+    }
+
+    lines.finish()
+}
+
+/// Rewrap text that was previously wrapped to a different width.
+///
+/// Consecutive non-empty lines are joined with a space in to paragraphs which are then wrapped to
+/// `width` with [`wrap_text`]. Empty lines (or lines of only spaces) separate paragraphs and are
+/// kept as they are, so use them for line breaks that should survive a reflow. The indentation
+/// of the first line in a paragraph is kept.
+///
+/// ```
+/// # use sketch::reflow;
+/// let text = "the quick\nbrown fox\n\njumps";
+/// assert_eq!(reflow(text, 20), ["the quick brown fox", "", "jumps"]);
+/// ```
+pub fn reflow(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut paragraph = String::new();
+
+    for line in text.split('\n') {
+        if line.trim().is_empty() {
+            if !paragraph.is_empty() {
+                lines.extend(wrap_text(&paragraph, width));
+                paragraph.clear();
+            }
+            lines.push(String::new());
+        } else if paragraph.is_empty() {
+            paragraph.push_str(line.trim_end());
+        } else {
+            paragraph.push(' ');
+            paragraph.push_str(line.trim());
+        }
+    }
+
+    if !paragraph.is_empty() {
+        lines.extend(wrap_text(&paragraph, width));
+    }
+
+    lines
+}
+
+/// Split text in to pages of `page_height` lines, such as for printing or exporting to a file.
+///
+/// The `header` and `footer` are added to the top and bottom of every page and count towards
+/// its height, leaving at least one line for the content. The last page is padded with empty
+/// lines so every page is the same height. Styling active at the end of a page is reset and then
+/// reopened at the start of the next, so each page can be used on its own.
+///
+/// ```
+/// # use sketch::paginate;
+/// let pages = paginate("a\nb\nc", 3, Some("Report"), None);
+/// assert_eq!(pages, ["Report\na\nb", "Report\nc\n"]);
+/// ```
+pub fn paginate(
+    content: &str,
+    page_height: usize,
+    header: Option<&str>,
+    footer: Option<&str>,
+) -> Vec<String> {
+    let lines_in = |text: Option<&str>| text.map_or(0, |text| text.split('\n').count());
+    let body_height = page_height
+        .saturating_sub(lines_in(header) + lines_in(footer))
+        .max(1);
+
+    let lines: Vec<&str> = content.split('\n').collect();
+    let mut pages = Vec::new();
+    let mut active = String::new();
+
+    for body in lines.chunks(body_height) {
+        let mut page = String::new();
+        if let Some(header) = header {
+            page.push_str(header);
+            page.push('\n');
+        }
+
+        page.push_str(&active);
+        for (n, line) in body.iter().enumerate() {
+            if n > 0 {
+                page.push('\n');
+            }
+            page.push_str(line);
+            for token in tokens(line) {
+                if let Token::Escape(code) = token {
+                    track_style(&mut active, code);
+                }
+            }
+        }
+        if !active.is_empty() {
+            page.push_str(RESET);
+        }
+        page.extend(std::iter::repeat_n('\n', body_height - body.len()));
+
+        if let Some(footer) = footer {
+            page.push('\n');
+            page.push_str(footer);
+        }
+        pages.push(page);
+    }
+
+    pages
+}
+
+/// Reverse the visible characters of each line, such as for mirrored or right to left layouts.
+///
+/// Each character keeps its styling, and clusters of characters shown together, such as flags,
+/// accented letters and emoji with skin tones or zero width joiners, are kept together.
+///
+/// ```
+/// # use sketch::mirror;
+/// assert_eq!(mirror("abc\n12"), "cba\n21");
+/// assert_eq!(mirror("\x1b[1mab\x1b[0mc"), "c\x1b[1mba\x1b[0m");
+/// ```
+pub fn mirror(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut active = String::new();
+
+    for (n, line) in text.split('\n').enumerate() {
+        if n > 0 {
+            result.push('\n');
+        }
+
+        // Where each cluster starts in the line without its escape codes.
+        let visible = strip_escapes(line);
+        let mut starts = clusters(&visible).map(|(n, _)| n).peekable();
+        let mut position = 0;
+
+        // Each cluster of characters that are shown together, with the styling active for it.
+        let mut groups: Vec<(String, String)> = Vec::new();
+        let mut pending = String::new();
+        for token in tokens(line) {
+            match token {
+                Token::Escape(code) if code.ends_with('m') => track_style(&mut active, code),
+                // Other escape codes, such as links, stay in front of the next character.
+                Token::Escape(code) => pending.push_str(code),
+                Token::Char(c) => {
+                    pending.push(c);
+                    match (starts.next_if_eq(&position), groups.last_mut()) {
+                        (None, Some((cluster, _))) => cluster.push_str(&pending),
+                        _ => groups.push((pending.clone(), active.clone())),
+                    }
+                    pending.clear();
+                    position += c.len_utf8();
+                }
+            }
+        }
+
+        let mut written = String::new();
+        for (cluster, style) in groups.iter().rev() {
+            if *style != written {
+                if !written.is_empty() {
+                    result.push_str(RESET);
+                }
+                result.push_str(style);
+                written.clone_from(style);
+            }
+            result.push_str(cluster);
+        }
+        if !written.is_empty() {
+            result.push_str(RESET);
+        }
+        result.push_str(&pending);
+    }
+
+    result
+}
+
+/// Does `c` join on to the character before it, such as an accent or a skin tone.
+fn is_joining(c: char, previous: Option<char>) -> bool {
+    let is_modifier = ('\u{1f3fb}'..='\u{1f3ff}').contains(&c);
+    match previous {
+        None | Some('\n') => false,
+        Some(p) => !c.is_control() && (char_width(c) == 0 || is_modifier || p == '\u{200d}'),
+    }
+}
+
+/// Is `c` one of the pair of letters that make up a flag.
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1f1e6}'..='\u{1f1ff}').contains(&c)
+}
+
+/// The start of the cluster before `position`.
+pub(crate) fn prev_boundary(text: &str, position: usize) -> usize {
+    // Clusters such as flags can only be found from their start, so go forward from the start of
+    // the line rather than back from the position.
+    let line_start = text[..position].rfind('\n').map_or(0, |n| n + 1);
+    let mut start = line_start;
+    loop {
+        let end = next_boundary(text, start);
+        if end >= position {
+            return match start == position {
+                // The position is just after a newline.
+                true => text[..position]
+                    .char_indices()
+                    .next_back()
+                    .map_or(0, |(n, _)| n),
+                false => start,
+            };
+        }
+        start = end;
+    }
+}
+
+/// The end of the cluster after `position`.
+pub(crate) fn next_boundary(text: &str, position: usize) -> usize {
+    let mut chars = text[position..].char_indices();
+    let Some((_, first)) = chars.next() else {
+        return position;
+    };
+    let mut previous = first;
+    let mut flag = is_regional_indicator(first);
+    for (n, c) in chars {
+        let pairs_flag = flag && is_regional_indicator(c);
+        if !pairs_flag && !is_joining(c, Some(previous)) {
+            return position + n;
+        }
+        flag = false;
+        previous = c;
+    }
+    text.len()
+}
+
+/// Split `text` into clusters of characters shown together, with their byte offsets.
+pub(crate) fn clusters(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut position = 0;
+    std::iter::from_fn(move || {
+        if position == text.len() {
+            return None;
+        }
+        let start = position;
+        position = next_boundary(text, start);
+        Some((start, &text[start..position]))
+    })
+}
+
+/// Update `active`, the styling codes used since the last reset, with an escape code.
+pub(crate) fn track_style(active: &mut String, code: &str) {
+    if is_reset(code) {
+        active.clear();
+    } else if code.ends_with('m') {
+        active.push_str(code);
+    }
+}
+
+/// Builds the lines for [`wrap_text`] keeping track of the active styling.
+#[derive(Default)]
+struct Lines {
+    lines: Vec<String>,
+    line: String,
+    line_width: usize,
+    active: String,
+}
+
+impl Lines {
+    fn push_word(&mut self, spaces: &str, word: &[Token], word_width: usize, width: usize) {
+        let spaces_width = spaces.chars().map(char_width).sum::<usize>();
+
+        let fits = self.line_width + spaces_width + word_width <= width;
+        if fits || self.line_width == 0 {
+            self.line.push_str(spaces);
+            self.line_width += spaces_width;
+        } else if word_width > 0 {
+            // Spaces where the line is broken are dropped.
+            self.newline();
+        }
+
+        for token in word {
+            match *token {
+                Token::Char(c) => {
+                    let w = char_width(c);
+                    if self.line_width > 0 && self.line_width + w > width {
+                        self.newline();
+                    }
+                    self.line.push(c);
+                    self.line_width += w;
+                }
+                Token::Escape(code) => {
+                    self.line.push_str(code);
+                    track_style(&mut self.active, code);
+                }
+            }
+        }
+    }
+
+    fn newline(&mut self) {
+        if !self.active.is_empty() {
+            self.line.push_str(RESET);
+        }
+        self.lines
+            .push(std::mem::replace(&mut self.line, self.active.clone()));
+        self.line_width = 0;
+    }
+
+    fn finish(mut self) -> Vec<String> {
+        self.lines.push(self.line);
+        self.lines
+    }
+}
+
+/// Cut out the columns `start..start + width` of a line.
+///
+/// Escape codes are all kept so the styling of the cut out part is the same as in the full line.
+/// A wide character cut in half by either edge is replaced with a space for the half inside.
+pub(crate) fn slice_columns(line: &str, start: usize, width: usize) -> String {
+    let end = start + width;
+    let mut result = String::new();
+    let mut col = 0;
+
+    for token in tokens(line) {
+        match token {
+            Token::Escape(code) => result.push_str(code),
+            Token::Char(c) => {
+                let w = char_width(c);
+                if col >= start && col + w <= end {
+                    result.push(c);
+                } else if col < end && col + w > start {
+                    // Only part of a wide character is inside.
+                    let inside = (col + w).min(end) - col.max(start);
+                    result.extend(std::iter::repeat_n(' ', inside));
+                }
+                col += w;
+            }
+        }
+    }
+
+    result
+}
+
+/// The number of columns at the end of a line dimmed by [`fade_end`].
+const FADE_LENGTH: usize = 3;
+
+/// Cut a line down to `width` columns, dimming the last few columns if anything was cut off to
+/// suggest that there is more.
+///
+/// Lines that fit are returned unchanged. If `dim` is `false`, such as on terminals without dim
+/// text, the line is only cut.
+pub(crate) fn fade_end(line: &str, width: usize, dim: bool) -> String {
+    if self::width(line) <= width {
+        return line.to_string();
+    }
+
+    let fade_start = width.saturating_sub(FADE_LENGTH);
+    let mut result = String::new();
+    let mut col = 0;
+    let mut fading = false;
+
+    for token in tokens(line) {
+        match token {
+            Token::Escape(code) => {
+                result.push_str(code);
+                // Resets inside the faded part would undo the dimming.
+                if fading && col < width && (is_reset(code) || code == "\x1b[22m") {
+                    result.push_str("\x1b[2m");
+                }
+            }
+            Token::Char(c) => {
+                let w = char_width(c);
+                if dim && !fading && col + w > fade_start && col < width {
+                    result.push_str("\x1b[2m");
+                    fading = true;
+                }
+                if col + w <= width {
+                    result.push(c);
+                } else if col < width {
+                    // Only part of a wide character fits.
+                    result.extend(std::iter::repeat_n(' ', width - col));
+                }
+                col += w;
+            }
+        }
+    }
+
+    if fading {
+        result.push_str(RESET);
+    }
+    result
+}
+
+/// Style the columns `start..end` of a line for each range, such as to highlight search matches.
+///
+/// The ranges must be in order and not overlap. Each is given the escape codes to style it with,
+/// and the line's own styling is put back after it.
+pub(crate) fn highlight_columns(line: &str, ranges: &[(usize, usize, String)]) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut ranges = ranges.iter().peekable();
+    let mut current: Option<&(usize, usize, String)> = None;
+    let mut active = String::new();
+    let mut col = 0;
+
+    for token in tokens(line) {
+        match token {
+            Token::Escape(code) => {
+                result.push_str(code);
+                track_style(&mut active, code);
+                // Keep the highlight over any styling inside the range.
+                if let Some((_, _, codes)) = current {
+                    result.push_str(codes);
+                }
+            }
+            Token::Char(c) => {
+                if current.is_some_and(|&(_, end, _)| col >= end) {
+                    result.push_str(RESET);
+                    result.push_str(&active);
+                    current = None;
+                }
+                while ranges.next_if(|&&(_, end, _)| end <= col).is_some() {}
+                if current.is_none() {
+                    if let Some(range) = ranges.next_if(|&&(start, _, _)| start <= col) {
+                        result.push_str(&range.2);
+                        current = Some(range);
+                    }
+                }
+                result.push(c);
+                col += char_width(c);
+            }
+        }
+    }
+
+    if current.is_some() {
+        result.push_str(RESET);
+        result.push_str(&active);
+    }
+    result
+}
+
+/// A piece of text that is either an ANSI escape code or a single character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Token<'a> {
+    /// A complete escape code including the leading `ESC`.
+    Escape(&'a str),
+    /// A character which is not part of an escape code.
+    Char(char),
+}
+
+/// Split text in to escape codes and characters.
+pub(crate) fn tokens(text: &str) -> impl Iterator<Item = Token<'_>> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let c = rest.chars().next()?;
+        if c != '\x1b' {
+            rest = &rest[c.len_utf8()..];
+            return Some(Token::Char(c));
+        }
+
+        let len = escape_len(rest);
+        let (code, tail) = rest.split_at(len);
+        rest = tail;
+        Some(Token::Escape(code))
+    })
+}
+
+/// The length in bytes of the escape code at the start of `text`.
+fn escape_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    match bytes.get(1) {
+        // CSI, ends with a byte in the range 0x40..=0x7E.
+        Some(b'[') => bytes[2..]
+            .iter()
+            .position(|b| (0x40..=0x7E).contains(b))
+            .map_or(bytes.len(), |i| i + 3),
+        // OSC, ends with BEL or ST.
+        Some(b']') => {
+            let mut i = 2;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'\x07' => return i + 1,
+                    b'\x1b' if bytes.get(i + 1) == Some(&b'\\') => return i + 2,
+                    _ => i += 1,
+                }
+            }
+            bytes.len()
+        }
+        Some(_) => 1 + text[1..].chars().next().map_or(0, char::len_utf8),
+        None => 1,
+    }
+}
+
+/// The parameters of an SGR (styling) escape code, or `None` if it is a different escape code.
+///
+/// An empty parameter is returned as `0`.
+pub(crate) fn sgr_params(code: &str) -> Option<Vec<u16>> {
+    let params = code.strip_prefix("\x1b[")?.strip_suffix('m')?;
+    params
+        .split([';', ':'])
+        .map(|p| if p.is_empty() { Ok(0) } else { p.parse() })
+        .collect::<Result<_, _>>()
+        .ok()
+}
+
+/// Is this escape code a full reset of the styling.
+pub(crate) fn is_reset(code: &str) -> bool {
+    code == RESET || code == "\x1b[m"
+}
+
+/// Remove every escape code from text, leaving only what is shown.
+pub(crate) fn strip_escapes(text: &str) -> String {
+    tokens(text)
+        .filter_map(|token| match token {
+            Token::Char(c) => Some(c),
+            Token::Escape(_) => None,
+        })
+        .collect()
+}
+
+/// The number of columns text takes up, excluding escape codes.
+pub(crate) fn width(text: &str) -> usize {
+    tokens(text)
+        .map(|token| match token {
+            Token::Char(c) => char_width(c),
+            Token::Escape(_) => 0,
+        })
+        .sum()
+}
+
+/// The number of columns a character takes up.
+pub(crate) fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        let tokens: Vec<_> = tokens("a\x1b[1;31mb\x1b]8;;x\x07c").collect();
+        assert_eq!(
+            tokens,
+            [
+                Token::Char('a'),
+                Token::Escape("\x1b[1;31m"),
+                Token::Char('b'),
+                Token::Escape("\x1b]8;;x\x07"),
+                Token::Char('c'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_escapes() {
+        let text = "\x1b[1ma\x1b[0m \x1b]8;;https://example.com\x07link\x1b]8;;\x07";
+        assert_eq!(strip_escapes(text), "a link");
+    }
+
+    #[test]
+    fn test_sgr_params() {
+        assert_eq!(sgr_params("\x1b[m"), Some(vec![0]));
+        assert_eq!(sgr_params("\x1b[1;38;5;9m"), Some(vec![1, 38, 5, 9]));
+        assert_eq!(sgr_params("\x1b[2J"), None);
+    }
+
+    #[test]
+    fn test_slice_columns() {
+        assert_eq!(slice_columns("abcdef", 2, 3), "cde");
+        assert_eq!(slice_columns("abc", 2, 5), "c");
+        assert_eq!(slice_columns("abc", 5, 5), "");
+        assert_eq!(
+            slice_columns("\x1b[1mab\x1b[0mcd", 1, 2),
+            "\x1b[1mb\x1b[0mc"
+        );
+    }
+
+    #[test]
+    fn test_slice_columns_wide_characters() {
+        assert_eq!(slice_columns("你好世界", 2, 4), "好世");
+        assert_eq!(slice_columns("你好世界", 1, 4), " 好 ");
+        assert_eq!(slice_columns("a你", 0, 2), "a ");
+    }
+
+    #[test]
+    fn test_wrap_words() {
+        let lines = wrap_text("the quick brown fox jumps", 10);
+        assert_eq!(lines, ["the quick", "brown fox", "jumps"]);
+    }
+
+    #[test]
+    fn test_wrap_exact_fit() {
+        let lines = wrap_text("abc def", 7);
+        assert_eq!(lines, ["abc def"]);
+    }
+
+    #[test]
+    fn test_wrap_long_word() {
+        let lines = wrap_text("a abcdefghij b", 4);
+        assert_eq!(lines, ["a", "abcd", "efgh", "ij b"]);
+    }
+
+    #[test]
+    fn test_wrap_keeps_newlines() {
+        let lines = wrap_text("one two\n\nthree", 5);
+        assert_eq!(lines, ["one", "two", "", "three"]);
+    }
+
+    #[test]
+    fn test_wrap_keeps_leading_spaces() {
+        let lines = wrap_text("  indented text", 20);
+        assert_eq!(lines, ["  indented text"]);
+    }
+
+    #[test]
+    fn test_wrap_wide_characters() {
+        let lines = wrap_text("你好世界", 5);
+        assert_eq!(lines, ["你好", "世界"]);
+        let lines = wrap_text("ab 👍👍 c", 4);
+        assert_eq!(lines, ["ab", "👍👍", "c"]);
+    }
+
+    #[test]
+    fn test_wrap_mixed_width() {
+        let lines = wrap_text("a你b好c", 3);
+        assert_eq!(lines, ["a你", "b好", "c"]);
+    }
+
+    #[test]
+    fn test_wrap_ignores_escapes() {
+        let lines = wrap_text("\x1b[1mbold\x1b[0m text", 9);
+        assert_eq!(lines, ["\x1b[1mbold\x1b[0m text"]);
+    }
+
+    #[test]
+    fn test_wrap_reopens_style() {
+        let lines = wrap_text("\x1b[31mred text\x1b[0m plain", 5);
+        assert_eq!(
+            lines,
+            ["\x1b[31mred\x1b[0m", "\x1b[31mtext\x1b[0m", "plain"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_drops_overflowing_trailing_spaces() {
+        let lines = wrap_text("abc   ", 4);
+        assert_eq!(lines, ["abc"]);
+        let lines = wrap_text("abc ", 4);
+        assert_eq!(lines, ["abc "]);
+    }
+
+    #[test]
+    fn test_reflow_wider() {
+        let text = wrap_text("the quick brown fox jumps over the lazy dog", 10).join("\n");
+        assert_eq!(
+            reflow(&text, 20),
+            ["the quick brown fox", "jumps over the lazy", "dog"]
+        );
+    }
+
+    #[test]
+    fn test_reflow_narrower() {
+        assert_eq!(reflow("one two three", 7), ["one two", "three"]);
+    }
+
+    #[test]
+    fn test_reflow_keeps_paragraphs() {
+        let text = "first\nparagraph\n\nsecond\nparagraph";
+        assert_eq!(
+            reflow(text, 40),
+            ["first paragraph", "", "second paragraph"]
+        );
+    }
+
+    #[test]
+    fn test_reflow_keeps_blank_lines() {
+        let text = "a\n\n\n  \nb\n";
+        assert_eq!(reflow(text, 40), ["a", "", "", "", "b", ""]);
+    }
+
+    #[test]
+    fn test_reflow_keeps_first_indent() {
+        let text = "  indented\n  wrapped";
+        assert_eq!(reflow(text, 40), ["  indented wrapped"]);
+    }
+
+    #[test]
+    fn test_wrap_empty() {
+        assert_eq!(wrap_text("", 10), [""]);
+    }
+
+    #[test]
+    fn test_fade_end() {
+        assert_eq!(fade_end("short", 10, true), "short");
+        assert_eq!(fade_end("0123456789", 6, true), "012\x1b[2m345\x1b[0m");
+        assert_eq!(fade_end("0123456789", 6, false), "012345");
+    }
+
+    #[test]
+    fn test_fade_end_keeps_dim_after_reset() {
+        let line = "0123\x1b[1m4\x1b[0m56789";
+        assert_eq!(
+            fade_end(line, 6, true),
+            "012\x1b[2m3\x1b[1m4\x1b[0m\x1b[2m5\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_paginate_pads_last_page() {
+        let pages = paginate("1\n2\n3", 4, Some("head"), Some("foot"));
+        assert_eq!(pages, ["head\n1\n2\nfoot", "head\n3\n\nfoot"]);
+    }
+
+    #[test]
+    fn test_paginate_reopens_style() {
+        let pages = paginate("\x1b[1ma\nb\x1b[0m\nc", 1, None, None);
+        assert_eq!(pages, ["\x1b[1ma\x1b[0m", "\x1b[1mb\x1b[0m", "c"]);
+    }
+
+    #[test]
+    fn test_mirror_keeps_styles_with_characters() {
+        let line = "\x1b[31ma\x1b[1mb\x1b[0mc";
+        assert_eq!(mirror(line), "c\x1b[31m\x1b[1mb\x1b[0m\x1b[31ma\x1b[0m");
+    }
+
+    #[test]
+    fn test_mirror_keeps_clusters_together() {
+        assert_eq!(mirror("a世e\u{301}"), "e\u{301}世a");
+        let family = "👨\u{200d}👩\u{200d}👧";
+        assert_eq!(mirror(&format!("x{family}")), format!("{family}x"));
+    }
+
+    #[test]
+    fn test_mirror_keeps_flags_and_skin_tones() {
+        assert_eq!(mirror("🇬🇧🇫🇷"), "🇫🇷🇬🇧");
+        assert_eq!(mirror("👍🏽x"), "x👍🏽");
+        assert_eq!(mirror("\x1b[1m🇬🇧\x1b[0m🇫🇷"), "🇫🇷\x1b[1m🇬🇧\x1b[0m");
+    }
+
+    #[test]
+    fn test_boundaries_keep_clusters_together() {
+        // An e with a combining accent, then a family emoji joined with zero width joiners.
+        let text = "ae\u{301}👩\u{200d}👧!";
+        let clusters: Vec<_> = clusters(text).map(|(_, c)| c).collect();
+        assert_eq!(clusters, ["a", "e\u{301}", "👩\u{200d}👧", "!"]);
+        assert_eq!(prev_boundary(text, text.len() - 1), 4);
+        assert_eq!(prev_boundary(text, 4), 1);
+        assert_eq!(next_boundary("a\nb", 1), 2);
+        assert_eq!(prev_boundary("a\nb", 2), 1);
+        assert_eq!(prev_boundary("a\nb", 1), 0);
+    }
+
+    #[test]
+    fn test_flags_and_skin_tones_are_clusters() {
+        let text = "🇳🇿🇦🇺👍🏽";
+        let clusters: Vec<_> = clusters(text).map(|(_, c)| c).collect();
+        assert_eq!(clusters, ["🇳🇿", "🇦🇺", "👍🏽"]);
+        assert_eq!(prev_boundary(text, 16), 8);
+    }
+
+    #[test]
+    fn test_highlight_columns() {
+        let ranges = [
+            (1, 3, String::from("\x1b[7m")),
+            (5, 6, String::from("\x1b[7m")),
+        ];
+        assert_eq!(
+            highlight_columns("ab\x1b[1mcdef", &ranges),
+            "a\x1b[7mb\x1b[1m\x1b[7mc\x1b[0m\x1b[1mde\x1b[7mf\x1b[0m\x1b[1m"
+        );
+    }
+
+    #[test]
+    fn test_highlight_columns_wide_characters() {
+        let ranges = [(2, 4, String::from("\x1b[7m"))];
+        assert_eq!(highlight_columns("世界!", &ranges), "世\x1b[7m界\x1b[0m!");
+    }
+}
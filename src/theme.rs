@@ -0,0 +1,83 @@
+use crate::Style;
+use std::collections::HashMap;
+
+/// The style returned for keys which are not in a theme.
+static PLAIN: Style = Style::new();
+
+/// A set of named styles, so an app can define its styles once and switch between them at
+/// runtime.
+///
+/// The default theme has these styles, which the built in [`widgets`](crate::widgets) use when
+/// given a theme:
+///
+/// * `"title"`: Bold.
+/// * `"prompt"`: Bold.
+/// * `"selected"`: Reversed.
+/// * `"match"`: Yellow and bold.
+/// * `"muted"`: Dim.
+/// * `"error"`: Red.
+///
+/// ```
+/// # use sketch::{Style, Theme};
+/// let theme = Theme::default().with("title", Style::new().blue().bold());
+/// let title = theme.get("title").render_in("Settings", 20);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    styles: HashMap<String, Style>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new()
+            .with("title", Style::new().bold())
+            .with("prompt", Style::new().bold())
+            .with("selected", Style::new().reverse())
+            .with("match", Style::new().yellow().bold())
+            .with("muted", Style::new().dim())
+            .with("error", Style::new().red())
+    }
+}
+
+impl Theme {
+    /// Create a theme with no styles.
+    pub fn new() -> Self {
+        Self {
+            styles: HashMap::new(),
+        }
+    }
+
+    /// Add a style to the theme, replacing any with the same key.
+    pub fn with(mut self, key: impl Into<String>, style: Style) -> Self {
+        self.set(key, style);
+        self
+    }
+
+    /// Add a style to the theme, replacing any with the same key.
+    pub fn set(&mut self, key: impl Into<String>, style: Style) {
+        self.styles.insert(key.into(), style);
+    }
+
+    /// Get the style for a key, or an unstyled [`Style`] if the theme doesn't have it.
+    pub fn get(&self, key: &str) -> &Style {
+        self.styles.get(key).unwrap_or(&PLAIN)
+    }
+
+    /// Does the theme have a style for this key.
+    pub fn contains(&self, key: &str) -> bool {
+        self.styles.contains_key(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_missing_is_plain() {
+        let theme = Theme::new().with("title", Style::new().bold());
+        assert_eq!(theme.get("title"), &Style::new().bold());
+        assert_eq!(theme.get("missing"), &Style::new());
+        assert!(!theme.contains("missing"));
+    }
+}
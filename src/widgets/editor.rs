@@ -0,0 +1,223 @@
+use crate::{
+    text::{clusters, next_boundary, prev_boundary},
+    Key, KeyCode, Msg, SetClipboard, Style,
+};
+use std::ops::Range;
+
+const CURSOR_STYLE: Style = Style::new().reverse();
+const SELECTION_STYLE: Style = Style::new().reverse();
+
+/// The text, cursor and selection shared by the text editing widgets.
+///
+/// Positions are byte offsets into the text which are always on the boundary of a cluster of
+/// characters shown together, such as a letter and its accents.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Editor {
+    pub(crate) text: String,
+    pub(crate) cursor: usize,
+    anchor: Option<usize>,
+}
+
+/// What an [`Editor`] did with a key.
+pub(crate) enum Edit {
+    /// The key wasn't used.
+    Ignored,
+    /// The key was used.
+    Handled,
+    /// The key was used and this message should be sent.
+    Send(Msg),
+}
+
+impl Editor {
+    pub(crate) fn new(text: String) -> Self {
+        Self {
+            cursor: text.len(),
+            text,
+            anchor: None,
+        }
+    }
+
+    /// Replace the text, moving the cursor to the end and clearing the selection.
+    pub(crate) fn set_text(&mut self, text: String) {
+        *self = Self::new(text);
+    }
+
+    /// The selected bytes, if any are selected.
+    pub(crate) fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        match anchor.cmp(&self.cursor) {
+            std::cmp::Ordering::Less => Some(anchor..self.cursor),
+            std::cmp::Ordering::Greater => Some(self.cursor..anchor),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    pub(crate) fn selected_text(&self) -> Option<&str> {
+        self.selection().map(|range| &self.text[range])
+    }
+
+    /// Move the cursor, extending the selection from where it was if `extend` is set and
+    /// clearing it otherwise.
+    pub(crate) fn move_to(&mut self, position: usize, extend: bool) {
+        if !extend {
+            self.anchor = None;
+        } else if self.anchor.is_none() {
+            self.anchor = Some(self.cursor);
+        }
+        self.cursor = position;
+    }
+
+    pub(crate) fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.text.len();
+    }
+
+    /// Insert text at the cursor, replacing the selection.
+    pub(crate) fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        self.text.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    /// Remove the selected text, returning whether there was any.
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.anchor = None;
+        match selection {
+            Some(range) => {
+                self.cursor = range.start;
+                self.text.replace_range(range, "");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove the selection, or the cluster before the cursor if nothing is selected.
+    fn delete_back(&mut self) {
+        if !self.delete_selection() {
+            let start = prev_boundary(&self.text, self.cursor);
+            self.text.replace_range(start..self.cursor, "");
+            self.cursor = start;
+        }
+    }
+
+    /// Remove the selection, or the cluster after the cursor if nothing is selected.
+    fn delete_forward(&mut self) {
+        if !self.delete_selection() {
+            let end = next_boundary(&self.text, self.cursor);
+            self.text.replace_range(self.cursor..end, "");
+        }
+    }
+
+    /// A message to copy the selection to the clipboard, if anything is selected.
+    pub(crate) fn copy(&self) -> Option<Msg> {
+        let text = self.selected_text()?;
+        Some(Msg::new(SetClipboard(text.to_string())))
+    }
+
+    /// Remove the selection, returning a message to copy it to the clipboard.
+    pub(crate) fn cut(&mut self) -> Option<Msg> {
+        let msg = self.copy()?;
+        self.delete_selection();
+        Some(msg)
+    }
+
+    /// The start of the line the cursor is on.
+    fn line_start(&self) -> usize {
+        self.text[..self.cursor].rfind('\n').map_or(0, |n| n + 1)
+    }
+
+    /// The end of the line the cursor is on, not including the newline.
+    fn line_end(&self) -> usize {
+        let rest = &self.text[self.cursor..];
+        self.cursor + rest.find('\n').unwrap_or(rest.len())
+    }
+
+    /// Handle the keys for editing a line of text, the widgets handle moving between lines.
+    ///
+    /// Shift with a movement key extends the selection, ctrl+a selects everything and ctrl+c
+    /// and ctrl+x copy and cut the selection.
+    pub(crate) fn handle_key(&mut self, key: &Key) -> Edit {
+        let extend = key.with_shift();
+        match key.code {
+            KeyCode::Left => match self.selection() {
+                Some(range) if !extend => self.move_to(range.start, false),
+                _ => self.move_to(prev_boundary(&self.text, self.cursor), extend),
+            },
+            KeyCode::Right => match self.selection() {
+                Some(range) if !extend => self.move_to(range.end, false),
+                _ => self.move_to(next_boundary(&self.text, self.cursor), extend),
+            },
+            KeyCode::Home => self.move_to(self.line_start(), extend),
+            KeyCode::End => self.move_to(self.line_end(), extend),
+            KeyCode::Backspace => self.delete_back(),
+            KeyCode::Delete => self.delete_forward(),
+            KeyCode::Char('a') if key.with_control() => self.select_all(),
+            KeyCode::Char('c') if key.with_control() => {
+                return self.copy().map_or(Edit::Handled, Edit::Send)
+            }
+            KeyCode::Char('x') if key.with_control() => {
+                return self.cut().map_or(Edit::Handled, Edit::Send)
+            }
+            KeyCode::Char(c) if !key.with_control() && !key.with_alt() => {
+                self.insert(c.encode_utf8(&mut [0; 4]))
+            }
+            _ => return Edit::Ignored,
+        }
+        Edit::Handled
+    }
+}
+
+/// How a cluster of an [`Editor`]'s text is drawn.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Highlight {
+    None,
+    Cursor,
+    Selection,
+}
+
+impl Editor {
+    /// Draw part of the text, highlighting the cursor and selection if `focused`.
+    ///
+    /// A cursor at the end of `range` is drawn after the text if `cursor_at_end` is set, for
+    /// when the range is the line the cursor is on.
+    pub(crate) fn render(&self, range: Range<usize>, focused: bool, cursor_at_end: bool) -> String {
+        let selection = self.selection().filter(|_| focused);
+        let show_cursor = focused && selection.is_none();
+        let highlight = |position: usize| match &selection {
+            Some(selection) if selection.contains(&position) => Highlight::Selection,
+            _ if show_cursor && position == self.cursor => Highlight::Cursor,
+            _ => Highlight::None,
+        };
+
+        let mut line = String::new();
+        let mut run = String::new();
+        let mut run_highlight = Highlight::None;
+        let text = &self.text[range.clone()];
+        for (n, cluster) in clusters(text) {
+            let highlight = highlight(range.start + n);
+            if highlight != run_highlight {
+                push_run(&mut line, &run, run_highlight);
+                run.clear();
+                run_highlight = highlight;
+            }
+            run.push_str(cluster);
+        }
+        push_run(&mut line, &run, run_highlight);
+
+        if show_cursor && cursor_at_end && self.cursor == range.end {
+            line.push_str(&CURSOR_STYLE.render(" "));
+        }
+        line
+    }
+}
+
+fn push_run(line: &mut String, run: &str, highlight: Highlight) {
+    match highlight {
+        _ if run.is_empty() => {}
+        Highlight::None => line.push_str(run),
+        Highlight::Cursor => line.push_str(&CURSOR_STYLE.render(run)),
+        Highlight::Selection => line.push_str(&SELECTION_STYLE.render(run)),
+    }
+}
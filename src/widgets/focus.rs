@@ -0,0 +1,89 @@
+/// A widget which can be focused, so a container can choose which of its widgets gets input.
+///
+/// A focused widget handles input and draws itself highlighted, such as showing its selection.
+/// An unfocused widget ignores keyboard input and draws itself without the highlight, so it is
+/// clear which widget typing will go to. Widgets start focused so a lone widget works without
+/// any focus handling.
+pub trait Focusable {
+    /// Give this widget focus.
+    fn focus(&mut self);
+    /// Take focus away from this widget.
+    fn blur(&mut self);
+    /// Does this widget have focus.
+    fn is_focused(&self) -> bool;
+}
+
+/// Move focus to the next widget after the focused one, or the previous if `backwards`, wrapping
+/// around at the ends. Returns the index of the newly focused widget.
+///
+/// Every other widget is blurred. If none are focused the first is focused, or the last if
+/// `backwards`.
+///
+/// ```
+/// # use sketch::widgets::{cycle_focus, Focusable, Select};
+/// let mut a = Select::new(["a"]);
+/// let mut b = Select::new(["b"]);
+/// b.blur();
+/// assert_eq!(cycle_focus(&mut [&mut a, &mut b], false), Some(1));
+/// assert!(!a.is_focused() && b.is_focused());
+/// ```
+pub fn cycle_focus(widgets: &mut [&mut dyn Focusable], backwards: bool) -> Option<usize> {
+    let len = widgets.len();
+    if len == 0 {
+        return None;
+    }
+
+    let next = match widgets.iter().position(|w| w.is_focused()) {
+        Some(n) if backwards => (n + len - 1) % len,
+        Some(n) => (n + 1) % len,
+        None if backwards => len - 1,
+        None => 0,
+    };
+    for (n, widget) in widgets.iter_mut().enumerate() {
+        if n == next {
+            widget.focus();
+        } else {
+            widget.blur();
+        }
+    }
+    Some(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Widget(bool);
+
+    impl Focusable for Widget {
+        fn focus(&mut self) {
+            self.0 = true;
+        }
+
+        fn blur(&mut self) {
+            self.0 = false;
+        }
+
+        fn is_focused(&self) -> bool {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_cycle_focus() {
+        let (mut a, mut b, mut c) = (Widget(true), Widget(false), Widget(false));
+        assert_eq!(cycle_focus(&mut [&mut a, &mut b, &mut c], false), Some(1));
+        assert_eq!(cycle_focus(&mut [&mut a, &mut b, &mut c], false), Some(2));
+        assert_eq!(cycle_focus(&mut [&mut a, &mut b, &mut c], false), Some(0));
+        assert_eq!(cycle_focus(&mut [&mut a, &mut b, &mut c], true), Some(2));
+        assert!(!a.0 && !b.0 && c.0);
+    }
+
+    #[test]
+    fn test_cycle_focus_none_focused() {
+        let (mut a, mut b) = (Widget(false), Widget(false));
+        assert_eq!(cycle_focus(&mut [&mut a, &mut b], true), Some(1));
+        assert_eq!(cycle_focus(&mut [], false), None);
+    }
+}
@@ -0,0 +1,374 @@
+use crate::{join_horizontal, layout::truncate, text, Key, KeyCode, Model, Msg, Resize, Style};
+
+const KEY_STYLE: Style = Style::new().bold();
+const DESCRIPTION_STYLE: Style = Style::new().dim();
+const TITLE_STYLE: Style = Style::new().bold().underline();
+
+/// The width assumed before the first [`Resize`] if the terminal's size is unknown.
+const DEFAULT_WIDTH: usize = 80;
+
+/// The separator between bindings in the short help.
+const SHORT_SEPARATOR: &str = " • ";
+
+/// The gap between columns of the full help.
+const COLUMN_GAP: &str = "    ";
+
+/// A set of keys which do one thing, with the text describing it in a [`Help`].
+///
+/// ```
+/// # use sketch::{widgets::KeyBinding, KeyCode};
+/// let up = KeyBinding::new([KeyCode::Up, KeyCode::Char('k')]).with_help("↑/k", "move up");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    keys: Vec<KeyCode>,
+    control: bool,
+    key_help: String,
+    description: String,
+    enabled: bool,
+}
+
+impl KeyBinding {
+    /// Create a new enabled [`KeyBinding`] for any of `keys`, without any help text.
+    pub fn new(keys: impl IntoIterator<Item = KeyCode>) -> Self {
+        Self {
+            keys: keys.into_iter().collect(),
+            control: false,
+            key_help: String::new(),
+            description: String::new(),
+            enabled: true,
+        }
+    }
+
+    /// Only match the keys while ctrl is held.
+    pub fn with_control(mut self) -> Self {
+        self.control = true;
+        self
+    }
+
+    /// Set how the keys are shown in a [`Help`], such as `"↑/k"`, and what they do.
+    ///
+    /// Bindings without help text aren't shown.
+    pub fn with_help(mut self, key: impl Into<String>, description: impl Into<String>) -> Self {
+        self.key_help = key.into();
+        self.description = description.into();
+        self
+    }
+
+    /// Is the binding enabled, disabled bindings never match and aren't shown.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable the binding, such as when what it does isn't possible right now.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Is `key` one of this binding's keys.
+    ///
+    /// Keys with alt held never match, and keys with ctrl held only match bindings made with
+    /// [`KeyBinding::with_control`].
+    pub fn matches(&self, key: &Key) -> bool {
+        self.enabled
+            && key.with_control() == self.control
+            && !key.with_alt()
+            && self.keys.contains(&key.code)
+    }
+
+    /// Is the binding shown in a [`Help`].
+    fn is_shown(&self) -> bool {
+        self.enabled && !(self.key_help.is_empty() && self.description.is_empty())
+    }
+
+    /// The key and description styled for a [`Help`].
+    fn render(&self) -> String {
+        format!(
+            "{} {}",
+            KEY_STYLE.render(&self.key_help),
+            DESCRIPTION_STYLE.render(&self.description)
+        )
+    }
+}
+
+/// [`KeyBinding`]s in named groups, such as `"Navigation"` and `"Actions"`.
+///
+/// ```
+/// # use sketch::{widgets::{KeyBinding, KeyMap}, KeyCode};
+/// let keys = KeyMap::new()
+///     .with_group("Navigation", [
+///         KeyBinding::new([KeyCode::Up]).with_help("↑", "up"),
+///         KeyBinding::new([KeyCode::Down]).with_help("↓", "down"),
+///     ])
+///     .with_group("Actions", [KeyBinding::new([KeyCode::Char('q')]).with_help("q", "quit")]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    groups: Vec<(String, Vec<KeyBinding>)>,
+}
+
+impl KeyMap {
+    /// Create a new [`KeyMap`] without any bindings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a group of bindings after the existing ones.
+    pub fn with_group(
+        mut self,
+        title: impl Into<String>,
+        bindings: impl IntoIterator<Item = KeyBinding>,
+    ) -> Self {
+        self.groups
+            .push((title.into(), bindings.into_iter().collect()));
+        self
+    }
+
+    /// The groups of bindings with their titles.
+    pub fn groups(&self) -> impl Iterator<Item = (&str, &[KeyBinding])> {
+        self.groups
+            .iter()
+            .map(|(title, bindings)| (title.as_str(), bindings.as_slice()))
+    }
+
+    /// The bindings in the group titled `title`, mutably, such as to enable or disable them.
+    pub fn group_mut(&mut self, title: &str) -> Option<&mut [KeyBinding]> {
+        let (_, bindings) = self.groups.iter_mut().find(|(t, _)| t == title)?;
+        Some(bindings)
+    }
+
+    /// The first binding that `key` matches, if any.
+    pub fn matching(&self, key: &Key) -> Option<&KeyBinding> {
+        self.groups
+            .iter()
+            .flat_map(|(_, bindings)| bindings)
+            .find(|binding| binding.matches(key))
+    }
+}
+
+/// Help for the keys of an app, drawn from a [`KeyMap`].
+///
+/// The short help is a single line of every binding, cut off with a `…` if it doesn't fit. The
+/// full help lays each group out as a column under its title, starting a new row of columns
+/// when they don't fit in the width. Switch between them with [`Help::toggle`], such as when `?`
+/// is pressed.
+///
+/// The width is taken from [`Resize`] messages.
+///
+/// ```
+/// # use sketch::{widgets::{Help, KeyBinding, KeyMap}, KeyCode, Model};
+/// let keys = KeyMap::new().with_group("Actions", [
+///     KeyBinding::new([KeyCode::Char('q')]).with_help("q", "quit"),
+///     KeyBinding::new([KeyCode::Char('?')]).with_help("?", "more"),
+/// ]);
+/// let mut help = Help::new(keys).with_width(40);
+/// help.toggle();
+/// println!("{}", help.view());
+/// ```
+pub struct Help {
+    keys: KeyMap,
+    show_all: bool,
+    width: usize,
+}
+
+impl Help {
+    /// Create a new [`Help`] showing the short help.
+    pub fn new(keys: KeyMap) -> Self {
+        Self {
+            keys,
+            show_all: false,
+            width: crate::terminal_size().map_or(DEFAULT_WIDTH, |(cols, _)| cols as usize),
+        }
+    }
+
+    /// Set the width to fit the help in. Defaults to the width of the terminal.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// The bindings shown.
+    pub fn keys(&self) -> &KeyMap {
+        &self.keys
+    }
+
+    /// The bindings shown, mutably.
+    pub fn keys_mut(&mut self) -> &mut KeyMap {
+        &mut self.keys
+    }
+
+    /// Is the full help shown rather than the short help.
+    pub fn is_showing_all(&self) -> bool {
+        self.show_all
+    }
+
+    /// Set whether the full help is shown rather than the short help.
+    pub fn set_show_all(&mut self, show_all: bool) {
+        self.show_all = show_all;
+    }
+
+    /// Switch between the short and full help.
+    pub fn toggle(&mut self) {
+        self.show_all = !self.show_all;
+    }
+
+    /// Every shown binding on one line.
+    pub fn short_view(&self) -> String {
+        let separator = DESCRIPTION_STYLE.render(SHORT_SEPARATOR);
+        let line = self
+            .keys
+            .groups
+            .iter()
+            .flat_map(|(_, bindings)| bindings)
+            .filter(|binding| binding.is_shown())
+            .map(KeyBinding::render)
+            .collect::<Vec<_>>()
+            .join(&separator);
+        truncate(&line, self.width)
+    }
+
+    /// Each group as a column under its title, in as many rows as are needed to fit the width.
+    pub fn full_view(&self) -> String {
+        let columns: Vec<String> = self
+            .keys
+            .groups
+            .iter()
+            .filter_map(|(title, bindings)| group_column(title, bindings))
+            .collect();
+
+        let mut rows = Vec::new();
+        let mut row: Vec<&str> = Vec::new();
+        let mut row_width = 0;
+        for column in &columns {
+            let width = column.split('\n').map(text::width).max().unwrap_or(0);
+            let gap = if row.is_empty() { 0 } else { COLUMN_GAP.len() };
+            if !row.is_empty() && row_width + gap + width > self.width {
+                rows.push(join_columns(&row));
+                row.clear();
+                row_width = 0;
+            }
+            row_width += if row.is_empty() { width } else { gap + width };
+            row.push(column);
+        }
+        if !row.is_empty() {
+            rows.push(join_columns(&row));
+        }
+
+        rows.join("\n\n")
+            .split('\n')
+            .map(|line| truncate(line.trim_end(), self.width))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// A group's title above its bindings, with the descriptions lined up. `None` if none of the
+/// bindings are shown.
+fn group_column(title: &str, bindings: &[KeyBinding]) -> Option<String> {
+    let bindings: Vec<&KeyBinding> = bindings.iter().filter(|b| b.is_shown()).collect();
+    let key_width = bindings
+        .iter()
+        .map(|binding| text::width(&binding.key_help))
+        .max()?;
+
+    let mut lines = vec![TITLE_STYLE.render(title)];
+    lines.extend(bindings.iter().map(|binding| {
+        let padding = key_width - text::width(&binding.key_help);
+        format!(
+            "{}{} {}",
+            KEY_STYLE.render(&binding.key_help),
+            " ".repeat(padding),
+            DESCRIPTION_STYLE.render(&binding.description)
+        )
+    }));
+    Some(lines.join("\n"))
+}
+
+/// Lay columns out side by side with a gap between each.
+fn join_columns(columns: &[&str]) -> String {
+    let mut blocks = Vec::with_capacity(columns.len() * 2);
+    for (n, column) in columns.iter().enumerate() {
+        if n > 0 {
+            blocks.push(COLUMN_GAP);
+        }
+        blocks.push(column);
+    }
+    join_horizontal(&blocks, None)
+}
+
+impl Model for Help {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if let Some(resize) = msg.cast::<Resize>() {
+            self.width = resize.width as usize;
+        }
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        match self.show_all {
+            true => self.full_view(),
+            false => self.short_view(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::strip_escapes;
+
+    fn keys() -> KeyMap {
+        KeyMap::new()
+            .with_group(
+                "Navigation",
+                [
+                    KeyBinding::new([KeyCode::Up]).with_help("↑", "up"),
+                    KeyBinding::new([KeyCode::PageDown]).with_help("pgdn", "next page"),
+                ],
+            )
+            .with_group(
+                "Actions",
+                [
+                    KeyBinding::new([KeyCode::Char('q')]).with_help("q", "quit"),
+                    KeyBinding::new([KeyCode::Char('c')]).with_control(),
+                ],
+            )
+    }
+
+    #[test]
+    fn test_short_view_fits_width() {
+        let help = Help::new(keys()).with_width(80);
+        assert_eq!(
+            strip_escapes(&help.short_view()),
+            "↑ up • pgdn next page • q quit"
+        );
+        let help = help.with_width(12);
+        assert_eq!(strip_escapes(&help.short_view()), "↑ up • pgdn…");
+    }
+
+    #[test]
+    fn test_full_view_wraps_groups() {
+        let help = Help::new(keys()).with_width(40);
+        assert_eq!(
+            strip_escapes(&help.full_view()),
+            "Navigation        Actions\n↑    up           q quit\npgdn next page"
+        );
+        let help = help.with_width(20);
+        assert_eq!(
+            strip_escapes(&help.full_view()),
+            "Navigation\n↑    up\npgdn next page\n\nActions\nq quit"
+        );
+    }
+
+    #[test]
+    fn test_disabled_bindings_are_hidden() {
+        let mut keys = keys();
+        keys.group_mut("Actions").unwrap()[0].set_enabled(false);
+        let help = Help::new(keys).with_width(80);
+        assert_eq!(strip_escapes(&help.short_view()), "↑ up • pgdn next page");
+        assert_eq!(
+            strip_escapes(&help.full_view()),
+            "Navigation\n↑    up\npgdn next page"
+        );
+    }
+}
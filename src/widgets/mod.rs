@@ -0,0 +1,30 @@
+//! Reusable components to build apps from.
+//!
+//! Each widget implements [`Model`](crate::Model) so it can be held in your own model, given
+//! messages from your [`Model::update`](crate::Model::update) and rendered as part of your
+//! [`Model::view`](crate::Model::view).
+
+pub use focus::*;
+pub use help::*;
+pub use select::*;
+pub use skeleton::*;
+pub use spinner::*;
+pub use split::*;
+pub use status::*;
+pub use text_area::*;
+pub use text_input::*;
+pub use tree::*;
+pub use viewport::*;
+
+mod editor;
+mod focus;
+mod help;
+mod select;
+mod skeleton;
+mod spinner;
+mod split;
+mod status;
+mod text_area;
+mod text_input;
+mod tree;
+mod viewport;
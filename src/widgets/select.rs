@@ -0,0 +1,399 @@
+use super::Focusable;
+use crate::{text::prev_boundary, Key, KeyCode, Message, Model, Msg, Style, Theme};
+
+const PROMPT_STYLE: Style = Style::new().bold();
+const SELECTED_STYLE: Style = Style::new().reverse();
+const MATCH_STYLE: Style = Style::new().yellow().bold();
+const COUNT_STYLE: Style = Style::new().dim();
+
+/// A list of items filtered by fuzzy matching a typed query, such as for a command palette.
+///
+/// Typing edits the query, up and down (or ctrl+p and ctrl+n) move the selection and enter
+/// chooses the selected item, sending a [`Selected`] message. Items are sorted by how well they
+/// match the query, with the matched characters highlighted.
+///
+/// ```
+/// # use sketch::{widgets::Select, Model};
+/// let select = Select::new(["Open File", "Save File", "Quit"]);
+/// println!("{}", select.view());
+/// ```
+pub struct Select {
+    items: Vec<String>,
+    query: String,
+    matches: Vec<Match>,
+    selected: usize,
+    chosen: Option<usize>,
+    height: usize,
+    prompt: String,
+    styles: Styles,
+    focused: bool,
+}
+
+/// The styles used to draw a [`Select`].
+struct Styles {
+    prompt: Style,
+    selected: Style,
+    matched: Style,
+    count: Style,
+}
+
+/// A message sent by [`Select`] when an item is chosen.
+#[derive(Debug)]
+pub struct Selected {
+    /// The index of the item in the list given to [`Select::new`].
+    pub index: usize,
+    /// The item.
+    pub item: String,
+}
+impl Message for Selected {}
+
+/// An item which matches the query.
+struct Match {
+    index: usize,
+    fuzzy: FuzzyMatch,
+}
+
+impl Select {
+    /// Create a new [`Select`] from a list of items.
+    pub fn new<S: Into<String>>(items: impl IntoIterator<Item = S>) -> Self {
+        let mut select = Self {
+            items: items.into_iter().map(Into::into).collect(),
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            chosen: None,
+            height: 10,
+            prompt: String::from("> "),
+            styles: Styles {
+                prompt: PROMPT_STYLE,
+                selected: SELECTED_STYLE,
+                matched: MATCH_STYLE,
+                count: COUNT_STYLE,
+            },
+            focused: true,
+        };
+        select.filter();
+        select
+    }
+
+    /// Set the maximum number of items to show at once. Defaults to 10.
+    pub fn with_height(mut self, height: usize) -> Self {
+        self.height = height.max(1);
+        self
+    }
+
+    /// Set the text shown before the query. Defaults to `"> "`.
+    pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Use the `"prompt"`, `"selected"`, `"match"` and `"muted"` styles from a [`Theme`].
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.styles = Styles {
+            prompt: theme.get("prompt").clone(),
+            selected: theme.get("selected").clone(),
+            matched: theme.get("match").clone(),
+            count: theme.get("muted").clone(),
+        };
+        self
+    }
+
+    /// The current query.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Set the query, filtering the items.
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.query = query.into();
+        self.filter();
+    }
+
+    /// The currently selected item, if any match the query.
+    pub fn selected(&self) -> Option<&str> {
+        let m = self.matches.get(self.selected)?;
+        Some(&self.items[m.index])
+    }
+
+    /// The item chosen by pressing enter, if any.
+    pub fn chosen(&self) -> Option<&str> {
+        self.chosen.map(|index| self.items[index].as_str())
+    }
+
+    /// The number of items which match the query.
+    pub fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn filter(&mut self) {
+        self.matches = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let fuzzy = fuzzy_match(&self.query, item)?;
+                Some(Match { index, fuzzy })
+            })
+            .collect();
+        // This sort is stable so equal scores stay in their original order.
+        self.matches
+            .sort_by_key(|m| std::cmp::Reverse(m.fuzzy.score));
+        self.selected = 0;
+    }
+
+    fn render_item(&self, m: &Match, selected: bool) -> String {
+        let mut line = String::from(if selected { "> " } else { "  " });
+        for (n, c) in self.items[m.index].chars().enumerate() {
+            if m.fuzzy.positions.contains(&n) {
+                line.push_str(&self.styles.matched.render(c.to_string()));
+            } else {
+                line.push(c);
+            }
+        }
+
+        if selected && self.focused {
+            self.styles.selected.render(line)
+        } else {
+            line
+        }
+    }
+}
+
+/// When unfocused the query can't be edited and the selected item isn't highlighted.
+impl Focusable for Select {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn blur(&mut self) {
+        self.focused = false;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl Model for Select {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        let Some(key) = msg.cast::<Key>().filter(|_| self.focused) else {
+            return (self, None);
+        };
+        if key.is_release() {
+            return (self, None);
+        }
+
+        match (key.code, key.with_control()) {
+            (KeyCode::Up, _) | (KeyCode::Char('p'), true) => {
+                self.selected = self.selected.saturating_sub(1)
+            }
+            (KeyCode::Down, _) | (KeyCode::Char('n'), true) => {
+                self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1))
+            }
+            (KeyCode::Enter, _) => {
+                if let Some(m) = self.matches.get(self.selected) {
+                    self.chosen = Some(m.index);
+                    let selected = Selected {
+                        index: m.index,
+                        item: self.items[m.index].clone(),
+                    };
+                    return (self, Some(Msg::new(selected)));
+                }
+            }
+            (KeyCode::Backspace, _) => {
+                self.query
+                    .truncate(prev_boundary(&self.query, self.query.len()));
+                self.filter();
+            }
+            (KeyCode::Char(c), false) if !key.with_alt() => {
+                self.query.push(c);
+                self.filter();
+            }
+            _ => {}
+        }
+
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        let mut lines = vec![format!(
+            "{}{}",
+            self.styles.prompt.render(&self.prompt),
+            self.query
+        )];
+        lines.push(self.styles.count.render(format!(
+            "{}/{}",
+            self.matches.len(),
+            self.items.len()
+        )));
+
+        if self.matches.is_empty() {
+            lines.push(self.styles.count.render("  No matches"));
+        }
+
+        // Scroll so the selected item is always shown.
+        let start = (self.selected + 1).saturating_sub(self.height);
+        for (n, m) in self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(self.height)
+        {
+            lines.push(self.render_item(m, n == self.selected));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// The result of [`fuzzy_match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// How well the query matched, higher is better.
+    pub score: i64,
+    /// The indexes of the characters (not bytes) in the candidate which matched.
+    pub positions: Vec<usize>,
+}
+
+/// Match `query` against `candidate` if every character of the query appears in order in the
+/// candidate, ignoring case.
+///
+/// Matches score higher when the matched characters are next to each other or at the start of
+/// words.
+///
+/// ```
+/// # use sketch::widgets::fuzzy_match;
+/// assert!(fuzzy_match("of", "Open File").is_some());
+/// assert!(fuzzy_match("fo", "Open File").is_none());
+/// ```
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let mut query = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut positions = Vec::new();
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut last_match: Option<usize> = None;
+
+    for (n, c) in candidate.chars().enumerate() {
+        let Some(&q) = query.peek() else {
+            break;
+        };
+
+        if c.to_lowercase().eq(std::iter::once(q)) {
+            query.next();
+            score += 1;
+
+            let word_start = match previous {
+                None => true,
+                Some(p) => !p.is_alphanumeric() || (p.is_lowercase() && c.is_uppercase()),
+            };
+            if word_start {
+                score += 3;
+            }
+
+            match last_match {
+                Some(last) if last + 1 == n => score += 5,
+                Some(last) => score -= (n - last - 1) as i64,
+                None => {}
+            }
+
+            positions.push(n);
+            last_match = Some(n);
+        }
+
+        previous = Some(c);
+    }
+
+    if query.peek().is_some() {
+        return None;
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::new(Key::from(KeyEvent::new(code, KeyModifiers::NONE)))
+    }
+
+    fn type_str(mut select: Select, s: &str) -> Select {
+        for c in s.chars() {
+            select = select.update(&key(KeyCode::Char(c))).0;
+        }
+        select
+    }
+
+    #[test]
+    fn test_fuzzy_match_positions() {
+        let m = fuzzy_match("of", "Open File").unwrap();
+        assert_eq!(m.positions, [0, 5]);
+        assert!(fuzzy_match("xyz", "Open File").is_none());
+        assert_eq!(fuzzy_match("", "anything").unwrap().positions, []);
+    }
+
+    #[test]
+    fn test_fuzzy_match_prefers_consecutive() {
+        let consecutive = fuzzy_match("sav", "Save").unwrap();
+        let spread = fuzzy_match("sav", "Show all views").unwrap();
+        assert!(consecutive.score > spread.score);
+    }
+
+    #[test]
+    fn test_select_filters_and_sorts() {
+        let select = Select::new(["Show all views", "Quit", "Save"]);
+        let select = type_str(select, "sav");
+        assert_eq!(select.match_count(), 2);
+        assert_eq!(select.selected(), Some("Save"));
+    }
+
+    #[test]
+    fn test_select_no_matches() {
+        let select = type_str(Select::new(["a", "b"]), "z");
+        assert_eq!(select.match_count(), 0);
+        assert_eq!(select.selected(), None);
+        assert!(select.view().contains("No matches"));
+        let (select, msg) = select.update(&key(KeyCode::Enter));
+        assert!(msg.is_none());
+        assert_eq!(select.chosen(), None);
+    }
+
+    #[test]
+    fn test_select_choose() {
+        let select = Select::new(["one", "two", "three"]);
+        let select = select.update(&key(KeyCode::Down)).0;
+        let (select, msg) = select.update(&key(KeyCode::Enter));
+        let selected = msg.unwrap();
+        let selected = selected.cast::<Selected>().unwrap();
+        assert_eq!(selected.index, 1);
+        assert_eq!(selected.item, "two");
+        assert_eq!(select.chosen(), Some("two"));
+    }
+
+    #[test]
+    fn test_select_backspace() {
+        let select = type_str(Select::new(["two", "three"]), "tw");
+        assert_eq!(select.match_count(), 1);
+        let select = select.update(&key(KeyCode::Backspace)).0;
+        assert_eq!(select.query(), "t");
+        assert_eq!(select.match_count(), 2);
+
+        // A letter with a combining accent is removed as one.
+        let select = type_str(select, "e\u{301}");
+        let select = select.update(&key(KeyCode::Backspace)).0;
+        assert_eq!(select.query(), "t");
+    }
+
+    #[test]
+    fn test_unfocused_ignores_input() {
+        let mut select = Select::new(["one", "two"]);
+        select.blur();
+        let select = type_str(select, "tw");
+        assert_eq!(select.query(), "");
+        assert!(!select.view().contains("\x1b[7m"));
+    }
+}
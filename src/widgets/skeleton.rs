@@ -0,0 +1,131 @@
+use crate::{Model, Msg, Rect, Style, Tick};
+
+const BAR_STYLE: Style = Style::new().dim();
+const SHINE_STYLE: Style = Style::new();
+
+/// The block used to draw the bars.
+const BLOCK: char = '█';
+
+/// How long each bar is as a percentage of the width, repeated for every row.
+const BAR_LENGTHS: [usize; 4] = [100, 90, 95, 60];
+
+/// The number of columns lit up by the shimmer.
+const SHINE_WIDTH: usize = 4;
+
+/// A placeholder of dim bars to show while content is loading.
+///
+/// The bars fill the size of a [`Rect`] so the view keeps the same layout once the real content
+/// is swapped in. If animated, a shimmer moves across the bars each time a [`Tick`] message is
+/// received.
+///
+/// ```
+/// # use sketch::{widgets::Skeleton, Model, Rect};
+/// let skeleton = Skeleton::new(Rect::new(0, 0, 20, 3)).with_animation(false);
+/// assert_eq!(skeleton.view().lines().count(), 3);
+/// ```
+pub struct Skeleton {
+    area: Rect,
+    animated: bool,
+    frame: usize,
+}
+
+impl Skeleton {
+    /// Create a new [`Skeleton`] the size of `area`.
+    pub fn new(area: Rect) -> Self {
+        Self {
+            area,
+            animated: true,
+            frame: 0,
+        }
+    }
+
+    /// Set whether a shimmer moves across the bars on each [`Tick`]. Defaults to `true`.
+    pub fn with_animation(mut self, animated: bool) -> Self {
+        self.animated = animated;
+        self
+    }
+
+    /// Change the size of the placeholder.
+    pub fn set_area(&mut self, area: Rect) {
+        self.area = area;
+    }
+
+    /// The column the shimmer starts at, which may be before the start of the bars.
+    fn shine_start(&self) -> isize {
+        let width = self.area.width as usize;
+        // Leave a gap between each pass of the shimmer.
+        let cycle = width + SHINE_WIDTH * 2;
+        (self.frame % cycle) as isize - SHINE_WIDTH as isize
+    }
+
+    fn render_bar(&self, length: usize) -> String {
+        if !self.animated {
+            return BAR_STYLE.render(BLOCK.to_string().repeat(length));
+        }
+
+        let start = self.shine_start().clamp(0, length as isize) as usize;
+        let end = (self.shine_start() + SHINE_WIDTH as isize).clamp(0, length as isize) as usize;
+
+        let mut bar = String::new();
+        for (style, n) in [
+            (BAR_STYLE, start),
+            (SHINE_STYLE, end - start),
+            (BAR_STYLE, length - end),
+        ] {
+            if n > 0 {
+                bar.push_str(&style.render(BLOCK.to_string().repeat(n)));
+            }
+        }
+        bar
+    }
+}
+
+impl Model for Skeleton {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if self.animated && msg.is::<Tick>() {
+            self.frame = self.frame.wrapping_add(1);
+        }
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        let width = self.area.width as usize;
+        (0..self.area.height as usize)
+            .map(|row| {
+                let length = width * BAR_LENGTHS[row % BAR_LENGTHS.len()] / 100;
+                let bar = self.render_bar(length);
+                format!("{bar}{}", " ".repeat(width - length))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    #[test]
+    fn test_fills_area() {
+        let skeleton = Skeleton::new(Rect::new(0, 0, 10, 5));
+        let view = skeleton.view();
+        assert_eq!(view.split('\n').count(), 5);
+        assert!(view.split('\n').all(|line| text::width(line) == 10));
+    }
+
+    #[test]
+    fn test_tick_moves_shimmer() {
+        let skeleton = Skeleton::new(Rect::new(0, 0, 10, 1));
+        let before = skeleton.view();
+        let (skeleton, _) = skeleton.update(&Msg::new(Tick));
+        assert_ne!(skeleton.view(), before);
+    }
+
+    #[test]
+    fn test_without_animation() {
+        let skeleton = Skeleton::new(Rect::new(0, 0, 4, 1)).with_animation(false);
+        let (skeleton, _) = skeleton.update(&Msg::new(Tick));
+        assert_eq!(skeleton.view(), "\x1b[2m████\x1b[0m");
+    }
+}
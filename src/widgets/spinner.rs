@@ -0,0 +1,78 @@
+use crate::{Model, Msg, Style, Tick};
+
+/// The frames of the spinner, one shown for each [`Tick`].
+const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// A single character animation to show something is happening, moving on to its next frame
+/// each time a [`Tick`] message is received.
+///
+/// ```
+/// # use sketch::{widgets::Spinner, Model, Msg, Tick};
+/// let spinner = Spinner::new();
+/// assert_eq!(spinner.view(), "⠋\x1b[0m");
+/// let (spinner, _) = spinner.update(&Msg::new(Tick));
+/// assert_eq!(spinner.view(), "⠙\x1b[0m");
+/// ```
+pub struct Spinner {
+    style: Style,
+    frame: usize,
+}
+
+impl Spinner {
+    /// Create a new [`Spinner`].
+    pub fn new() -> Self {
+        Self {
+            style: Style::new(),
+            frame: 0,
+        }
+    }
+
+    /// Set the style of the spinner. Defaults to [`Style::new`].
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Move on to the next frame, as is done for each [`Tick`].
+    pub fn tick(&mut self) {
+        self.frame = (self.frame + 1) % FRAMES.len();
+    }
+
+    /// The character for the current frame.
+    pub fn symbol(&self) -> char {
+        FRAMES[self.frame]
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Model for Spinner {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if msg.is::<Tick>() {
+            self.tick();
+        }
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        self.style.render(self.symbol().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wraps_around() {
+        let mut spinner = Spinner::new();
+        for _ in 0..FRAMES.len() {
+            spinner.tick();
+        }
+        assert_eq!(spinner.symbol(), FRAMES[0]);
+    }
+}
@@ -0,0 +1,355 @@
+use crate::{two_columns, wrap_text, Batch, Key, KeyCode, Model, Mouse, Msg, Resize};
+
+/// The width and height assumed before the first [`Resize`] if the terminal's size is unknown.
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// How the panes of a [`Split`] are laid out.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// The first pane on the left and the second on the right, separated by a line.
+    #[default]
+    SideBySide,
+    /// The first pane above the second.
+    Stacked,
+}
+
+/// One of the panes of a [`Split`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    /// The left or top pane.
+    First,
+    /// The right or bottom pane.
+    Second,
+}
+
+/// Two models shown next to each other, each taking half of the screen.
+///
+/// Input from the keyboard and mouse is only given to the focused pane, which is switched with
+/// tab and shift+tab. [`Resize`] messages are given to each pane with the size of its half and
+/// every other message is given to both. If both panes return a message from the same update,
+/// only the focused pane's message is kept.
+///
+/// ```no_run
+/// # use sketch::{widgets::{Select, Split}, App};
+/// let split = Split::new(Select::new(["a", "b"]), Select::new(["c", "d"]));
+/// let split = App::new(split).run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct Split<A, B> {
+    first: A,
+    second: B,
+    direction: SplitDirection,
+    focused: Pane,
+    size: (u16, u16),
+}
+
+impl<A: Model, B: Model> Split<A, B> {
+    /// Create a new [`Split`] with the first pane focused.
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            direction: SplitDirection::default(),
+            focused: Pane::First,
+            size: crate::terminal_size().unwrap_or(DEFAULT_SIZE),
+        }
+    }
+
+    /// Set how the panes are laid out. Defaults to [`SplitDirection::SideBySide`].
+    pub fn with_direction(mut self, direction: SplitDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// The pane given input.
+    pub fn focused(&self) -> Pane {
+        self.focused
+    }
+
+    /// Set the pane given input.
+    pub fn set_focused(&mut self, pane: Pane) {
+        self.focused = pane;
+    }
+
+    /// The first pane's model.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// The second pane's model.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// The first pane's model, mutably.
+    pub fn first_mut(&mut self) -> &mut A {
+        &mut self.first
+    }
+
+    /// The second pane's model, mutably.
+    pub fn second_mut(&mut self) -> &mut B {
+        &mut self.second
+    }
+
+    /// The sizes of the first and second panes.
+    fn pane_sizes(&self) -> ((u16, u16), (u16, u16)) {
+        let (width, height) = self.size;
+        match self.direction {
+            SplitDirection::SideBySide => {
+                // One column is used by the separator.
+                let first = width.saturating_sub(1) / 2;
+                let second = width.saturating_sub(1 + first);
+                ((first, height), (second, height))
+            }
+            SplitDirection::Stacked => {
+                let first = height / 2;
+                ((width, first), (width, height - first))
+            }
+        }
+    }
+
+    fn update_first(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        let (first, out) = self.first.update(msg);
+        self.first = first;
+        (self, out)
+    }
+
+    fn update_second(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        let (second, out) = self.second.update(msg);
+        self.second = second;
+        (self, out)
+    }
+
+    /// Update each pane with its own message, keeping the focused pane's returned message.
+    fn update_both(self, first: &Msg, second: &Msg) -> (Self, Option<Msg>) {
+        let (split, first) = self.update_first(first);
+        let (split, second) = split.update_second(second);
+        let out = match split.focused {
+            Pane::First => first.or(second),
+            Pane::Second => second.or(first),
+        };
+        (split, out)
+    }
+
+    fn update_focused(self, msg: &Msg) -> (Self, Option<Msg>) {
+        match self.focused {
+            Pane::First => self.update_first(msg),
+            Pane::Second => self.update_second(msg),
+        }
+    }
+}
+
+impl<A: Model, B: Model> Model for Split<A, B> {
+    fn startup(&self) -> Option<Msg> {
+        match (self.first.startup(), self.second.startup()) {
+            (Some(first), Some(second)) => Some(Msg::new(Batch::new([first, second]))),
+            (first, second) => first.or(second),
+        }
+    }
+
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if let Some(key) = msg.cast::<Key>() {
+            if matches!(key.code, KeyCode::Tab | KeyCode::BackTab) {
+                if key.is_press() {
+                    self.focused = match self.focused {
+                        Pane::First => Pane::Second,
+                        Pane::Second => Pane::First,
+                    };
+                }
+                return (self, None);
+            }
+            return self.update_focused(msg);
+        }
+
+        #[cfg(feature = "paste")]
+        if msg.is::<crate::Paste>() {
+            return self.update_focused(msg);
+        }
+
+        if msg.is::<Mouse>() {
+            return self.update_focused(msg);
+        }
+
+        if let Some(resize) = msg.cast::<Resize>() {
+            self.size = (resize.width, resize.height);
+            let ((w1, h1), (w2, h2)) = self.pane_sizes();
+            let first = Msg::new(Resize {
+                width: w1,
+                height: h1,
+            });
+            let second = Msg::new(Resize {
+                width: w2,
+                height: h2,
+            });
+            return self.update_both(&first, &second);
+        }
+
+        self.update_both(msg, msg)
+    }
+
+    fn view(&self) -> String {
+        let ((first_width, first_height), (second_width, _)) = self.pane_sizes();
+        let first = wrap_text(&self.first.view(), first_width as usize);
+        let second = wrap_text(&self.second.view(), second_width as usize);
+
+        match self.direction {
+            SplitDirection::SideBySide => two_columns(
+                &first.join("\n"),
+                &second.join("\n"),
+                first_width as usize,
+                1,
+                Some('│'),
+            ),
+            SplitDirection::Stacked => {
+                let mut lines: Vec<&str> = first
+                    .iter()
+                    .map(String::as_str)
+                    .take(first_height as usize)
+                    .collect();
+                lines.resize(first_height as usize, "");
+                lines.extend(second.iter().map(String::as_str));
+                lines.join("\n")
+            }
+        }
+    }
+
+    fn on_quit(&self) {
+        self.first.on_quit();
+        self.second.on_quit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// A model recording the keys and sizes it is given, and whether it was quit.
+    #[derive(Default)]
+    struct Recorder {
+        keys: String,
+        size: Option<(u16, u16)>,
+        quit: AtomicBool,
+    }
+
+    impl Model for Recorder {
+        fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+            if let Some(key) = msg.cast::<Key>() {
+                if let KeyCode::Char(c) = key.code {
+                    self.keys.push(c);
+                }
+            }
+            if let Some(resize) = msg.cast::<Resize>() {
+                self.size = Some((resize.width, resize.height));
+            }
+            (self, None)
+        }
+
+        fn view(&self) -> String {
+            self.keys.clone()
+        }
+
+        fn on_quit(&self) {
+            self.quit.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::new(Key::from(KeyEvent::new(code, KeyModifiers::NONE)))
+    }
+
+    #[test]
+    fn test_input_goes_to_focused_pane() {
+        let split = Split::new(Recorder::default(), Recorder::default());
+        let (split, _) = split.update(&key(KeyCode::Char('a')));
+        let (split, _) = split.update(&key(KeyCode::Tab));
+        assert_eq!(split.focused(), Pane::Second);
+        let (split, _) = split.update(&key(KeyCode::Char('b')));
+        assert_eq!(split.first().keys, "a");
+        assert_eq!(split.second().keys, "b");
+    }
+
+    #[test]
+    fn test_resize_is_split() {
+        let split = Split::new(Recorder::default(), Recorder::default());
+        let resize = Msg::new(Resize {
+            width: 21,
+            height: 10,
+        });
+        let (split, _) = split.update(&resize);
+        assert_eq!(split.first().size, Some((10, 10)));
+        assert_eq!(split.second().size, Some((10, 10)));
+
+        let split = split.with_direction(SplitDirection::Stacked);
+        let (split, _) = split.update(&resize);
+        assert_eq!(split.first().size, Some((21, 5)));
+        assert_eq!(split.second().size, Some((21, 5)));
+    }
+
+    #[test]
+    fn test_view() {
+        let split = Split::new(Recorder::default(), Recorder::default());
+        let (split, _) = split.update(&Msg::new(Resize {
+            width: 7,
+            height: 4,
+        }));
+        let (split, _) = split.update(&key(KeyCode::Char('a')));
+        assert_eq!(split.view(), "a  │");
+
+        let split = split.with_direction(SplitDirection::Stacked);
+        assert_eq!(split.view(), "a\n\n");
+    }
+
+    #[test]
+    fn test_view_wraps_panes_to_their_width() {
+        let split = Split::new(Recorder::default(), Recorder::default());
+        let (split, _) = split.update(&Msg::new(Resize {
+            width: 7,
+            height: 4,
+        }));
+        let (split, _) = split.update(&key(KeyCode::Char('a')));
+        let (split, _) = split.update(&key(KeyCode::Tab));
+        let (split, _) = split.update(&key(KeyCode::Char('b')));
+        let (split, _) = split.update(&key(KeyCode::Char('c')));
+        let (split, _) = split.update(&key(KeyCode::Char('d')));
+        let (split, _) = split.update(&key(KeyCode::Char('e')));
+        assert_eq!(split.view(), "a  │bcd\n   │e");
+    }
+
+    #[test]
+    fn test_on_quit_reaches_both_panes() {
+        let split = Split::new(Recorder::default(), Recorder::default());
+        split.on_quit();
+        assert!(split.first().quit.load(Ordering::Relaxed));
+        assert!(split.second().quit.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_startup_batches_both_panes() {
+        struct Starts(&'static str);
+
+        impl Model for Starts {
+            fn startup(&self) -> Option<Msg> {
+                Some(Msg::new(crate::PrintAbove(self.0.to_string())))
+            }
+
+            fn update(self, _: &Msg) -> (Self, Option<Msg>) {
+                (self, None)
+            }
+
+            fn view(&self) -> String {
+                String::new()
+            }
+        }
+
+        let split = Split::new(Starts("first"), Starts("second"));
+        let msg = split.startup().unwrap();
+        let batch = msg.cast::<Batch>().unwrap().take();
+        let started: Vec<&str> = batch
+            .iter()
+            .map(|msg| msg.cast::<crate::PrintAbove>().unwrap().0.as_str())
+            .collect();
+        assert_eq!(started, ["first", "second"]);
+    }
+}
@@ -0,0 +1,112 @@
+use super::Spinner;
+use crate::{Model, Msg, Style, Tick};
+
+const SUCCESS_STYLE: Style = Style::new().green();
+const FAILURE_STYLE: Style = Style::new().red();
+
+/// A line showing the status of a task, with a [`Spinner`] before a label while it is running
+/// and a ✓ or ✗ once it has succeeded or failed.
+///
+/// ```
+/// # use sketch::{widgets::Status, Model};
+/// let mut status = Status::new("Downloading…");
+/// status.set_label("Downloaded");
+/// status.succeed();
+/// assert_eq!(status.view(), "\x1b[92m✓\x1b[0m Downloaded");
+/// ```
+pub struct Status {
+    spinner: Spinner,
+    label: String,
+    state: State,
+}
+
+/// Where the task shown by a [`Status`] is up to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl Status {
+    /// Create a new running [`Status`] with a label.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            spinner: Spinner::new(),
+            label: label.into(),
+            state: State::Running,
+        }
+    }
+
+    /// Set the style of the spinner shown while running. Defaults to [`Style::new`].
+    pub fn with_spinner_style(mut self, style: Style) -> Self {
+        self.spinner = self.spinner.with_style(style);
+        self
+    }
+
+    /// Change the label shown after the spinner or final symbol.
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = label.into();
+    }
+
+    /// Show the task as having succeeded with a green ✓.
+    pub fn succeed(&mut self) {
+        self.state = State::Succeeded;
+    }
+
+    /// Show the task as having failed with a red ✗.
+    pub fn fail(&mut self) {
+        self.state = State::Failed;
+    }
+
+    /// Is the task still running.
+    pub fn is_running(&self) -> bool {
+        self.state == State::Running
+    }
+
+    /// Move the spinner on to its next frame, as is done for each [`Tick`].
+    pub fn tick(&mut self) {
+        if self.is_running() {
+            self.spinner.tick();
+        }
+    }
+}
+
+impl Model for Status {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if msg.is::<Tick>() {
+            self.tick();
+        }
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        let symbol = match self.state {
+            State::Running => self.spinner.view(),
+            State::Succeeded => SUCCESS_STYLE.render("✓"),
+            State::Failed => FAILURE_STYLE.render("✗"),
+        };
+        format!("{symbol} {}", self.label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_shows_spinner() {
+        let status = Status::new("Loading");
+        let (status, _) = status.update(&Msg::new(Tick));
+        assert_eq!(status.view(), "⠙\x1b[0m Loading");
+    }
+
+    #[test]
+    fn test_failed_stops_spinner() {
+        let mut status = Status::new("Saving");
+        status.fail();
+        status.tick();
+        assert!(!status.is_running());
+        assert_eq!(status.view(), "\x1b[91m✗\x1b[0m Saving");
+    }
+}
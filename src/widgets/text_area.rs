@@ -0,0 +1,295 @@
+use super::{
+    editor::{Edit, Editor},
+    Focusable,
+};
+use crate::{text, Key, KeyCode, Model, Msg};
+use std::ops::Range;
+
+/// Several lines of editable text, such as for a message or a description.
+///
+/// Lines longer than the width of the area are wrapped, and it scrolls to keep the cursor shown.
+/// Editing works like [`TextInput`](super::TextInput) with up and down also moving between rows
+/// and enter starting a new line. Holding shift while moving selects text, including across
+/// rows, which ctrl+c copies and ctrl+x cuts by sending a
+/// [`SetClipboard`](crate::SetClipboard) message.
+///
+/// ```
+/// # use sketch::{widgets::TextArea, Model};
+/// let area = TextArea::new().with_size(20, 5).with_value("Dear sketch,\n");
+/// assert_eq!(area.value().lines().count(), 1);
+/// println!("{}", area.view());
+/// ```
+pub struct TextArea {
+    editor: Editor,
+    width: usize,
+    height: usize,
+    y_offset: usize,
+    focused: bool,
+}
+
+impl TextArea {
+    /// Create a new empty [`TextArea`].
+    pub fn new() -> Self {
+        Self {
+            editor: Editor::default(),
+            width: 40,
+            height: 5,
+            y_offset: 0,
+            focused: true,
+        }
+    }
+
+    /// Set the starting text, with the cursor at the end.
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Set the number of columns and rows to show. Defaults to 40 by 5.
+    pub fn with_size(mut self, width: usize, height: usize) -> Self {
+        self.set_size(width, height);
+        self
+    }
+
+    /// Set the number of columns and rows to show.
+    pub fn set_size(&mut self, width: usize, height: usize) {
+        self.width = width.max(1);
+        self.height = height.max(1);
+        self.scroll_to_cursor();
+    }
+
+    /// The text that has been entered.
+    pub fn value(&self) -> &str {
+        &self.editor.text
+    }
+
+    /// Replace the text, moving the cursor to the end and clearing the selection.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.editor.set_text(value.into());
+        self.scroll_to_cursor();
+    }
+
+    /// The position of the cursor as a byte offset into [`TextArea::value`].
+    pub fn cursor(&self) -> usize {
+        self.editor.cursor
+    }
+
+    /// The bytes of [`TextArea::value`] which are selected, if any are.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        self.editor.selection()
+    }
+
+    /// The text which is selected, if any is.
+    pub fn selected_text(&self) -> Option<&str> {
+        self.editor.selected_text()
+    }
+
+    /// Select all of the text.
+    pub fn select_all(&mut self) {
+        self.editor.select_all();
+    }
+
+    /// A [`SetClipboard`](crate::SetClipboard) message to copy the selection, if anything is
+    /// selected. This is what ctrl+c sends.
+    pub fn copy(&self) -> Option<Msg> {
+        self.editor.copy()
+    }
+
+    /// Remove the selection, returning a [`SetClipboard`](crate::SetClipboard) message to copy
+    /// it. This is what ctrl+x sends.
+    pub fn cut(&mut self) -> Option<Msg> {
+        let msg = self.editor.cut();
+        self.scroll_to_cursor();
+        msg
+    }
+
+    /// The bytes of the text shown on each row once wrapped, not including newlines.
+    fn rows(&self) -> Vec<Range<usize>> {
+        let mut rows = Vec::new();
+        let mut start = 0;
+        let mut width = 0;
+        for (n, cluster) in text::clusters(&self.editor.text) {
+            if cluster == "\n" {
+                rows.push(start..n);
+                start = n + 1;
+                width = 0;
+                continue;
+            }
+            let cluster_width = text::width(cluster);
+            if width > 0 && width + cluster_width > self.width {
+                rows.push(start..n);
+                start = n;
+                width = 0;
+            }
+            width += cluster_width;
+        }
+        rows.push(start..self.editor.text.len());
+        rows
+    }
+
+    /// The row the cursor is on. A cursor where a long line is wrapped is on the later row.
+    fn cursor_row(&self, rows: &[Range<usize>]) -> usize {
+        let cursor = self.editor.cursor;
+        rows.iter()
+            .rposition(|row| row.start <= cursor)
+            .unwrap_or(0)
+    }
+
+    /// Move the cursor to the row above or below, keeping it in about the same column.
+    fn move_vertically(&mut self, down: bool, extend: bool) {
+        let rows = self.rows();
+        let row = self.cursor_row(&rows);
+        let target = match down {
+            true if row + 1 < rows.len() => row + 1,
+            false if row > 0 => row - 1,
+            // Go to the start or end from the first or last row.
+            true => return self.editor.move_to(self.editor.text.len(), extend),
+            false => return self.editor.move_to(0, extend),
+        };
+
+        let text = &self.editor.text;
+        let column = text::width(&text[rows[row].start..self.editor.cursor]);
+        let range = rows[target].clone();
+        let mut position = range.start;
+        let mut width = 0;
+        for (n, cluster) in text::clusters(&text[range.clone()]) {
+            width += text::width(cluster);
+            if width > column {
+                break;
+            }
+            position = range.start + n + cluster.len();
+        }
+        // The end of a wrapped row is shown at the start of the next one.
+        let wrapped = rows
+            .get(target + 1)
+            .is_some_and(|next| next.start == range.end);
+        if wrapped && position == range.end && position > range.start {
+            position = text::prev_boundary(text, position);
+        }
+        self.editor.move_to(position, extend);
+    }
+
+    /// Scroll so the row with the cursor is shown.
+    fn scroll_to_cursor(&mut self) {
+        let row = self.cursor_row(&self.rows());
+        if row < self.y_offset {
+            self.y_offset = row;
+        } else if row >= self.y_offset + self.height {
+            self.y_offset = row + 1 - self.height;
+        }
+    }
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// When unfocused the text can't be edited and the cursor and selection aren't shown.
+impl Focusable for TextArea {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn blur(&mut self) {
+        self.focused = false;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl Model for TextArea {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if !self.focused {
+            return (self, None);
+        }
+
+        #[cfg(feature = "paste")]
+        if let Some(crate::Paste(text)) = msg.cast() {
+            self.editor.insert(text);
+            self.scroll_to_cursor();
+            return (self, None);
+        }
+
+        let Some(key) = msg.cast::<Key>().filter(|key| !key.is_release()) else {
+            return (self, None);
+        };
+        let msg = match key.code {
+            KeyCode::Up => {
+                self.move_vertically(false, key.with_shift());
+                None
+            }
+            KeyCode::Down => {
+                self.move_vertically(true, key.with_shift());
+                None
+            }
+            KeyCode::Enter => {
+                self.editor.insert("\n");
+                None
+            }
+            _ => match self.editor.handle_key(key) {
+                Edit::Send(msg) => Some(msg),
+                Edit::Handled | Edit::Ignored => None,
+            },
+        };
+        self.scroll_to_cursor();
+        (self, msg)
+    }
+
+    fn view(&self) -> String {
+        let rows = self.rows();
+        let cursor_row = self.cursor_row(&rows);
+        rows.into_iter()
+            .enumerate()
+            .skip(self.y_offset)
+            .take(self.height)
+            .map(|(n, row)| self.editor.render(row, self.focused, n == cursor_row))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    fn press(area: TextArea, code: KeyCode, modifiers: KeyModifiers) -> TextArea {
+        area.update(&Msg::new(Key::from(KeyEvent::new(code, modifiers))))
+            .0
+    }
+
+    #[test]
+    fn test_wraps_and_scrolls_to_cursor() {
+        let area = TextArea::new().with_size(3, 2).with_value("abcdefg");
+        assert_eq!(area.rows(), [0..3, 3..6, 6..7]);
+        assert_eq!(area.view(), "def\ng\x1b[7m \x1b[0m");
+        let area = press(area, KeyCode::Up, KeyModifiers::NONE);
+        let area = press(area, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(area.cursor(), 1);
+        assert_eq!(area.view(), "a\x1b[7mb\x1b[0mc\ndef");
+    }
+
+    #[test]
+    fn test_selection_across_wrapped_rows() {
+        let area = TextArea::new().with_size(3, 3).with_value("abc世\nx");
+        let area = press(area, KeyCode::Up, KeyModifiers::SHIFT);
+        assert_eq!(area.selected_text(), Some("世\nx"));
+        assert_eq!(area.view(), "abc\n\x1b[7m世\x1b[0m\n\x1b[7mx\x1b[0m");
+        let area = press(area, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(area.value(), "abc");
+    }
+
+    #[test]
+    fn test_enter_starts_new_line() {
+        let area = TextArea::new().with_value("ab");
+        let area = press(area, KeyCode::Left, KeyModifiers::NONE);
+        let area = press(area, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(area.value(), "a\nb");
+        let area = press(area, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(area.cursor(), 0);
+    }
+}
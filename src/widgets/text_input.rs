@@ -0,0 +1,226 @@
+use super::{
+    editor::{Edit, Editor},
+    Focusable,
+};
+use crate::{Key, Model, Msg, Style};
+use std::ops::Range;
+
+const PROMPT_STYLE: Style = Style::new().bold();
+
+/// A single line of editable text, such as for a name or a search.
+///
+/// Typing inserts at the cursor, left and right move it and home and end go to the start and
+/// end. Holding shift while moving selects text, which ctrl+c copies and ctrl+x cuts by sending
+/// a [`SetClipboard`](crate::SetClipboard) message. Typing or deleting replaces the selection
+/// and ctrl+a selects everything.
+///
+/// ```
+/// # use sketch::{widgets::TextInput, Model};
+/// let input = TextInput::new().with_value("sketch");
+/// assert_eq!(input.value(), "sketch");
+/// println!("{}", input.view());
+/// ```
+pub struct TextInput {
+    editor: Editor,
+    prompt: String,
+    prompt_style: Style,
+    focused: bool,
+}
+
+impl TextInput {
+    /// Create a new empty [`TextInput`].
+    pub fn new() -> Self {
+        Self {
+            editor: Editor::default(),
+            prompt: String::from("> "),
+            prompt_style: PROMPT_STYLE,
+            focused: true,
+        }
+    }
+
+    /// Set the starting text, with the cursor at the end.
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Set the text shown before the value. Defaults to `"> "`.
+    pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Set the style of the prompt. Defaults to bold.
+    pub fn with_prompt_style(mut self, style: Style) -> Self {
+        self.prompt_style = style;
+        self
+    }
+
+    /// The text that has been entered.
+    pub fn value(&self) -> &str {
+        &self.editor.text
+    }
+
+    /// Replace the text, moving the cursor to the end and clearing the selection.
+    ///
+    /// Newlines are replaced with spaces since the input is a single line.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.editor.set_text(value.into().replace('\n', " "));
+    }
+
+    /// The position of the cursor as a byte offset into [`TextInput::value`].
+    pub fn cursor(&self) -> usize {
+        self.editor.cursor
+    }
+
+    /// The bytes of [`TextInput::value`] which are selected, if any are.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        self.editor.selection()
+    }
+
+    /// The text which is selected, if any is.
+    pub fn selected_text(&self) -> Option<&str> {
+        self.editor.selected_text()
+    }
+
+    /// Select all of the text.
+    pub fn select_all(&mut self) {
+        self.editor.select_all();
+    }
+
+    /// A [`SetClipboard`](crate::SetClipboard) message to copy the selection, if anything is
+    /// selected. This is what ctrl+c sends.
+    pub fn copy(&self) -> Option<Msg> {
+        self.editor.copy()
+    }
+
+    /// Remove the selection, returning a [`SetClipboard`](crate::SetClipboard) message to copy
+    /// it. This is what ctrl+x sends.
+    pub fn cut(&mut self) -> Option<Msg> {
+        self.editor.cut()
+    }
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// When unfocused the text can't be edited and the cursor and selection aren't shown.
+impl Focusable for TextInput {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn blur(&mut self) {
+        self.focused = false;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl Model for TextInput {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        if !self.focused {
+            return (self, None);
+        }
+
+        #[cfg(feature = "paste")]
+        if let Some(crate::Paste(text)) = msg.cast() {
+            self.editor.insert(&text.replace('\n', " "));
+            return (self, None);
+        }
+
+        let Some(key) = msg.cast::<Key>().filter(|key| !key.is_release()) else {
+            return (self, None);
+        };
+        match self.editor.handle_key(key) {
+            Edit::Send(msg) => (self, Some(msg)),
+            Edit::Handled | Edit::Ignored => (self, None),
+        }
+    }
+
+    fn view(&self) -> String {
+        let value = self
+            .editor
+            .render(0..self.editor.text.len(), self.focused, true);
+        format!("{}{value}", self.prompt_style.render(&self.prompt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KeyCode, SetClipboard};
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    fn press(input: TextInput, code: KeyCode, modifiers: KeyModifiers) -> (TextInput, Option<Msg>) {
+        input.update(&Msg::new(Key::from(KeyEvent::new(code, modifiers))))
+    }
+
+    #[test]
+    fn test_shift_arrows_select() {
+        let input = TextInput::new().with_value("hello");
+        let (input, _) = press(input, KeyCode::Left, KeyModifiers::SHIFT);
+        let (input, _) = press(input, KeyCode::Left, KeyModifiers::SHIFT);
+        assert_eq!(input.selection(), Some(3..5));
+        assert_eq!(input.selected_text(), Some("lo"));
+        assert_eq!(input.view(), "\x1b[1m> \x1b[0mhel\x1b[7mlo\x1b[0m");
+
+        let (input, _) = press(input, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(input.selection(), None);
+        assert_eq!(input.cursor(), 3);
+    }
+
+    #[test]
+    fn test_typing_replaces_selection() {
+        let input = TextInput::new().with_value("e\u{301}a");
+        let (input, _) = press(input, KeyCode::Home, KeyModifiers::NONE);
+        let (input, _) = press(input, KeyCode::Right, KeyModifiers::SHIFT);
+        assert_eq!(input.selected_text(), Some("e\u{301}"));
+        let (input, _) = press(input, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(input.value(), "xa");
+        let (input, _) = press(input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "a");
+    }
+
+    #[test]
+    fn test_cut_sends_clipboard() {
+        let input = TextInput::new().with_value("abc");
+        let (input, _) = press(input, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        let (input, msg) = press(input, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert_eq!(msg.unwrap().cast::<SetClipboard>().unwrap().0, "abc");
+        assert_eq!(input.value(), "");
+        let (_, msg) = press(input, KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(msg.is_none());
+    }
+
+    #[test]
+    fn test_backspace_multi_byte_characters() {
+        // A precomposed é, then an e with a combining accent.
+        let input = TextInput::new().with_value("a\u{e9}e\u{301}");
+        let (input, _) = press(input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "a\u{e9}");
+        let (input, _) = press(input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "a");
+        assert_eq!(input.cursor(), 1);
+    }
+
+    #[test]
+    fn test_backspace_and_delete_emoji() {
+        let input = TextInput::new().with_value("👍🏽🇳🇿👩\u{200d}👧");
+        let (input, _) = press(input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "👍🏽🇳🇿");
+        let (input, _) = press(input, KeyCode::Home, KeyModifiers::NONE);
+        let (input, _) = press(input, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(input.value(), "🇳🇿");
+        let (input, _) = press(input, KeyCode::Right, KeyModifiers::NONE);
+        let (input, _) = press(input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "");
+        let (input, _) = press(input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "");
+    }
+}
@@ -0,0 +1,327 @@
+use super::Focusable;
+use crate::{Key, KeyCode, Model, Msg, Style};
+
+const SELECTED_STYLE: Style = Style::new().reverse();
+const COLLAPSED_STYLE: Style = Style::new().dim();
+
+/// A node of a [`Tree`], holding a value and the nodes below it.
+#[derive(Debug, Clone)]
+pub struct TreeNode<T> {
+    /// The value shown for this node.
+    pub value: T,
+    /// The nodes below this one.
+    pub children: Vec<TreeNode<T>>,
+    expanded: bool,
+}
+
+impl<T> TreeNode<T> {
+    /// Create a new [`TreeNode`] without any children.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            children: Vec::new(),
+            expanded: true,
+        }
+    }
+
+    /// Add nodes below this one.
+    pub fn with_children(mut self, children: impl IntoIterator<Item = TreeNode<T>>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Set whether the children of this node are shown. Defaults to `true`.
+    pub fn with_expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// Are the children of this node shown.
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+}
+
+/// A hierarchy drawn with `├─`, `└─` and `│` connecting each node to its parent, such as a file
+/// tree.
+///
+/// Up and down move the selection between the shown nodes. Right expands the selected node or
+/// moves to its first child, left collapses it or moves to its parent, and enter toggles it.
+/// Collapsed nodes with children are shown with a `…` after their label.
+///
+/// ```
+/// # use sketch::{widgets::{Tree, TreeNode}, Model};
+/// let tree = Tree::new(
+///     [TreeNode::new("src").with_children([
+///         TreeNode::new("lib.rs"),
+///         TreeNode::new("widgets").with_children([TreeNode::new("tree.rs")]),
+///     ])],
+///     |name| name.to_string(),
+/// );
+/// assert_eq!(tree.selected(), Some(&"src"));
+/// println!("{}", tree.view());
+/// ```
+pub struct Tree<T> {
+    roots: Vec<TreeNode<T>>,
+    label: Box<dyn Fn(&T) -> String>,
+    selected: Vec<usize>,
+    focused: bool,
+}
+
+/// A node shown by a [`Tree`].
+struct Row {
+    /// The index of the node among its siblings at each level of the tree.
+    path: Vec<usize>,
+    /// The connectors drawn before the node's label.
+    prefix: String,
+}
+
+impl<T> Tree<T> {
+    /// Create a new [`Tree`] from its top level nodes, using `label` to get the text shown for
+    /// each node.
+    pub fn new(
+        roots: impl IntoIterator<Item = TreeNode<T>>,
+        label: impl Fn(&T) -> String + 'static,
+    ) -> Self {
+        let roots: Vec<_> = roots.into_iter().collect();
+        Self {
+            selected: if roots.is_empty() { vec![] } else { vec![0] },
+            roots,
+            label: Box::new(label),
+            focused: true,
+        }
+    }
+
+    /// The top level nodes.
+    pub fn roots(&self) -> &[TreeNode<T>] {
+        &self.roots
+    }
+
+    /// The value of the selected node, or `None` if the tree is empty.
+    pub fn selected(&self) -> Option<&T> {
+        self.node(&self.selected).map(|node| &node.value)
+    }
+
+    /// The index of the selected node among its siblings at each level of the tree, starting
+    /// with the top level.
+    pub fn selected_path(&self) -> &[usize] {
+        &self.selected
+    }
+
+    /// Show or hide the children of the selected node.
+    pub fn set_expanded(&mut self, expanded: bool) {
+        if let Some(node) = self.node_mut() {
+            node.expanded = expanded;
+        }
+    }
+
+    fn node(&self, path: &[usize]) -> Option<&TreeNode<T>> {
+        let (first, rest) = path.split_first()?;
+        let mut node = self.roots.get(*first)?;
+        for &n in rest {
+            node = node.children.get(n)?;
+        }
+        Some(node)
+    }
+
+    /// The selected node.
+    fn node_mut(&mut self) -> Option<&mut TreeNode<T>> {
+        let (first, rest) = self.selected.split_first()?;
+        let mut node = self.roots.get_mut(*first)?;
+        for &n in rest {
+            node = node.children.get_mut(n)?;
+        }
+        Some(node)
+    }
+
+    /// The nodes which are shown, in order.
+    fn rows(&self) -> Vec<Row> {
+        fn walk<T>(
+            nodes: &[TreeNode<T>],
+            path: &mut Vec<usize>,
+            guides: &str,
+            rows: &mut Vec<Row>,
+        ) {
+            for (n, node) in nodes.iter().enumerate() {
+                let last = n + 1 == nodes.len();
+                path.push(n);
+                // The top level nodes aren't connected to anything.
+                let (prefix, child_guides) = match path.len() {
+                    1 => (String::new(), String::new()),
+                    _ if last => (format!("{guides}└─ "), format!("{guides}   ")),
+                    _ => (format!("{guides}├─ "), format!("{guides}│  ")),
+                };
+                rows.push(Row {
+                    path: path.clone(),
+                    prefix,
+                });
+                if node.expanded {
+                    walk(&node.children, path, &child_guides, rows);
+                }
+                path.pop();
+            }
+        }
+
+        let mut rows = Vec::new();
+        walk(&self.roots, &mut Vec::new(), "", &mut rows);
+        rows
+    }
+
+    /// Move the selection by `n` shown nodes, up if negative.
+    fn move_selection(&mut self, n: isize) {
+        let rows = self.rows();
+        let Some(current) = rows.iter().position(|row| row.path == self.selected) else {
+            return;
+        };
+        let next = current.saturating_add_signed(n).min(rows.len() - 1);
+        self.selected.clone_from(&rows[next].path);
+    }
+
+    fn expand_or_enter(&mut self) {
+        let Some(node) = self.node_mut() else {
+            return;
+        };
+        if node.children.is_empty() {
+            return;
+        }
+        if node.expanded {
+            self.selected.push(0);
+        } else {
+            node.expanded = true;
+        }
+    }
+
+    fn collapse_or_leave(&mut self) {
+        let Some(node) = self.node_mut() else {
+            return;
+        };
+        if node.expanded && !node.children.is_empty() {
+            node.expanded = false;
+        } else if self.selected.len() > 1 {
+            self.selected.pop();
+        }
+    }
+}
+
+/// When unfocused the selection can't be moved and isn't highlighted.
+impl<T> Focusable for Tree<T> {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn blur(&mut self) {
+        self.focused = false;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl<T> Model for Tree<T> {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        let Some(key) = msg.cast::<Key>().filter(|_| self.focused) else {
+            return (self, None);
+        };
+        if key.is_release() {
+            return (self, None);
+        }
+
+        match key.code {
+            KeyCode::Enter => {
+                if let Some(node) = self.node_mut() {
+                    node.expanded = !node.expanded;
+                }
+            }
+            _ if key.is_up() => self.move_selection(-1),
+            _ if key.is_down() => self.move_selection(1),
+            _ if key.is_right() => self.expand_or_enter(),
+            _ if key.is_left() => self.collapse_or_leave(),
+            _ => {}
+        }
+
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        self.rows()
+            .into_iter()
+            .filter_map(|row| {
+                let node = self.node(&row.path)?;
+                let mut label = (self.label)(&node.value);
+                if self.focused && row.path == self.selected {
+                    label = SELECTED_STYLE.render(label);
+                }
+                if !node.expanded && !node.children.is_empty() {
+                    label.push_str(&COLLAPSED_STYLE.render(" …"));
+                }
+                Some(format!("{}{label}", row.prefix))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    fn press(tree: Tree<&'static str>, code: KeyCode) -> Tree<&'static str> {
+        let key = Key::from(KeyEvent::new(code, KeyModifiers::NONE));
+        tree.update(&Msg::new(key)).0
+    }
+
+    fn files() -> Tree<&'static str> {
+        let mut tree = Tree::new(
+            [TreeNode::new("src").with_children([
+                TreeNode::new("widgets").with_children([TreeNode::new("mod.rs")]),
+                TreeNode::new("lib.rs"),
+            ])],
+            |name| name.to_string(),
+        );
+        tree.blur();
+        tree
+    }
+
+    #[test]
+    fn test_connectors() {
+        let tree = files();
+        assert_eq!(tree.view(), "src\n├─ widgets\n│  └─ mod.rs\n└─ lib.rs");
+    }
+
+    #[test]
+    fn test_last_child_has_no_guide() {
+        let mut tree = Tree::new(
+            [TreeNode::new("a").with_children([
+                TreeNode::new("b"),
+                TreeNode::new("c").with_children([TreeNode::new("d"), TreeNode::new("e")]),
+            ])],
+            |name| name.to_string(),
+        );
+        tree.blur();
+        assert_eq!(tree.view(), "a\n├─ b\n└─ c\n   ├─ d\n   └─ e");
+    }
+
+    #[test]
+    fn test_arrow_keys_navigate_and_collapse() {
+        let mut tree = files();
+        tree.focus();
+        let tree = press(tree, KeyCode::Right);
+        assert_eq!(tree.selected(), Some(&"widgets"));
+        let tree = press(tree, KeyCode::Left);
+        let tree = press(tree, KeyCode::Down);
+        assert_eq!(tree.selected(), Some(&"lib.rs"));
+        assert_eq!(tree.selected_path(), [0, 1]);
+
+        let mut tree = press(tree, KeyCode::Left);
+        tree.blur();
+        assert_eq!(tree.view(), "src\n├─ widgets\x1b[2m …\x1b[0m\n└─ lib.rs");
+        tree.focus();
+        let tree = press(tree, KeyCode::Left);
+        assert_eq!(tree.selected(), Some(&"src"));
+        assert!(!tree.roots()[0].is_expanded());
+        let tree = press(tree, KeyCode::Down);
+        assert_eq!(tree.selected(), Some(&"src"));
+    }
+}
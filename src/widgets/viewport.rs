@@ -0,0 +1,461 @@
+use super::Focusable;
+use crate::{
+    capabilities,
+    text::{self, highlight_columns, slice_columns, Token},
+    wrap_text, Key, KeyCode, Model, Msg, Style,
+};
+
+const MATCH_STYLE: Style = Style::new().reverse();
+const CURRENT_MATCH_STYLE: Style = Style::new().reverse().bold();
+
+/// A scrollable view of text larger than the space it is shown in, such as a log or a file.
+///
+/// Up and down (or `j` and `k` with [vim keys](crate::set_vim_keys)), page up and page down
+/// scroll vertically, with ctrl+home and ctrl+end going to the top and bottom. Lines are wrapped
+/// to the width of the viewport unless horizontal scrolling is enabled, in which case left and
+/// right scroll sideways and home and end go to the start and end of the lines.
+///
+/// Text can be searched for with [`Viewport::search`], which highlights the matches and scrolls
+/// to them. While searching `n` and `N` go to the next and previous match.
+///
+/// ```
+/// # use sketch::{widgets::Viewport, Model};
+/// let mut viewport = Viewport::new("one\ntwo\nthree").with_size(10, 2);
+/// viewport.scroll_down(1);
+/// assert_eq!(viewport.view(), "two\nthree");
+/// ```
+pub struct Viewport {
+    content: String,
+    lines: Vec<String>,
+    width: usize,
+    height: usize,
+    y_offset: usize,
+    x_offset: usize,
+    horizontal_scroll: bool,
+    focused: bool,
+    query: Vec<char>,
+    case_sensitive: bool,
+    matches: Vec<Match>,
+    current_match: usize,
+}
+
+/// Where a search match is, in columns of one of the laid out lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Match {
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Viewport {
+    /// Create a new [`Viewport`] showing `content`.
+    pub fn new(content: impl Into<String>) -> Self {
+        let mut viewport = Self {
+            content: content.into(),
+            lines: Vec::new(),
+            width: 80,
+            height: 24,
+            y_offset: 0,
+            x_offset: 0,
+            horizontal_scroll: false,
+            focused: true,
+            query: Vec::new(),
+            case_sensitive: false,
+            matches: Vec::new(),
+            current_match: 0,
+        };
+        viewport.layout();
+        viewport
+    }
+
+    /// Set the number of columns and rows to show. Defaults to 80 by 24.
+    pub fn with_size(mut self, width: usize, height: usize) -> Self {
+        self.set_size(width, height);
+        self
+    }
+
+    /// Set whether long lines can be scrolled sideways instead of being wrapped. Defaults to
+    /// `false`.
+    pub fn with_horizontal_scroll(mut self, horizontal_scroll: bool) -> Self {
+        self.horizontal_scroll = horizontal_scroll;
+        self.layout();
+        self
+    }
+
+    /// Set whether searches match upper and lower case letters exactly. Defaults to `false`.
+    pub fn with_case_sensitive_search(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self.find_matches();
+        self
+    }
+
+    /// Set the number of columns and rows to show.
+    pub fn set_size(&mut self, width: usize, height: usize) {
+        self.width = width.max(1);
+        self.height = height.max(1);
+        self.layout();
+    }
+
+    /// Replace the text being shown, keeping the scroll position where possible.
+    pub fn set_content(&mut self, content: impl Into<String>) {
+        self.content = content.into();
+        self.layout();
+    }
+
+    /// The first line shown.
+    pub fn y_offset(&self) -> usize {
+        self.y_offset
+    }
+
+    /// The first column shown.
+    pub fn x_offset(&self) -> usize {
+        self.x_offset
+    }
+
+    /// Scroll up by `n` lines.
+    pub fn scroll_up(&mut self, n: usize) {
+        self.y_offset = self.y_offset.saturating_sub(n);
+    }
+
+    /// Scroll down by `n` lines.
+    pub fn scroll_down(&mut self, n: usize) {
+        self.y_offset = (self.y_offset + n).min(self.max_y_offset());
+    }
+
+    /// Scroll to the first line.
+    pub fn goto_top(&mut self) {
+        self.y_offset = 0;
+    }
+
+    /// Scroll to the last line.
+    pub fn goto_bottom(&mut self) {
+        self.y_offset = self.max_y_offset();
+    }
+
+    /// Scroll left by `n` columns, if horizontal scrolling is enabled.
+    pub fn scroll_left(&mut self, n: usize) {
+        self.x_offset = self.x_offset.saturating_sub(n);
+    }
+
+    /// Scroll right by `n` columns, if horizontal scrolling is enabled.
+    ///
+    /// This stops once the end of the widest line is shown.
+    pub fn scroll_right(&mut self, n: usize) {
+        self.x_offset = (self.x_offset + n).min(self.max_x_offset());
+    }
+
+    /// Scroll to the start of the lines.
+    pub fn goto_line_start(&mut self) {
+        self.x_offset = 0;
+    }
+
+    /// Scroll to the end of the widest line.
+    pub fn goto_line_end(&mut self) {
+        self.x_offset = self.max_x_offset();
+    }
+
+    /// Highlight each place `query` is found and scroll to the first one at or after the top of
+    /// the view. An empty query clears the search.
+    ///
+    /// Matches are found in the text as it is shown, ignoring styling, so a match can't span two
+    /// lines, including where a long line has been wrapped.
+    ///
+    /// ```
+    /// # use sketch::widgets::Viewport;
+    /// let mut viewport = Viewport::new("one\ntwo\nthree").with_size(10, 1);
+    /// viewport.search("T");
+    /// assert_eq!(viewport.match_count(), 2);
+    /// assert_eq!(viewport.y_offset(), 1);
+    /// ```
+    pub fn search(&mut self, query: &str) {
+        self.query = query.chars().collect();
+        self.find_matches();
+        self.current_match = self
+            .matches
+            .iter()
+            .position(|m| m.line >= self.y_offset)
+            .unwrap_or(0);
+        self.scroll_to_match();
+    }
+
+    /// Stop searching and remove the highlights.
+    pub fn clear_search(&mut self) {
+        self.search("");
+    }
+
+    /// The number of matches for the current search.
+    pub fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// The index of the match that was last scrolled to, or `None` if there are no matches.
+    pub fn current_match(&self) -> Option<usize> {
+        (!self.matches.is_empty()).then_some(self.current_match)
+    }
+
+    /// Scroll to the next match, going back to the first after the last.
+    pub fn next_match(&mut self) {
+        if !self.matches.is_empty() {
+            self.current_match = (self.current_match + 1) % self.matches.len();
+            self.scroll_to_match();
+        }
+    }
+
+    /// Scroll to the previous match, going to the last before the first.
+    pub fn prev_match(&mut self) {
+        if !self.matches.is_empty() {
+            let len = self.matches.len();
+            self.current_match = (self.current_match + len - 1) % len;
+            self.scroll_to_match();
+        }
+    }
+
+    /// Scroll so the current match is shown, if it isn't already.
+    fn scroll_to_match(&mut self) {
+        let Some(&Match { line, start, end }) = self.matches.get(self.current_match) else {
+            return;
+        };
+        if line < self.y_offset || line >= self.y_offset + self.height {
+            self.y_offset = line.min(self.max_y_offset());
+        }
+        if start < self.x_offset || end > self.x_offset + self.width {
+            self.x_offset = start.min(self.max_x_offset());
+        }
+    }
+
+    /// Find where the query is in the laid out lines.
+    fn find_matches(&mut self) {
+        self.matches.clear();
+        if self.query.is_empty() {
+            return;
+        }
+
+        let fold = |c: char| match self.case_sensitive {
+            true => c,
+            false => c.to_lowercase().next().unwrap_or(c),
+        };
+        let query: Vec<char> = self.query.iter().copied().map(fold).collect();
+
+        for (n, line) in self.lines.iter().enumerate() {
+            // Each visible character with the column it starts at.
+            let mut chars = Vec::new();
+            let mut col = 0;
+            for token in text::tokens(line) {
+                if let Token::Char(c) = token {
+                    chars.push((fold(c), col));
+                    col += text::char_width(c);
+                }
+            }
+
+            let mut i = 0;
+            while i + query.len() <= chars.len() {
+                let found = chars[i..i + query.len()]
+                    .iter()
+                    .zip(&query)
+                    .all(|((c, _), q)| c == q);
+                if !found {
+                    i += 1;
+                    continue;
+                }
+                let end = chars.get(i + query.len()).map_or(col, |&(_, start)| start);
+                self.matches.push(Match {
+                    line: n,
+                    start: chars[i].1,
+                    end,
+                });
+                i += query.len();
+            }
+        }
+
+        self.current_match = self.current_match.min(self.matches.len().saturating_sub(1));
+    }
+
+    /// A line with any matches in it highlighted.
+    fn highlight(&self, n: usize, line: &str) -> String {
+        let capabilities = capabilities();
+        let ranges: Vec<_> = self
+            .matches
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.line == n)
+            .map(|(i, m)| {
+                let style = match i == self.current_match {
+                    true => &CURRENT_MATCH_STYLE,
+                    false => &MATCH_STYLE,
+                };
+                (m.start, m.end, style.codes(capabilities))
+            })
+            .collect();
+        match ranges.is_empty() {
+            true => line.to_string(),
+            false => highlight_columns(line, &ranges),
+        }
+    }
+
+    fn max_y_offset(&self) -> usize {
+        self.lines.len().saturating_sub(self.height)
+    }
+
+    fn max_x_offset(&self) -> usize {
+        if !self.horizontal_scroll {
+            return 0;
+        }
+        let widest = self.lines.iter().map(|line| text::width(line)).max();
+        widest.unwrap_or(0).saturating_sub(self.width)
+    }
+
+    /// Split the content in to lines and keep the offsets in range.
+    fn layout(&mut self) {
+        self.lines = if self.horizontal_scroll {
+            self.content.split('\n').map(String::from).collect()
+        } else {
+            wrap_text(&self.content, self.width)
+        };
+        self.y_offset = self.y_offset.min(self.max_y_offset());
+        self.x_offset = self.x_offset.min(self.max_x_offset());
+        self.find_matches();
+    }
+}
+
+impl Focusable for Viewport {
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn blur(&mut self) {
+        self.focused = false;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl Model for Viewport {
+    fn update(mut self, msg: &Msg) -> (Self, Option<Msg>) {
+        let Some(key) = msg.cast::<Key>().filter(|_| self.focused) else {
+            return (self, None);
+        };
+        if key.is_release() {
+            return (self, None);
+        }
+
+        match key.code {
+            KeyCode::PageUp => self.scroll_up(self.height),
+            KeyCode::PageDown => self.scroll_down(self.height),
+            KeyCode::Home if key.with_control() => self.goto_top(),
+            KeyCode::End if key.with_control() => self.goto_bottom(),
+            KeyCode::Home => self.goto_line_start(),
+            KeyCode::End => self.goto_line_end(),
+            KeyCode::Char('n') if !self.query.is_empty() => self.next_match(),
+            KeyCode::Char('N') if !self.query.is_empty() => self.prev_match(),
+            _ if key.is_up() => self.scroll_up(1),
+            _ if key.is_down() => self.scroll_down(1),
+            _ if key.is_left() => self.scroll_left(1),
+            _ if key.is_right() => self.scroll_right(1),
+            _ => {}
+        }
+
+        (self, None)
+    }
+
+    fn view(&self) -> String {
+        let lines = self.lines.iter().enumerate().skip(self.y_offset);
+        let lines = lines
+            .take(self.height)
+            .map(|(n, line)| self.highlight(n, line));
+        if self.horizontal_scroll {
+            lines
+                .map(|line| slice_columns(&line, self.x_offset, self.width))
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else {
+            lines.collect::<Vec<_>>().join("\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vertical_scroll_is_clamped() {
+        let mut viewport = Viewport::new("1\n2\n3\n4").with_size(5, 2);
+        viewport.scroll_down(10);
+        assert_eq!(viewport.y_offset(), 2);
+        assert_eq!(viewport.view(), "3\n4");
+        viewport.scroll_up(1);
+        assert_eq!(viewport.view(), "2\n3");
+    }
+
+    #[test]
+    fn test_wraps_without_horizontal_scroll() {
+        let mut viewport = Viewport::new("one two three").with_size(7, 3);
+        assert_eq!(viewport.view(), "one two\nthree");
+        viewport.scroll_right(3);
+        assert_eq!(viewport.x_offset(), 0);
+    }
+
+    #[test]
+    fn test_horizontal_scroll() {
+        let mut viewport = Viewport::new("abcdefgh\nxy")
+            .with_size(4, 2)
+            .with_horizontal_scroll(true);
+        assert_eq!(viewport.view(), "abcd\nxy");
+        viewport.scroll_right(2);
+        assert_eq!(viewport.view(), "cdef\n");
+        viewport.goto_line_end();
+        assert_eq!(viewport.x_offset(), 4);
+        assert_eq!(viewport.view(), "efgh\n");
+        viewport.scroll_right(1);
+        assert_eq!(viewport.x_offset(), 4);
+        viewport.goto_line_start();
+        assert_eq!(viewport.x_offset(), 0);
+    }
+
+    #[test]
+    fn test_horizontal_scroll_wide_characters() {
+        let mut viewport = Viewport::new("你好世界")
+            .with_size(4, 1)
+            .with_horizontal_scroll(true);
+        viewport.scroll_right(1);
+        assert_eq!(viewport.view(), " 好 ");
+    }
+
+    #[test]
+    fn test_search_moves_between_matches() {
+        let mut viewport = Viewport::new("ab\ncd\nab\nef").with_size(5, 1);
+        viewport.search("ab");
+        assert_eq!(viewport.match_count(), 2);
+        assert_eq!(viewport.current_match(), Some(0));
+        viewport.next_match();
+        assert_eq!(viewport.y_offset(), 2);
+        viewport.next_match();
+        assert_eq!(viewport.y_offset(), 0);
+        viewport.prev_match();
+        assert_eq!(viewport.current_match(), Some(1));
+    }
+
+    #[test]
+    fn test_search_highlights_styled_and_wide_text() {
+        let mut viewport = Viewport::new("\x1b[1m世界\x1b[0m Hello").with_size(20, 1);
+        viewport.search("界 h");
+        assert_eq!(viewport.match_count(), 1);
+        assert_eq!(
+            viewport.view(),
+            "\x1b[1m世\x1b[1m\x1b[7m界\x1b[0m\x1b[1m\x1b[7m H\x1b[0mello"
+        );
+    }
+
+    #[test]
+    fn test_search_case_sensitive_and_no_match() {
+        let mut viewport = Viewport::new("Apple apple").with_case_sensitive_search(true);
+        viewport.search("apple");
+        assert_eq!(viewport.match_count(), 1);
+        viewport.search("pear");
+        assert_eq!(viewport.current_match(), None);
+        viewport.next_match();
+        assert_eq!(viewport.view(), "Apple apple");
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{terminal, Mouse, Rect};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Mutex, MutexGuard,
+};
+
+/// The number of frames drawn by running apps, used to drop zones from earlier frames.
+static FRAME: AtomicU64 = AtomicU64::new(0);
+
+/// Start a new frame, so zones registered while drawing the last one are removed.
+pub(crate) fn next_frame() {
+    FRAME.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Finds which part of the view the mouse is over, such as which button was clicked.
+///
+/// Register each clickable part with its [`Rect`] while building the view, then look up where
+/// [`Mouse`] messages landed with [`HitMap::hit_test`]. While an app is running zones are
+/// removed once the next frame is drawn, so zones which are no longer drawn can't be hit.
+/// Otherwise they are kept until they are cleared.
+///
+/// The mouse is only read if [`App::with_mouse_capture`](crate::App::with_mouse_capture) is
+/// enabled.
+///
+/// ```
+/// # use sketch::*;
+/// struct Buttons {
+///     zones: HitMap,
+/// }
+///
+/// impl Model for Buttons {
+///     fn update(self, msg: &Msg) -> (Self, Option<Msg>) {
+///         if let Some(mouse) = msg.cast::<Mouse>().filter(|mouse| mouse.is_press()) {
+///             if self.zones.hit_test(mouse).as_deref() == Some("quit") {
+///                 return (self, Some(Msg::new(Quit)));
+///             }
+///         }
+///         (self, None)
+///     }
+///
+///     fn view(&self) -> String {
+///         self.zones.register("quit", Rect::new(0, 0, 6, 1));
+///         String::from("[Quit]")
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct HitMap {
+    zones: Mutex<Zones>,
+}
+
+/// The zones of a [`HitMap`] along with the frame they were registered in.
+#[derive(Debug, Default)]
+struct Zones {
+    frame: u64,
+    zones: Vec<(String, Rect)>,
+}
+
+impl HitMap {
+    /// Create a new [`HitMap`] without any zones.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove every zone.
+    pub fn clear(&self) {
+        self.zones().zones.clear();
+    }
+
+    /// Add a zone named `name` covering `rect` of the view.
+    ///
+    /// Zones registered later are on top of earlier ones where they overlap.
+    pub fn register(&self, name: impl Into<String>, rect: Rect) {
+        self.zones().zones.push((name.into(), rect));
+    }
+
+    /// The area of the zone named `name`, if it is registered.
+    pub fn zone(&self, name: &str) -> Option<Rect> {
+        let zones = self.zones();
+        let (_, rect) = zones.zones.iter().rev().find(|(n, _)| n == name)?;
+        Some(*rect)
+    }
+
+    /// The name of the zone the mouse is over, if any.
+    ///
+    /// The mouse position is moved to match the view, such as when it is centered by
+    /// [`App::with_max_width`](crate::App::with_max_width) or drawn below the cursor by
+    /// [`App::with_inline`](crate::App::with_inline).
+    pub fn hit_test(&self, mouse: &Mouse) -> Option<String> {
+        let (column, row) = terminal::view_origin();
+        self.hit_test_at(
+            mouse.column.checked_sub(column)?,
+            mouse.row.checked_sub(row)?,
+        )
+    }
+
+    /// The name of the zone over the column and row of the view, if any.
+    pub fn hit_test_at(&self, column: u16, row: u16) -> Option<String> {
+        let zones = self.zones();
+        let (name, _) = zones
+            .zones
+            .iter()
+            .rev()
+            .find(|(_, rect)| rect.contains(column, row))?;
+        Some(name.clone())
+    }
+
+    fn zones(&self) -> MutexGuard<'_, Zones> {
+        self.zones_in(FRAME.load(Ordering::Relaxed))
+    }
+
+    /// The zones registered in `frame`, removing any from earlier frames.
+    fn zones_in(&self, frame: u64) -> MutexGuard<'_, Zones> {
+        let mut zones = self.zones.lock().unwrap_or_else(|e| e.into_inner());
+        if zones.frame != frame {
+            zones.frame = frame;
+            zones.zones.clear();
+        }
+        zones
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_test_finds_top_zone() {
+        let zones = HitMap::new();
+        zones.register("panel", Rect::new(0, 0, 10, 5));
+        zones.register("button", Rect::new(2, 1, 4, 1));
+        assert_eq!(zones.hit_test_at(3, 1).as_deref(), Some("button"));
+        assert_eq!(zones.hit_test_at(6, 1).as_deref(), Some("panel"));
+        assert_eq!(zones.hit_test_at(10, 1), None);
+        assert_eq!(zones.zone("button"), Some(Rect::new(2, 1, 4, 1)));
+    }
+
+    #[test]
+    fn test_clear_removes_zones() {
+        let zones = HitMap::new();
+        zones.register("button", Rect::new(0, 0, 4, 1));
+        zones.clear();
+        assert_eq!(zones.hit_test_at(0, 0), None);
+        assert_eq!(zones.zone("button"), None);
+    }
+
+    #[test]
+    fn test_next_frame_removes_zones() {
+        let zones = HitMap::new();
+        let frame = FRAME.load(Ordering::Relaxed);
+        zones.register("button", Rect::new(0, 0, 4, 1));
+        assert_eq!(zones.zones_in(frame).zones.len(), 1);
+        assert!(zones.zones_in(frame + 1).zones.is_empty());
+    }
+}